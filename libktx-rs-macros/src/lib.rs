@@ -4,36 +4,85 @@
 //! Utilities for testing [`libktx_rs`].
 //! a library for reading, transcoding and writing [Khronos Textures (KTX)](https://www.khronos.org/ktx/).
 
-use glob::glob;
+use glob::{glob, Pattern};
 use proc_macro::TokenStream;
 use proc_macro2::Span;
 use quote::quote;
-use std::{collections::HashSet, iter::FromIterator, ops::Sub};
+use std::{
+    collections::{HashMap, HashSet},
+    iter::FromIterator,
+};
 use syn::{
-    self,
+    self, parenthesized,
     parse::{Parse, ParseStream, Result},
     parse_macro_input,
     punctuated::Punctuated,
-    Ident, LitStr, Path, Token,
+    token, Ident, LitBool, LitStr, Path, Token,
 };
 
+/// The kind of test generated for the files matched by a [`GlobPattern`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TestKind {
+    /// A plain `#[test]`.
+    Plain,
+    /// A `#[test] #[ignore]`.
+    Ignore,
+    /// A `#[test] #[should_panic]`, or `#[should_panic(expected = "...")]` if a message is given.
+    ShouldPanic(Option<String>),
+}
+
 struct GlobPattern {
     inverted: bool,
+    kind: TestKind,
     pattern: LitStr,
 }
 
 impl Parse for GlobPattern {
     fn parse(input: ParseStream) -> Result<Self> {
+        let mut kind = TestKind::Plain;
+        if input.peek(Ident) && (input.peek2(Token![:]) || input.peek2(token::Paren)) {
+            let tag: Ident = input.parse()?;
+            kind = match tag.to_string().as_str() {
+                "ignore" => TestKind::Ignore,
+                "should_panic" if input.peek(token::Paren) => {
+                    let content;
+                    parenthesized!(content in input);
+                    TestKind::ShouldPanic(Some(content.parse::<LitStr>()?.value()))
+                }
+                "should_panic" => TestKind::ShouldPanic(None),
+                _ => return Err(syn::Error::new(tag.span(), "unknown file_tests! glob tag")),
+            };
+            input.parse::<Token![:]>()?;
+        }
         let inverted = input.parse::<Token![!]>().is_ok();
+        if inverted && kind != TestKind::Plain {
+            return Err(input.error("inverted globs cannot be tagged"));
+        }
         let pattern = input.parse()?;
-        Ok(GlobPattern { inverted, pattern })
+        Ok(GlobPattern {
+            inverted,
+            kind,
+            pattern,
+        })
     }
 }
 
+/// Returns `true` if `input` starts with a `file_tests!` option (as opposed to a tagged glob).
+fn peek_option(input: ParseStream) -> bool {
+    input.peek2(Token![:])
+        && input
+            .fork()
+            .parse::<Ident>()
+            .map(|ident| ident == "relative_to" || ident == "require_matches")
+            .unwrap_or(false)
+}
+
 type GlobPatternList = Punctuated<GlobPattern, Token![,]>;
 
 struct FileTestsInput {
     test_fn: Path,
+    relative_to: Option<LitStr>,
+    require_matches: bool,
     globs: GlobPatternList,
 }
 
@@ -41,16 +90,58 @@ impl Parse for FileTestsInput {
     fn parse(input: ParseStream) -> Result<Self> {
         let test_fn: Path = input.parse()?;
         input.parse::<Token![=>]>()?;
+
+        let mut relative_to = None;
+        let mut require_matches = false;
+        while peek_option(input) {
+            let option: Ident = input.parse()?;
+            input.parse::<Token![:]>()?;
+            match option.to_string().as_str() {
+                "relative_to" => relative_to = Some(input.parse()?),
+                "require_matches" => require_matches = input.parse::<LitBool>()?.value,
+                _ => return Err(syn::Error::new(option.span(), "unknown file_tests! option")),
+            }
+            input.parse::<Token![,]>()?;
+        }
+
         let globs: GlobPatternList = input.parse_terminated(GlobPattern::parse)?;
-        Ok(FileTestsInput { test_fn, globs })
+        Ok(FileTestsInput {
+            test_fn,
+            relative_to,
+            require_matches,
+            globs,
+        })
     }
 }
 
-fn glob_all<'a>(patterns: impl Iterator<Item = &'a GlobPattern>) -> HashSet<std::path::PathBuf> {
-    patterns
-        .filter_map(|pattern| glob(pattern.pattern.value().as_str()).ok())
-        .flat_map(|paths| paths.filter_map(|path| path.ok()))
-        .collect()
+/// Expands `$CARGO_MANIFEST_DIR` in `root` to the manifest directory of the crate being compiled.
+fn expand_root(root: &LitStr) -> Result<std::path::PathBuf> {
+    let mut root_str = root.value();
+    if root_str.contains("$CARGO_MANIFEST_DIR") {
+        let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+            .map_err(|_| syn::Error::new(root.span(), "CARGO_MANIFEST_DIR is not set"))?;
+        root_str = root_str.replace("$CARGO_MANIFEST_DIR", &manifest_dir);
+    }
+    Ok(root_str.into())
+}
+
+/// Returns the glob pattern string for `pattern`, relative to `root` (if any).
+fn pattern_string(root: Option<&std::path::Path>, pattern: &GlobPattern) -> String {
+    match root {
+        Some(root) => format!(
+            "{}/{}",
+            Pattern::escape(root.to_str().expect("Invalid root path")),
+            pattern.pattern.value()
+        ),
+        None => pattern.pattern.value(),
+    }
+}
+
+fn glob_one(pattern: &str) -> HashSet<std::path::PathBuf> {
+    match glob(pattern) {
+        Ok(paths) => paths.filter_map(|path| path.ok()).collect(),
+        Err(_) => HashSet::new(),
+    }
 }
 
 /// Collects a list of files at compile time (!), then generates a test case for each one of them.
@@ -61,20 +152,68 @@ fn glob_all<'a>(patterns: impl Iterator<Item = &'a GlobPattern>) -> HashSet<std:
 /// ````
 /// For each file matching the given glob pattern\[s\] (at compile time!), generates a `#[test]` that invokes
 /// ```rust,ignore
-/// fn test_fn(file: std::fs::File);
+/// fn test_fn(path: std::path::PathBuf, file: std::fs::File);
 /// ````
+/// `path` is the absolute path to the matched file, useful for diagnostics or for picking
+/// the expected (golden) outputs of the test.
 /// Globs preceded by `!` are inverted (matches are removed).
+///
+/// Options can be given before the globs:
+/// ```rust,ignore
+/// file_tests!(test_fn => relative_to: "$CARGO_MANIFEST_DIR", require_matches: true, "glob", ...);
+/// ````
+/// - `relative_to` makes globs relative to the given directory instead of the current one;
+///   `$CARGO_MANIFEST_DIR` is expanded to the directory of the manifest of the crate being compiled.
+/// - `require_matches` emits a compile error if any (non-inverted) glob does not match any file.
+///
+/// Globs can also be tagged, to mark the tests generated for their files as `#[ignore]` or `#[should_panic]`:
+/// ```rust,ignore
+/// file_tests!(test_fn => "good/*.ktx", ignore: "slow/*.ktx", should_panic("bad header"): "corrupt/*.ktx");
+/// ````
+/// `should_panic` can be given the expected panic message (as in `#[should_panic(expected = "...")]`),
+/// so that the test does not pass if it panics for an unrelated reason - e.g. the file failing to open.  
+/// Tags take precedence over untagged globs matching the same files (the last tag wins),
+/// so that e.g. known-corrupt files can be kept in the suite as negative tests. Inverted globs cannot be tagged.
 #[proc_macro]
 pub fn file_tests(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as FileTestsInput);
 
-    let glob_accepted = glob_all(input.globs.iter().filter(|pattern| !pattern.inverted));
-    let glob_rejected = glob_all(input.globs.iter().filter(|pattern| pattern.inverted));
-    let test_files = glob_accepted.sub(&glob_rejected);
+    let root = match input.relative_to.as_ref().map(expand_root).transpose() {
+        Ok(root) => root,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let mut glob_accepted = HashMap::new();
+    let mut glob_rejected = HashSet::new();
+    for pattern in input.globs.iter() {
+        let pattern_str = pattern_string(root.as_deref(), pattern);
+        let matches = glob_one(&pattern_str);
+        if pattern.inverted {
+            glob_rejected.extend(matches);
+        } else if input.require_matches && matches.is_empty() {
+            let message = format!("Glob pattern matches no files: {}", pattern_str);
+            return syn::Error::new(pattern.pattern.span(), message)
+                .to_compile_error()
+                .into();
+        } else {
+            for path in matches {
+                let kind = glob_accepted
+                    .entry(path)
+                    .or_insert_with(|| pattern.kind.clone());
+                if pattern.kind != TestKind::Plain {
+                    *kind = pattern.kind.clone();
+                }
+            }
+        }
+    }
+    let test_files: Vec<_> = glob_accepted
+        .into_iter()
+        .filter(|(path, _)| !glob_rejected.contains(path))
+        .collect();
 
     let test_fn_name = input.test_fn.segments.last().unwrap().ident.to_string();
 
-    let fns_tokens = test_files.iter().enumerate().map(|(i, path)| {
+    let fns_tokens = test_files.iter().enumerate().map(|(i, (path, kind))| {
         let mut fn_name = path
             .file_stem()
             .map(|name| {
@@ -99,9 +238,18 @@ pub fn file_tests(input: TokenStream) -> TokenStream {
         let abs_path = path.canonicalize().expect("Could not make absolute path");
         let path_str = abs_path.to_str().expect("Invalid path");
         let fn_ident = Ident::new(fn_name.as_str(), Span::call_site());
+        let kind_attr = match kind {
+            TestKind::Plain => quote! {},
+            TestKind::Ignore => quote! { #[ignore] },
+            TestKind::ShouldPanic(None) => quote! { #[should_panic] },
+            TestKind::ShouldPanic(Some(expected)) => {
+                quote! { #[should_panic(expected = #expected)] }
+            }
+        };
 
         quote! {
             #[test]
+            #kind_attr
             fn #fn_ident() {
                 let path = std::path::PathBuf::from(#path_str);
                 println!("Test file: {}", #path_str);
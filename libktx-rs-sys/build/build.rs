@@ -42,6 +42,11 @@ mod run_bindgen {
             //
             .clang_arg("-fparse-all-comments")
             .clang_args(INCLUDE_DIRS.iter().map(|id| format!("-I{}", id)))
+            .clang_args(if cfg!(feature = "vulkan") {
+                &["-DLIBKTX_RS_VULKAN", "-Ibuild/KTX-Software/lib/dfdutils"][..]
+            } else {
+                &[][..]
+            })
             .generate()
             .expect("generating the bindings");
 
@@ -116,8 +121,94 @@ include("../no_etc_unpack.cmake")
     }
 }
 
-#[cfg_attr(feature = "docs-only", allow(unreachable_code))]
+#[cfg(feature = "system-libktx")]
+mod system_libktx {
+    use std::path::PathBuf;
+
+    /// Links a prebuilt libKTX, found in `$KTX_LIB_DIR` (if set) or via pkg-config.  
+    /// Returns the directory it was found in, if known.
+    pub(crate) fn link(lib_kind: &str) -> Option<PathBuf> {
+        println!("cargo:rerun-if-env-changed=KTX_LIB_DIR");
+        if let Some(lib_dir) = std::env::var_os("KTX_LIB_DIR") {
+            let lib_dir = PathBuf::from(lib_dir);
+            println!("-- Link the system libKTX in {:?} to the crate", lib_dir);
+            println!("cargo:rustc-link-search=native={}", lib_dir.display());
+            println!("cargo:rustc-link-lib={}=ktx", lib_kind);
+            return Some(lib_dir);
+        }
+
+        println!("-- Link the system libKTX to the crate (via pkg-config)");
+        let mut config = pkg_config::Config::new();
+        config.statik(lib_kind == "static");
+        let library = config
+            .probe("libktx")
+            .or_else(|_| config.probe("ktx"))
+            .expect("libktx to be found by pkg-config (or set KTX_LIB_DIR to its directory)");
+        super::export_version(&library.version);
+        None
+    }
+}
+
+/// Links the ASTC encoder's static library in `lib_dir`, which is separate from libKTX when building statically
+/// (otherwise, it's built inside libktx.so).
+fn link_astcenc(lib_dir: &std::path::Path, required: bool) {
+    let astc_lib_path = match glob(format!("{}/*astcenc*.*", lib_dir.display()).as_str())
+        .expect("globbing lib/")
+        .next()
+    {
+        Some(path) => path.expect("the globbed path to be valid"),
+        None if required => panic!("{}", "[lib]astcenc*.{a,lib} to be present"),
+        None => return,
+    };
+    let astc_lib_name = astc_lib_path
+        .file_stem()
+        .expect("this path to refer to a filename")
+        .to_string_lossy();
+    let astc_lib_name = match astc_lib_name.strip_prefix("lib") {
+        Some(stripped) => stripped,
+        None => &astc_lib_name,
+    };
+
+    println!("cargo:rustc-link-lib=static={}", astc_lib_name);
+}
+
+fn link_cpp_stdlib() {
+    // Linux: GNU C++ standard library
+    #[cfg(target_os = "linux")]
+    println!("cargo:rustc-link-lib=dylib=stdc++");
+    // AppleDarwin, BSDs, Android...: LLVM's C++ standard library
+    #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+    println!("cargo:rustc-link-lib=dylib=c++");
+}
+
+/// Maps a feature toggle to a CMake boolean.
+fn on_off(enabled: bool) -> &'static str {
+    if enabled {
+        "ON"
+    } else {
+        "OFF"
+    }
+}
+
+/// Exports the version of the linked libKTX as `LIBKTX_VERSION` to the crate (see `sys::LIBKTX_VERSION`).  
+/// The last call wins.
+fn export_version(version: &str) {
+    println!("cargo:rustc-env=LIBKTX_VERSION={}", version);
+}
+
+#[cfg_attr(
+    any(feature = "docs-only", feature = "system-libktx"),
+    allow(unreachable_code)
+)]
 fn main() {
+    // The vendored KTX-Software version is stored in this crate's version metadata (e.g. `0.3.3+v4.0.0`)
+    let crate_version = std::env::var("CARGO_PKG_VERSION").expect("CARGO_PKG_VERSION to be set");
+    export_version(
+        crate_version
+            .split_once('+')
+            .map_or("unknown", |(_, ktx_version)| ktx_version),
+    );
+
     #[cfg(feature = "docs-only")]
     {
         println!("-- docs-only build; quitting");
@@ -129,12 +220,34 @@ fn main() {
     } else {
         (false, "OFF", "dylib")
     };
+
+    #[cfg(feature = "system-libktx")]
+    {
+        // Skip building KTX-Software altogether
+        let lib_dir = system_libktx::link(lib_kind);
+        if static_library {
+            if let Some(lib_dir) = lib_dir {
+                link_astcenc(&lib_dir, false);
+            }
+            link_cpp_stdlib();
+        }
+
+        #[cfg(feature = "run-bindgen")]
+        run_bindgen::generate_bindings();
+
+        println!("-- All done");
+        println!("cargo:rerun-if-changed=build/build.rs");
+        return;
+    }
+
     println!("-- Build KTX-Software");
 
     let mut lib_dir = etc_unpack::toggle(
         cmake::Config::new(SOURCE_DIR)
             .pic(true)
-            .define("KTX_FEATURE_STATIC_LIBRARY", static_library_flag),
+            .define("KTX_FEATURE_STATIC_LIBRARY", static_library_flag)
+            .define("KTX_FEATURE_GL_UPLOAD", on_off(cfg!(feature = "gl")))
+            .define("KTX_FEATURE_VK_UPLOAD", on_off(cfg!(feature = "vulkan"))),
     )
     .build();
     println!("Built {} to {:?}", lib_kind, lib_dir);
@@ -145,31 +258,9 @@ fn main() {
     println!("cargo:rustc-link-lib={}=ktx", lib_kind);
 
     if static_library {
-        // When building statically, the ASTC decoder is a separate static library
-        // (otherwise, it's built inside libktx.so)
-        let astc_lib_path = glob(format!("{}/*astcenc*.*", lib_dir.display()).as_str())
-            .expect("globbing lib/")
-            .next()
-            .expect("[lib]astcenc*.{a,lib} to be present")
-            .expect("the globbed path to be valid");
-        let astc_lib_name = astc_lib_path
-            .file_stem()
-            .expect("this path to refer to a filename")
-            .to_string_lossy();
-        let astc_lib_name = match astc_lib_name.strip_prefix("lib") {
-            Some(stripped) => stripped,
-            None => &astc_lib_name,
-        };
-
-        println!("cargo:rustc-link-lib=static={}", astc_lib_name);
+        link_astcenc(&lib_dir, true);
     }
-
-    // Linux: GNU C++ standard library
-    #[cfg(target_os = "linux")]
-    println!("cargo:rustc-link-lib=dylib=stdc++");
-    // AppleDarwin, BSDs, Android...: LLVM's C++ standard library
-    #[cfg(not(any(target_os = "linux", target_os = "windows")))]
-    println!("cargo:rustc-link-lib=dylib=c++");
+    link_cpp_stdlib();
 
     #[cfg(feature = "run-bindgen")]
     run_bindgen::generate_bindings();
@@ -5,3 +5,7 @@
 
 #include <ktx.h>
 #include <texture.h>
+
+#ifdef LIBKTX_RS_VULKAN
+#include <ktxvulkan.h>
+#endif
@@ -0,0 +1,131 @@
+/* hand-written subset of the bindings to ktxvulkan.h, matching what rust-bindgen generates */
+
+pub type VkFlags = u32;
+pub type VkFormat = ::std::os::raw::c_uint;
+pub type VkImageLayout = ::std::os::raw::c_uint;
+pub type VkImageTiling = ::std::os::raw::c_uint;
+pub type VkImageUsageFlags = VkFlags;
+pub type VkImageViewType = ::std::os::raw::c_uint;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct VkPhysicalDevice_T {
+    _unused: [u8; 0],
+}
+pub type VkPhysicalDevice = *mut VkPhysicalDevice_T;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct VkDevice_T {
+    _unused: [u8; 0],
+}
+pub type VkDevice = *mut VkDevice_T;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct VkQueue_T {
+    _unused: [u8; 0],
+}
+pub type VkQueue = *mut VkQueue_T;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct VkImage_T {
+    _unused: [u8; 0],
+}
+#[cfg(target_pointer_width = "64")]
+pub type VkImage = *mut VkImage_T;
+#[cfg(not(target_pointer_width = "64"))]
+pub type VkImage = u64;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct VkDeviceMemory_T {
+    _unused: [u8; 0],
+}
+#[cfg(target_pointer_width = "64")]
+pub type VkDeviceMemory = *mut VkDeviceMemory_T;
+#[cfg(not(target_pointer_width = "64"))]
+pub type VkDeviceMemory = u64;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct VkCommandPool_T {
+    _unused: [u8; 0],
+}
+#[cfg(target_pointer_width = "64")]
+pub type VkCommandPool = *mut VkCommandPool_T;
+#[cfg(not(target_pointer_width = "64"))]
+pub type VkCommandPool = u64;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct VkAllocationCallbacks {
+    _unused: [u8; 0],
+}
+#[doc = " @struct ktxVulkanTexture"]
+#[doc = " @brief Struct for returning information about the Vulkan texture image"]
+#[doc = "        created by the ktxTexture_VkUpload* functions."]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct ktxVulkanTexture {
+    #[doc = "< Handle to the Vulkan image created by the loader."]
+    pub image: VkImage,
+    #[doc = "< Format of the image data."]
+    pub imageFormat: VkFormat,
+    #[doc = "< Layout of the created image."]
+    pub imageLayout: VkImageLayout,
+    #[doc = "< The memory allocated for the image on the Vulkan device."]
+    pub deviceMemory: VkDeviceMemory,
+    #[doc = "< ViewType corresponding to @p image."]
+    pub viewType: VkImageViewType,
+    #[doc = "< The width of the image."]
+    pub width: u32,
+    #[doc = "< The height of the image."]
+    pub height: u32,
+    #[doc = "< The depth of the image."]
+    pub depth: u32,
+    #[doc = "< The number of MIP levels in the image."]
+    pub levelCount: u32,
+    #[doc = "< The number of array layers in the image."]
+    pub layerCount: u32,
+}
+#[doc = " @struct ktxVulkanDeviceInfo"]
+#[doc = " @brief Struct for passing information about the Vulkan device on which"]
+#[doc = "        to create images to the texture image loading functions."]
+#[doc = ""]
+#[doc = " Only ever handled through pointers returned by ktxVulkanDeviceInfo_Create()."]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct ktxVulkanDeviceInfo {
+    _unused: [u8; 0],
+}
+extern "C" {
+    pub fn ktxVulkanTexture_Destruct(
+        This: *mut ktxVulkanTexture,
+        device: VkDevice,
+        pAllocator: *const VkAllocationCallbacks,
+    );
+}
+extern "C" {
+    pub fn ktxVulkanDeviceInfo_Create(
+        physicalDevice: VkPhysicalDevice,
+        device: VkDevice,
+        queue: VkQueue,
+        cmdPool: VkCommandPool,
+        pAllocator: *const VkAllocationCallbacks,
+    ) -> *mut ktxVulkanDeviceInfo;
+}
+extern "C" {
+    pub fn ktxVulkanDeviceInfo_Destroy(This: *mut ktxVulkanDeviceInfo);
+}
+extern "C" {
+    pub fn ktxTexture_VkUploadEx(
+        This: *mut ktxTexture,
+        vdi: *mut ktxVulkanDeviceInfo,
+        vkTexture: *mut ktxVulkanTexture,
+        tiling: VkImageTiling,
+        usageFlags: VkImageUsageFlags,
+        finalLayout: VkImageLayout,
+    ) -> ktx_error_code_e;
+}
+extern "C" {
+    pub fn ktxTexture_VkUpload(
+        texture: *mut ktxTexture,
+        vdi: *mut ktxVulkanDeviceInfo,
+        vkTexture: *mut ktxVulkanTexture,
+    ) -> ktx_error_code_e;
+}
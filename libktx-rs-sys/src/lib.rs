@@ -15,6 +15,17 @@
 include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
 #[cfg(not(feature = "run-bindgen"))]
 include!("ffi.rs");
+#[cfg(all(feature = "vulkan", not(feature = "run-bindgen")))]
+include!("ffi_vulkan.rs");
+
+/// The version of the libKTX this crate was built against (e.g. `v4.0.0`).
+///
+/// This is the version of the vendored KTX-Software, unless a system libKTX was linked
+/// (see the `system-libktx` feature) and pkg-config reported its version.
+pub const LIBKTX_VERSION: &str = env!("LIBKTX_VERSION");
+
+/// Is libKTX linked statically (see the `static` feature) or dynamically?
+pub const LIBKTX_STATIC: bool = cfg!(feature = "static");
 
 pub type ktxStream__data = ktxStream__bindgen_ty_1;
 pub type ktxStream__custom_ptr = __BindgenUnionField<ktxStream__bindgen_ty_1__bindgen_ty_1>;
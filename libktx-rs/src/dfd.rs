@@ -0,0 +1,303 @@
+// Copyright (C) 2021 Paolo Jovon <paolo.jovon@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Data Format Descriptors (DFDs), which describe the layout of the data in KTX2 [`crate::Texture`]s.
+//!
+//! See the [Khronos Data Format Specification](https://www.khronos.org/registry/DataFormat/specs/1.3/dataformat.1.3.html).
+
+use crate::{
+    enums::{ColorModel, ColorPrimaries, TransferFunction},
+    KtxError,
+};
+
+/// The type of each sample (channel) of a texel, as described by a [`DfdBuilder`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DfdSampleType {
+    /// 8-bit unsigned normalized integer.
+    Byte,
+    /// 16-bit signed float.
+    Half,
+    /// 32-bit signed float.
+    Float,
+}
+
+impl DfdSampleType {
+    /// Returns the size in bytes of a sample of this type.
+    pub fn size(&self) -> u32 {
+        match self {
+            DfdSampleType::Byte => 1,
+            DfdSampleType::Half => 2,
+            DfdSampleType::Float => 4,
+        }
+    }
+}
+
+// KHR_DF_VERSIONNUMBER_1_3
+const VERSION_NUMBER: u32 = 2;
+// KHR_DF_PRIMARIES_BT709
+const PRIMARIES_BT709: u32 = 1;
+// Words in the basic descriptor block header, and in each sample
+const BASIC_HEADER_WORDS: usize = 6;
+const SAMPLE_WORDS: usize = 4;
+// KHR_DF_CHANNEL_RGBSDA_ALPHA
+const CHANNEL_ALPHA: u32 = 15;
+// KHR_DF_FLAG_ALPHA_PREMULTIPLIED
+const FLAG_ALPHA_PREMULTIPLIED: u32 = 0x1;
+// KHR_DF_SAMPLE_DATATYPE_{LINEAR, EXPONENT, SIGNED, FLOAT}
+const DATATYPE_LINEAR: u32 = 0x10;
+const DATATYPE_EXPONENT: u32 = 0x20;
+const DATATYPE_SIGNED: u32 = 0x40;
+const DATATYPE_FLOAT: u32 = 0x80;
+
+/// A builder for the basic DFD of an uncompressed format, with one to four channels (R, RG, RGB or RGBA)
+/// of the same [`DfdSampleType`].
+///
+/// The built DFD can be given to [`crate::sources::Ktx2CreateInfo::with_dfd`]. The C library only reads the DFD
+/// of textures created with [`crate::VkFormat::Undefined`]; for other formats, it generates its own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DfdBuilder {
+    num_channels: u32,
+    sample_type: DfdSampleType,
+    transfer_function: TransferFunction,
+    color_model: ColorModel,
+}
+
+impl DfdBuilder {
+    /// Returns a new builder for `num_channels` linear, 8-bit normalized channels (in the RGBSDA color model).
+    pub fn new(num_channels: u32) -> Self {
+        DfdBuilder {
+            num_channels,
+            sample_type: DfdSampleType::Byte,
+            transfer_function: TransferFunction::Linear,
+            color_model: ColorModel::Rgbsda,
+        }
+    }
+
+    /// Sets the type of every sample (channel).
+    pub fn sample_type(mut self, sample_type: DfdSampleType) -> Self {
+        self.sample_type = sample_type;
+        self
+    }
+
+    /// Sets the transfer function (e.g. [`TransferFunction::Srgb`]).  
+    /// For sRGB, the alpha channel (if any) is still marked as linear.
+    pub fn transfer_function(mut self, transfer_function: TransferFunction) -> Self {
+        self.transfer_function = transfer_function;
+        self
+    }
+
+    /// Sets the color model; channels are numbered according to it, with the 4th one always being alpha.
+    pub fn color_model(mut self, color_model: ColorModel) -> Self {
+        self.color_model = color_model;
+        self
+    }
+
+    /// Attempts to build the DFD, returning [`KtxError::InvalidValue`] if there are not 1 to 4 channels,
+    /// or if an sRGB transfer function is requested for float samples.
+    ///
+    /// The first word of the result is the total size of the DFD in bytes, as the C library expects.
+    pub fn build(&self) -> Result<Vec<u32>, KtxError> {
+        if !(1..=4).contains(&self.num_channels) {
+            return Err(KtxError::InvalidValue);
+        }
+        let is_srgb = self.transfer_function == TransferFunction::Srgb;
+        if is_srgb && self.sample_type != DfdSampleType::Byte {
+            return Err(KtxError::InvalidValue);
+        }
+
+        let num_samples = self.num_channels as usize;
+        let block_words = BASIC_HEADER_WORDS + SAMPLE_WORDS * num_samples;
+        let block_size = (block_words * std::mem::size_of::<u32>()) as u32;
+        let sample_size = self.sample_type.size();
+
+        let mut dfd = Vec::with_capacity(1 + block_words);
+        dfd.push(block_size + std::mem::size_of::<u32>() as u32);
+        // Vendor ID and descriptor type: Khronos, basic descriptor block
+        dfd.push(0);
+        dfd.push(VERSION_NUMBER | (block_size << 16));
+        dfd.push(
+            u32::from(self.color_model)
+                | (PRIMARIES_BT709 << 8)
+                | (u32::from(self.transfer_function) << 16),
+        );
+        // Texel block dimensions (minus one): 1x1x1x1
+        dfd.push(0);
+        // Bytes per plane: a single plane, holding every sample
+        dfd.push(sample_size * self.num_channels);
+        dfd.push(0);
+
+        for index in 0..self.num_channels {
+            let mut channel_type = if index == 3 { CHANNEL_ALPHA } else { index };
+            if is_srgb && index == 3 {
+                channel_type |= DATATYPE_LINEAR;
+            }
+            let (lower, upper) = match self.sample_type {
+                DfdSampleType::Byte => (0, 0xFF),
+                DfdSampleType::Half | DfdSampleType::Float => {
+                    channel_type |= DATATYPE_SIGNED | DATATYPE_FLOAT;
+                    ((-1.0f32).to_bits(), 1.0f32.to_bits())
+                }
+            };
+            let bit_offset = index * sample_size * 8;
+            let bit_length = sample_size * 8 - 1;
+            dfd.push(bit_offset | (bit_length << 16) | (channel_type << 24));
+            // Sample position: the texel's origin
+            dfd.push(0);
+            dfd.push(lower);
+            dfd.push(upper);
+        }
+        Ok(dfd)
+    }
+}
+
+/// A sample (i.e. channel, or part of one) in a [`DfdHeader`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct DfdSample {
+    /// Offset of the sample's first bit in the texel block.
+    pub bit_offset: u32,
+    /// Number of bits in the sample.
+    pub bit_length: u32,
+    /// The channel this sample belongs to; its meaning depends on [`DfdHeader::color_model`]
+    /// (e.g. 0 = red, 1 = green, 2 = blue, 15 = alpha for [`ColorModel::Rgbsda`]).
+    pub channel_id: u32,
+    /// Is the sample linear even if the transfer function is not (e.g. alpha in sRGB formats)?
+    pub is_linear: bool,
+    /// Is the sample an exponent?
+    pub is_exponent: bool,
+    pub is_signed: bool,
+    pub is_float: bool,
+    /// The (raw) values of the sample that map to the bottom and the top of its range.
+    pub lower: u32,
+    pub upper: u32,
+}
+
+/// The parsed basic descriptor block of a DFD, e.g. of [`crate::texture::Ktx2::dfd`].
+///
+/// Only the first descriptor block is parsed, as KTX2 requires it to be the basic one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DfdHeader {
+    pub color_model: ColorModel,
+    /// The raw color primaries (e.g. 1 = BT.709).
+    pub color_primaries: u32,
+    pub transfer_function: TransferFunction,
+    /// Is alpha premultiplied?
+    pub premultiplied_alpha: bool,
+    /// The dimensions of a texel block, in texels (e.g. `[4, 4, 1, 1]` for 4x4 block-compressed formats).
+    pub texel_block_dimensions: [u32; 4],
+    /// The number of bytes in each plane of a texel block.
+    pub bytes_planes: [u8; 8],
+    pub samples: Vec<DfdSample>,
+}
+
+/// Sets the transfer function in the basic descriptor block of `dfd`, in place.  
+/// For sRGB, alpha samples are marked as linear (as [`DfdBuilder`] does); otherwise, they are unmarked.
+///
+/// Fails with [`KtxError::InvalidValue`] if `dfd` can't be parsed (see [`DfdHeader::parse`]),
+/// or if `transfer_function` does not fit in a byte.
+pub(crate) fn set_transfer_function(
+    dfd: &mut [u32],
+    transfer_function: TransferFunction,
+) -> Result<(), KtxError> {
+    let header = DfdHeader::parse(dfd)?;
+    let value = u32::from(transfer_function);
+    if value > 0xFF {
+        return Err(KtxError::InvalidValue);
+    }
+    dfd[3] = (dfd[3] & !(0xFF << 16)) | (value << 16);
+
+    if header.color_model == ColorModel::Rgbsda {
+        let is_srgb = transfer_function == TransferFunction::Srgb;
+        let num_samples = header.samples.len();
+        for sample in dfd[1 + BASIC_HEADER_WORDS..]
+            .chunks_exact_mut(SAMPLE_WORDS)
+            .take(num_samples)
+        {
+            if (sample[0] >> 24) & 0xF == CHANNEL_ALPHA {
+                if is_srgb {
+                    sample[0] |= DATATYPE_LINEAR << 24;
+                } else {
+                    sample[0] &= !(DATATYPE_LINEAR << 24);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Sets the color primaries in the basic descriptor block of `dfd`, in place.
+///
+/// Fails with [`KtxError::InvalidValue`] if `dfd` can't be parsed (see [`DfdHeader::parse`]),
+/// or if `color_primaries` does not fit in a byte.
+pub(crate) fn set_color_primaries(
+    dfd: &mut [u32],
+    color_primaries: ColorPrimaries,
+) -> Result<(), KtxError> {
+    DfdHeader::parse(dfd)?;
+    let value = u32::from(color_primaries);
+    if value > 0xFF {
+        return Err(KtxError::InvalidValue);
+    }
+    dfd[3] = (dfd[3] & !(0xFF << 8)) | (value << 8);
+    Ok(())
+}
+
+impl DfdHeader {
+    /// Attempts to parse the basic descriptor block of `dfd`, whose first word is the total size of the DFD in bytes.  
+    /// Fails with [`KtxError::InvalidValue`] if `dfd` is shorter than declared, or the block is not a basic one.
+    pub fn parse(dfd: &[u32]) -> Result<Self, KtxError> {
+        let word_size = std::mem::size_of::<u32>();
+        let total_words = dfd.first().map_or(0, |&size| size as usize / word_size);
+        if total_words < 1 + BASIC_HEADER_WORDS || dfd.len() < total_words {
+            return Err(KtxError::InvalidValue);
+        }
+        // Vendor ID and descriptor type: Khronos, basic descriptor block
+        if dfd[1] != 0 {
+            return Err(KtxError::InvalidValue);
+        }
+        let block_words = (dfd[2] >> 16) as usize / word_size;
+        if block_words < BASIC_HEADER_WORDS || 1 + block_words > total_words {
+            return Err(KtxError::InvalidValue);
+        }
+
+        let bytes = |word: u32| word.to_le_bytes();
+        let dimensions = bytes(dfd[4]);
+        let (planes_low, planes_high) = (bytes(dfd[5]), bytes(dfd[6]));
+        let mut bytes_planes = [0; 8];
+        bytes_planes[..4].copy_from_slice(&planes_low);
+        bytes_planes[4..].copy_from_slice(&planes_high);
+
+        let samples = dfd[1 + BASIC_HEADER_WORDS..1 + block_words]
+            .chunks_exact(SAMPLE_WORDS)
+            .map(|sample| {
+                let channel_type = sample[0] >> 24;
+                DfdSample {
+                    bit_offset: sample[0] & 0xFFFF,
+                    bit_length: ((sample[0] >> 16) & 0xFF) + 1,
+                    channel_id: channel_type & 0xF,
+                    is_linear: channel_type & DATATYPE_LINEAR != 0,
+                    is_exponent: channel_type & DATATYPE_EXPONENT != 0,
+                    is_signed: channel_type & DATATYPE_SIGNED != 0,
+                    is_float: channel_type & DATATYPE_FLOAT != 0,
+                    lower: sample[2],
+                    upper: sample[3],
+                }
+            })
+            .collect();
+
+        Ok(DfdHeader {
+            color_model: (dfd[3] & 0xFF).into(),
+            color_primaries: (dfd[3] >> 8) & 0xFF,
+            transfer_function: ((dfd[3] >> 16) & 0xFF).into(),
+            premultiplied_alpha: (dfd[3] >> 24) & FLAG_ALPHA_PREMULTIPLIED != 0,
+            texel_block_dimensions: [
+                dimensions[0] as u32 + 1,
+                dimensions[1] as u32 + 1,
+                dimensions[2] as u32 + 1,
+                dimensions[3] as u32 + 1,
+            ],
+            bytes_planes,
+            samples,
+        })
+    }
+}
@@ -10,67 +10,132 @@ use std::{
     error::Error,
     ffi::CStr,
     fmt::{Display, Formatter},
+    str::FromStr,
 };
 
-/// Error codes as returned from the underlying C library.
+/// Defines a `#[repr(u32)]` enum whose variants wrap C enumerants, plus a `TryFrom<u32>` impl and
+/// an `ALL` constant for it - all from the same list of variants, so that they can never disagree.
 ///
-/// See [`sys::ktx_error_code_e`].
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-#[repr(u32)]
-pub enum KtxError {
-    FileDataError = sys::ktx_error_code_e_KTX_FILE_DATA_ERROR,
-    FileIsPipe = sys::ktx_error_code_e_KTX_FILE_ISPIPE,
-    FileOpenFailed = sys::ktx_error_code_e_KTX_FILE_OPEN_FAILED,
-    FileOverflow = sys::ktx_error_code_e_KTX_FILE_OVERFLOW,
-    FileReadError = sys::ktx_error_code_e_KTX_FILE_READ_ERROR,
-    FileSeekError = sys::ktx_error_code_e_KTX_FILE_SEEK_ERROR,
-    FileUnexpectedEof = sys::ktx_error_code_e_KTX_FILE_UNEXPECTED_EOF,
-    FileWriteError = sys::ktx_error_code_e_KTX_FILE_WRITE_ERROR,
-    GlError = sys::ktx_error_code_e_KTX_GL_ERROR,
-    InvalidOperation = sys::ktx_error_code_e_KTX_INVALID_OPERATION,
-    InvalidValue = sys::ktx_error_code_e_KTX_INVALID_VALUE,
-    NotFound = sys::ktx_error_code_e_KTX_NOT_FOUND,
-    OutOfMemory = sys::ktx_error_code_e_KTX_OUT_OF_MEMORY,
-    TranscodeFailed = sys::ktx_error_code_e_KTX_TRANSCODE_FAILED,
-    UnknownFileFormat = sys::ktx_error_code_e_KTX_UNKNOWN_FILE_FORMAT,
-    UnsupportedTextureType = sys::ktx_error_code_e_KTX_UNSUPPORTED_TEXTURE_TYPE,
-    UnsupportedFeature = sys::ktx_error_code_e_KTX_UNSUPPORTED_FEATURE,
-    LibraryNotLinked = sys::ktx_error_code_e_KTX_LIBRARY_NOT_LINKED,
-}
-
-impl TryFrom<u32> for KtxError {
-    type Error = &'static str;
+/// With `else $unknown, except $excluded: "error"` instead of an error message, an extra `$unknown(u32)` variant
+/// is added to catch any other value instead; the enum then converts to `u32` infallibly, and from any `u32` but
+/// `$excluded` (e.g. a success code, which is not an error).
+macro_rules! impl_try_from {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident: else $unknown:ident, except $excluded:path: $err:literal {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident = $value:path,
+            )*
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $(
+                $(#[$variant_meta])*
+                $variant,
+            )*
+            /// A value not known to these bindings (e.g. one added by a newer version of the C library).
+            $unknown(u32),
+        }
 
-    fn try_from(value: u32) -> Result<Self, Self::Error> {
-        // TODO: A bit ugly (but still manageable), convert to a macro?
-        Ok(match value {
-            sys::ktx_error_code_e_KTX_FILE_DATA_ERROR => Self::FileDataError,
-            sys::ktx_error_code_e_KTX_FILE_ISPIPE => Self::FileIsPipe,
-            sys::ktx_error_code_e_KTX_FILE_OPEN_FAILED => Self::FileOpenFailed,
-            sys::ktx_error_code_e_KTX_FILE_OVERFLOW => Self::FileOverflow,
-            sys::ktx_error_code_e_KTX_FILE_READ_ERROR => Self::FileReadError,
-            sys::ktx_error_code_e_KTX_FILE_SEEK_ERROR => Self::FileSeekError,
-            sys::ktx_error_code_e_KTX_FILE_UNEXPECTED_EOF => Self::FileUnexpectedEof,
-            sys::ktx_error_code_e_KTX_FILE_WRITE_ERROR => Self::FileWriteError,
-            sys::ktx_error_code_e_KTX_GL_ERROR => Self::GlError,
-            sys::ktx_error_code_e_KTX_INVALID_OPERATION => Self::InvalidOperation,
-            sys::ktx_error_code_e_KTX_INVALID_VALUE => Self::InvalidValue,
-            sys::ktx_error_code_e_KTX_NOT_FOUND => Self::NotFound,
-            sys::ktx_error_code_e_KTX_OUT_OF_MEMORY => Self::OutOfMemory,
-            sys::ktx_error_code_e_KTX_TRANSCODE_FAILED => Self::TranscodeFailed,
-            sys::ktx_error_code_e_KTX_UNKNOWN_FILE_FORMAT => Self::UnknownFileFormat,
-            sys::ktx_error_code_e_KTX_UNSUPPORTED_TEXTURE_TYPE => Self::UnsupportedTextureType,
-            sys::ktx_error_code_e_KTX_UNSUPPORTED_FEATURE => Self::UnsupportedFeature,
-            sys::ktx_error_code_e_KTX_LIBRARY_NOT_LINKED => Self::LibraryNotLinked,
-            _ => return Err("Not a KTX_ error variant"),
-        })
+        impl $name {
+            #[doc = concat!("All known [`", stringify!($name), "`] variants, in declaration order.")]
+            pub const ALL: &'static [Self] = &[$(Self::$variant),*];
+        }
+
+        impl TryFrom<u32> for $name {
+            type Error = &'static str;
+
+            fn try_from(value: u32) -> Result<Self, Self::Error> {
+                match value {
+                    $excluded => Err($err),
+                    $($value => Ok(Self::$variant),)*
+                    other => Ok(Self::$unknown(other)),
+                }
+            }
+        }
+
+        impl From<$name> for u32 {
+            fn from(value: $name) -> Self {
+                match value {
+                    $($name::$variant => $value,)*
+                    $name::$unknown(other) => other,
+                }
+            }
+        }
+    };
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident: $err:literal {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident = $value:path,
+            )*
+        }
+    ) => {
+        $(#[$meta])*
+        #[repr(u32)]
+        $vis enum $name {
+            $(
+                $(#[$variant_meta])*
+                $variant = $value,
+            )*
+        }
+
+        impl $name {
+            #[doc = concat!("All [`", stringify!($name), "`] variants, in declaration order.")]
+            pub const ALL: &'static [Self] = &[$(Self::$variant),*];
+        }
+
+        impl TryFrom<u32> for $name {
+            type Error = &'static str;
+
+            fn try_from(value: u32) -> Result<Self, Self::Error> {
+                match value {
+                    $($value => Ok(Self::$variant),)*
+                    _ => Err($err),
+                }
+            }
+        }
+    };
+}
+
+impl_try_from! {
+    /// Error codes as returned from the underlying C library.
+    ///
+    /// See [`sys::ktx_error_code_e`].
+    #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+    #[non_exhaustive]
+    pub enum KtxError: else Unknown, except sys::ktx_error_code_e_KTX_SUCCESS: "KTX_SUCCESS is not an error" {
+        FileDataError = sys::ktx_error_code_e_KTX_FILE_DATA_ERROR,
+        FileIsPipe = sys::ktx_error_code_e_KTX_FILE_ISPIPE,
+        FileOpenFailed = sys::ktx_error_code_e_KTX_FILE_OPEN_FAILED,
+        FileOverflow = sys::ktx_error_code_e_KTX_FILE_OVERFLOW,
+        FileReadError = sys::ktx_error_code_e_KTX_FILE_READ_ERROR,
+        FileSeekError = sys::ktx_error_code_e_KTX_FILE_SEEK_ERROR,
+        FileUnexpectedEof = sys::ktx_error_code_e_KTX_FILE_UNEXPECTED_EOF,
+        FileWriteError = sys::ktx_error_code_e_KTX_FILE_WRITE_ERROR,
+        GlError = sys::ktx_error_code_e_KTX_GL_ERROR,
+        InvalidOperation = sys::ktx_error_code_e_KTX_INVALID_OPERATION,
+        InvalidValue = sys::ktx_error_code_e_KTX_INVALID_VALUE,
+        NotFound = sys::ktx_error_code_e_KTX_NOT_FOUND,
+        OutOfMemory = sys::ktx_error_code_e_KTX_OUT_OF_MEMORY,
+        TranscodeFailed = sys::ktx_error_code_e_KTX_TRANSCODE_FAILED,
+        UnknownFileFormat = sys::ktx_error_code_e_KTX_UNKNOWN_FILE_FORMAT,
+        UnsupportedTextureType = sys::ktx_error_code_e_KTX_UNSUPPORTED_TEXTURE_TYPE,
+        UnsupportedFeature = sys::ktx_error_code_e_KTX_UNSUPPORTED_FEATURE,
+        LibraryNotLinked = sys::ktx_error_code_e_KTX_LIBRARY_NOT_LINKED,
     }
 }
 
 impl Display for KtxError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let KtxError::Unknown(code) = self {
+            return write!(f, "unknown KTX error ({})", code);
+        }
         // SAFETY: Safe - this just accessess a C array of strings under the hood
-        let c_str = unsafe { CStr::from_ptr(sys::ktxErrorString(*self as u32)) };
+        let c_str = unsafe { CStr::from_ptr(sys::ktxErrorString(u32::from(*self))) };
         match c_str.to_str() {
             Ok(msg) => write!(f, "{}", msg),
             _ => Err(std::fmt::Error),
@@ -80,18 +145,54 @@ impl Display for KtxError {
 
 impl Error for KtxError {}
 
+impl From<KtxError> for std::io::Error {
+    fn from(error: KtxError) -> Self {
+        use std::io::ErrorKind;
+        let kind = match error {
+            KtxError::FileDataError | KtxError::UnknownFileFormat => ErrorKind::InvalidData,
+            KtxError::FileOpenFailed => ErrorKind::NotFound,
+            KtxError::FileUnexpectedEof => ErrorKind::UnexpectedEof,
+            KtxError::InvalidValue => ErrorKind::InvalidInput,
+            KtxError::OutOfMemory => ErrorKind::OutOfMemory,
+            _ => ErrorKind::Other,
+        };
+        // The KtxError itself is the payload, so that its message is preserved (and it can be downcast to)
+        std::io::Error::new(kind, error)
+    }
+}
+
 pub(crate) fn ktx_result<T>(errcode: sys::ktx_error_code_e, ok: T) -> Result<T, KtxError> {
-    if errcode == sys::ktx_error_code_e_KTX_SUCCESS {
-        Ok(ok)
-    } else {
-        Err(KtxError::try_from(errcode as u32).unwrap_or(KtxError::InvalidValue))
+    // `KTX_SUCCESS` is the only code that is not a `KtxError`
+    match KtxError::try_from(errcode as u32) {
+        Ok(error) => Err(error),
+        Err(_) => Ok(ok),
+    }
+}
+
+/// Converts the error code of a failed C call (e.g. the `Err` of [`crate::RustKtxStream::new`]) to a [`KtxError`].
+pub(crate) fn ktx_error(errcode: sys::ktx_error_code_e) -> KtxError {
+    ktx_result(errcode, ()).expect_err("KTX_SUCCESS is not an error")
+}
+
+impl_try_from! {
+    /// The class (i.e. KTX version) of a [`crate::Texture`].
+    ///
+    /// See [`sys::class_id`].
+    #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub enum TextureClass: "Not a class_id variant" {
+        /// A KTX1 texture ([`crate::texture::Ktx1`]).
+        Ktx1 = sys::class_id_ktxTexture1_c,
+        /// A KTX2 texture ([`crate::texture::Ktx2`]).
+        Ktx2 = sys::class_id_ktxTexture2_c,
     }
 }
 
 /// The supercompression scheme for a [`crate::Texture`].
 ///
 /// See [`sys::ktxSupercmpScheme`].
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SuperCompressionScheme {
     None,
     BasisLZ,
@@ -138,11 +239,1210 @@ impl Display for SuperCompressionScheme {
     }
 }
 
+/// The source of a channel when swizzling a [`crate::Texture`]; see [`crate::Texture::swizzle`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Channel {
+    R,
+    G,
+    B,
+    A,
+    /// A constant zero.
+    Zero,
+    /// A constant one (i.e. the maximum value for normalized formats).
+    One,
+}
+
+/// The transfer function of the data in a KTX2 [`crate::Texture`], as stored in its DFD.
+///
+/// See <https://www.khronos.org/registry/DataFormat/specs/1.3/dataformat.1.3.inline.html#_emphasis_role_strong_emphasis_transferfunction_emphasis_emphasis>.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TransferFunction {
+    Linear,
+    Srgb,
+    Unknown(u32),
+}
+
+impl From<TransferFunction> for u32 {
+    fn from(transfer_function: TransferFunction) -> Self {
+        match transfer_function {
+            // KHR_DF_TRANSFER_LINEAR
+            TransferFunction::Linear => 1,
+            // KHR_DF_TRANSFER_SRGB
+            TransferFunction::Srgb => 2,
+            TransferFunction::Unknown(value) => value,
+        }
+    }
+}
+
+impl From<u32> for TransferFunction {
+    fn from(transfer_function: u32) -> Self {
+        match transfer_function {
+            1 => TransferFunction::Linear,
+            2 => TransferFunction::Srgb,
+            other => TransferFunction::Unknown(other),
+        }
+    }
+}
+
+/// The color primaries of the data in a KTX2 [`crate::Texture`], as stored in its DFD.
+///
+/// See <https://www.khronos.org/registry/DataFormat/specs/1.3/dataformat.1.3.inline.html#_emphasis_role_strong_emphasis_colorprimaries_emphasis_emphasis>.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ColorPrimaries {
+    Unspecified,
+    /// BT.709, which sRGB shares.
+    Bt709,
+    Bt2020,
+    DisplayP3,
+    AdobeRgb,
+    Unknown(u32),
+}
+
+impl From<ColorPrimaries> for u32 {
+    fn from(color_primaries: ColorPrimaries) -> Self {
+        match color_primaries {
+            // KHR_DF_PRIMARIES_UNSPECIFIED
+            ColorPrimaries::Unspecified => 0,
+            // KHR_DF_PRIMARIES_BT709
+            ColorPrimaries::Bt709 => 1,
+            // KHR_DF_PRIMARIES_BT2020
+            ColorPrimaries::Bt2020 => 4,
+            // KHR_DF_PRIMARIES_DISPLAYP3
+            ColorPrimaries::DisplayP3 => 10,
+            // KHR_DF_PRIMARIES_ADOBERGB
+            ColorPrimaries::AdobeRgb => 11,
+            ColorPrimaries::Unknown(value) => value,
+        }
+    }
+}
+
+impl From<u32> for ColorPrimaries {
+    fn from(color_primaries: u32) -> Self {
+        match color_primaries {
+            0 => ColorPrimaries::Unspecified,
+            1 => ColorPrimaries::Bt709,
+            4 => ColorPrimaries::Bt2020,
+            10 => ColorPrimaries::DisplayP3,
+            11 => ColorPrimaries::AdobeRgb,
+            other => ColorPrimaries::Unknown(other),
+        }
+    }
+}
+
+/// The color model of the data in a KTX2 [`crate::Texture`], as stored in its DFD.
+///
+/// Only the most common models are listed explicitly; any other value is kept as-is in [`ColorModel::Unknown`].  
+/// See <https://www.khronos.org/registry/DataFormat/specs/1.3/dataformat.1.3.inline.html#_emphasis_role_strong_emphasis_colormodel_emphasis_emphasis>.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ColorModel {
+    Unspecified,
+    /// Red, green, blue, stencil, depth, alpha.
+    Rgbsda,
+    /// Luma, chroma (Y'CbCr), stencil, depth, alpha.
+    Yuvsda,
+    /// Basis Universal ETC1S.
+    Etc1s,
+    /// Basis Universal UASTC.
+    Uastc,
+    Unknown(u32),
+}
+
+impl From<ColorModel> for u32 {
+    fn from(color_model: ColorModel) -> Self {
+        match color_model {
+            // KHR_DF_MODEL_UNSPECIFIED
+            ColorModel::Unspecified => 0,
+            // KHR_DF_MODEL_RGBSDA
+            ColorModel::Rgbsda => 1,
+            // KHR_DF_MODEL_YUVSDA
+            ColorModel::Yuvsda => 2,
+            // KHR_DF_MODEL_ETC1S
+            ColorModel::Etc1s => 163,
+            // KHR_DF_MODEL_UASTC
+            ColorModel::Uastc => 166,
+            ColorModel::Unknown(value) => value,
+        }
+    }
+}
+
+impl From<u32> for ColorModel {
+    fn from(color_model: u32) -> Self {
+        match color_model {
+            0 => ColorModel::Unspecified,
+            1 => ColorModel::Rgbsda,
+            2 => ColorModel::Yuvsda,
+            163 => ColorModel::Etc1s,
+            166 => ColorModel::Uastc,
+            other => ColorModel::Unknown(other),
+        }
+    }
+}
+
+/// The Basis Universal encoding of the data in a KTX2 [`crate::Texture`], as returned by
+/// [`crate::texture::Ktx2::basis_encoding`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BasisEncoding {
+    /// ETC1S, always BasisLZ-supercompressed. Smaller, but lower quality; cheap to transcode.
+    Etc1s,
+    /// UASTC, possibly ZStandard-supercompressed. Larger, but higher quality.
+    Uastc,
+}
+
+/// The Vulkan format of the data in a KTX2 [`crate::Texture`] (`VkFormat`).
+///
+/// Only the most common formats are listed explicitly; any other value is kept as-is in [`VkFormat::Unknown`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VkFormat {
+    Undefined,
+    R8Unorm,
+    R8Snorm,
+    R8Uint,
+    R8Sint,
+    R8Srgb,
+    R8G8Unorm,
+    R8G8Snorm,
+    R8G8Uint,
+    R8G8Sint,
+    R8G8Srgb,
+    R8G8B8Unorm,
+    R8G8B8Snorm,
+    R8G8B8Uint,
+    R8G8B8Sint,
+    R8G8B8Srgb,
+    B8G8R8Unorm,
+    B8G8R8Srgb,
+    R8G8B8A8Unorm,
+    R8G8B8A8Snorm,
+    R8G8B8A8Uint,
+    R8G8B8A8Sint,
+    R8G8B8A8Srgb,
+    B8G8R8A8Unorm,
+    B8G8R8A8Srgb,
+    A2B10G10R10UnormPack32,
+    R16Unorm,
+    R16Uint,
+    R16Sint,
+    R16Sfloat,
+    R16G16Unorm,
+    R16G16Sfloat,
+    R16G16B16A16Unorm,
+    R16G16B16A16Uint,
+    R16G16B16A16Sint,
+    R16G16B16A16Sfloat,
+    R32Uint,
+    R32Sint,
+    R32Sfloat,
+    R32G32Uint,
+    R32G32Sint,
+    R32G32Sfloat,
+    R32G32B32Sfloat,
+    R32G32B32A32Uint,
+    R32G32B32A32Sint,
+    R32G32B32A32Sfloat,
+    B10G11R11UfloatPack32,
+    E5B9G9R9UfloatPack32,
+    D16Unorm,
+    D32Sfloat,
+    S8Uint,
+    D24UnormS8Uint,
+    Bc1RgbUnormBlock,
+    Bc1RgbSrgbBlock,
+    Bc1RgbaUnormBlock,
+    Bc1RgbaSrgbBlock,
+    Bc2UnormBlock,
+    Bc2SrgbBlock,
+    Bc3UnormBlock,
+    Bc3SrgbBlock,
+    Bc4UnormBlock,
+    Bc4SnormBlock,
+    Bc5UnormBlock,
+    Bc5SnormBlock,
+    Bc6hUfloatBlock,
+    Bc6hSfloatBlock,
+    Bc7UnormBlock,
+    Bc7SrgbBlock,
+    Etc2R8G8B8UnormBlock,
+    Etc2R8G8B8SrgbBlock,
+    Etc2R8G8B8A1UnormBlock,
+    Etc2R8G8B8A1SrgbBlock,
+    Etc2R8G8B8A8UnormBlock,
+    Etc2R8G8B8A8SrgbBlock,
+    EacR11UnormBlock,
+    EacR11SnormBlock,
+    EacR11G11UnormBlock,
+    EacR11G11SnormBlock,
+    Astc4x4UnormBlock,
+    Astc4x4SrgbBlock,
+    Astc5x4UnormBlock,
+    Astc5x4SrgbBlock,
+    Astc5x5UnormBlock,
+    Astc5x5SrgbBlock,
+    Astc6x5UnormBlock,
+    Astc6x5SrgbBlock,
+    Astc6x6UnormBlock,
+    Astc6x6SrgbBlock,
+    Astc8x5UnormBlock,
+    Astc8x5SrgbBlock,
+    Astc8x6UnormBlock,
+    Astc8x6SrgbBlock,
+    Astc8x8UnormBlock,
+    Astc8x8SrgbBlock,
+    Astc10x5UnormBlock,
+    Astc10x5SrgbBlock,
+    Astc10x6UnormBlock,
+    Astc10x6SrgbBlock,
+    Astc10x8UnormBlock,
+    Astc10x8SrgbBlock,
+    Astc10x10UnormBlock,
+    Astc10x10SrgbBlock,
+    Astc12x10UnormBlock,
+    Astc12x10SrgbBlock,
+    Astc12x12UnormBlock,
+    Astc12x12SrgbBlock,
+    Pvrtc1Bpp2UnormBlockImg,
+    Pvrtc1Bpp4UnormBlockImg,
+    Pvrtc2Bpp2UnormBlockImg,
+    Pvrtc2Bpp4UnormBlockImg,
+    Pvrtc1Bpp2SrgbBlockImg,
+    Pvrtc1Bpp4SrgbBlockImg,
+    Pvrtc2Bpp2SrgbBlockImg,
+    Pvrtc2Bpp4SrgbBlockImg,
+    Unknown(u32),
+}
+
+impl From<VkFormat> for u32 {
+    fn from(format: VkFormat) -> Self {
+        match format {
+            VkFormat::Undefined => 0,
+            VkFormat::R8Unorm => 9,
+            VkFormat::R8Snorm => 10,
+            VkFormat::R8Uint => 13,
+            VkFormat::R8Sint => 14,
+            VkFormat::R8Srgb => 15,
+            VkFormat::R8G8Unorm => 16,
+            VkFormat::R8G8Snorm => 17,
+            VkFormat::R8G8Uint => 20,
+            VkFormat::R8G8Sint => 21,
+            VkFormat::R8G8Srgb => 22,
+            VkFormat::R8G8B8Unorm => 23,
+            VkFormat::R8G8B8Snorm => 24,
+            VkFormat::R8G8B8Uint => 27,
+            VkFormat::R8G8B8Sint => 28,
+            VkFormat::R8G8B8Srgb => 29,
+            VkFormat::B8G8R8Unorm => 30,
+            VkFormat::B8G8R8Srgb => 36,
+            VkFormat::R8G8B8A8Unorm => 37,
+            VkFormat::R8G8B8A8Snorm => 38,
+            VkFormat::R8G8B8A8Uint => 41,
+            VkFormat::R8G8B8A8Sint => 42,
+            VkFormat::R8G8B8A8Srgb => 43,
+            VkFormat::B8G8R8A8Unorm => 44,
+            VkFormat::B8G8R8A8Srgb => 50,
+            VkFormat::A2B10G10R10UnormPack32 => 64,
+            VkFormat::R16Unorm => 70,
+            VkFormat::R16Uint => 74,
+            VkFormat::R16Sint => 75,
+            VkFormat::R16Sfloat => 76,
+            VkFormat::R16G16Unorm => 77,
+            VkFormat::R16G16Sfloat => 83,
+            VkFormat::R16G16B16A16Unorm => 91,
+            VkFormat::R16G16B16A16Uint => 95,
+            VkFormat::R16G16B16A16Sint => 96,
+            VkFormat::R16G16B16A16Sfloat => 97,
+            VkFormat::R32Uint => 98,
+            VkFormat::R32Sint => 99,
+            VkFormat::R32Sfloat => 100,
+            VkFormat::R32G32Uint => 101,
+            VkFormat::R32G32Sint => 102,
+            VkFormat::R32G32Sfloat => 103,
+            VkFormat::R32G32B32Sfloat => 106,
+            VkFormat::R32G32B32A32Uint => 107,
+            VkFormat::R32G32B32A32Sint => 108,
+            VkFormat::R32G32B32A32Sfloat => 109,
+            VkFormat::B10G11R11UfloatPack32 => 122,
+            VkFormat::E5B9G9R9UfloatPack32 => 123,
+            VkFormat::D16Unorm => 124,
+            VkFormat::D32Sfloat => 126,
+            VkFormat::S8Uint => 127,
+            VkFormat::D24UnormS8Uint => 129,
+            VkFormat::Bc1RgbUnormBlock => 131,
+            VkFormat::Bc1RgbSrgbBlock => 132,
+            VkFormat::Bc1RgbaUnormBlock => 133,
+            VkFormat::Bc1RgbaSrgbBlock => 134,
+            VkFormat::Bc2UnormBlock => 135,
+            VkFormat::Bc2SrgbBlock => 136,
+            VkFormat::Bc3UnormBlock => 137,
+            VkFormat::Bc3SrgbBlock => 138,
+            VkFormat::Bc4UnormBlock => 139,
+            VkFormat::Bc4SnormBlock => 140,
+            VkFormat::Bc5UnormBlock => 141,
+            VkFormat::Bc5SnormBlock => 142,
+            VkFormat::Bc6hUfloatBlock => 143,
+            VkFormat::Bc6hSfloatBlock => 144,
+            VkFormat::Bc7UnormBlock => 145,
+            VkFormat::Bc7SrgbBlock => 146,
+            VkFormat::Etc2R8G8B8UnormBlock => 147,
+            VkFormat::Etc2R8G8B8SrgbBlock => 148,
+            VkFormat::Etc2R8G8B8A1UnormBlock => 149,
+            VkFormat::Etc2R8G8B8A1SrgbBlock => 150,
+            VkFormat::Etc2R8G8B8A8UnormBlock => 151,
+            VkFormat::Etc2R8G8B8A8SrgbBlock => 152,
+            VkFormat::EacR11UnormBlock => 153,
+            VkFormat::EacR11SnormBlock => 154,
+            VkFormat::EacR11G11UnormBlock => 155,
+            VkFormat::EacR11G11SnormBlock => 156,
+            VkFormat::Astc4x4UnormBlock => 157,
+            VkFormat::Astc4x4SrgbBlock => 158,
+            VkFormat::Astc5x4UnormBlock => 159,
+            VkFormat::Astc5x4SrgbBlock => 160,
+            VkFormat::Astc5x5UnormBlock => 161,
+            VkFormat::Astc5x5SrgbBlock => 162,
+            VkFormat::Astc6x5UnormBlock => 163,
+            VkFormat::Astc6x5SrgbBlock => 164,
+            VkFormat::Astc6x6UnormBlock => 165,
+            VkFormat::Astc6x6SrgbBlock => 166,
+            VkFormat::Astc8x5UnormBlock => 167,
+            VkFormat::Astc8x5SrgbBlock => 168,
+            VkFormat::Astc8x6UnormBlock => 169,
+            VkFormat::Astc8x6SrgbBlock => 170,
+            VkFormat::Astc8x8UnormBlock => 171,
+            VkFormat::Astc8x8SrgbBlock => 172,
+            VkFormat::Astc10x5UnormBlock => 173,
+            VkFormat::Astc10x5SrgbBlock => 174,
+            VkFormat::Astc10x6UnormBlock => 175,
+            VkFormat::Astc10x6SrgbBlock => 176,
+            VkFormat::Astc10x8UnormBlock => 177,
+            VkFormat::Astc10x8SrgbBlock => 178,
+            VkFormat::Astc10x10UnormBlock => 179,
+            VkFormat::Astc10x10SrgbBlock => 180,
+            VkFormat::Astc12x10UnormBlock => 181,
+            VkFormat::Astc12x10SrgbBlock => 182,
+            VkFormat::Astc12x12UnormBlock => 183,
+            VkFormat::Astc12x12SrgbBlock => 184,
+            VkFormat::Pvrtc1Bpp2UnormBlockImg => 1000054000,
+            VkFormat::Pvrtc1Bpp4UnormBlockImg => 1000054001,
+            VkFormat::Pvrtc2Bpp2UnormBlockImg => 1000054002,
+            VkFormat::Pvrtc2Bpp4UnormBlockImg => 1000054003,
+            VkFormat::Pvrtc1Bpp2SrgbBlockImg => 1000054004,
+            VkFormat::Pvrtc1Bpp4SrgbBlockImg => 1000054005,
+            VkFormat::Pvrtc2Bpp2SrgbBlockImg => 1000054006,
+            VkFormat::Pvrtc2Bpp4SrgbBlockImg => 1000054007,
+            VkFormat::Unknown(value) => value,
+        }
+    }
+}
+
+impl From<u32> for VkFormat {
+    fn from(format: u32) -> Self {
+        match format {
+            0 => VkFormat::Undefined,
+            9 => VkFormat::R8Unorm,
+            10 => VkFormat::R8Snorm,
+            13 => VkFormat::R8Uint,
+            14 => VkFormat::R8Sint,
+            15 => VkFormat::R8Srgb,
+            16 => VkFormat::R8G8Unorm,
+            17 => VkFormat::R8G8Snorm,
+            20 => VkFormat::R8G8Uint,
+            21 => VkFormat::R8G8Sint,
+            22 => VkFormat::R8G8Srgb,
+            23 => VkFormat::R8G8B8Unorm,
+            24 => VkFormat::R8G8B8Snorm,
+            27 => VkFormat::R8G8B8Uint,
+            28 => VkFormat::R8G8B8Sint,
+            29 => VkFormat::R8G8B8Srgb,
+            30 => VkFormat::B8G8R8Unorm,
+            36 => VkFormat::B8G8R8Srgb,
+            37 => VkFormat::R8G8B8A8Unorm,
+            38 => VkFormat::R8G8B8A8Snorm,
+            41 => VkFormat::R8G8B8A8Uint,
+            42 => VkFormat::R8G8B8A8Sint,
+            43 => VkFormat::R8G8B8A8Srgb,
+            44 => VkFormat::B8G8R8A8Unorm,
+            50 => VkFormat::B8G8R8A8Srgb,
+            64 => VkFormat::A2B10G10R10UnormPack32,
+            70 => VkFormat::R16Unorm,
+            74 => VkFormat::R16Uint,
+            75 => VkFormat::R16Sint,
+            76 => VkFormat::R16Sfloat,
+            77 => VkFormat::R16G16Unorm,
+            83 => VkFormat::R16G16Sfloat,
+            91 => VkFormat::R16G16B16A16Unorm,
+            95 => VkFormat::R16G16B16A16Uint,
+            96 => VkFormat::R16G16B16A16Sint,
+            97 => VkFormat::R16G16B16A16Sfloat,
+            98 => VkFormat::R32Uint,
+            99 => VkFormat::R32Sint,
+            100 => VkFormat::R32Sfloat,
+            101 => VkFormat::R32G32Uint,
+            102 => VkFormat::R32G32Sint,
+            103 => VkFormat::R32G32Sfloat,
+            106 => VkFormat::R32G32B32Sfloat,
+            107 => VkFormat::R32G32B32A32Uint,
+            108 => VkFormat::R32G32B32A32Sint,
+            109 => VkFormat::R32G32B32A32Sfloat,
+            122 => VkFormat::B10G11R11UfloatPack32,
+            123 => VkFormat::E5B9G9R9UfloatPack32,
+            124 => VkFormat::D16Unorm,
+            126 => VkFormat::D32Sfloat,
+            127 => VkFormat::S8Uint,
+            129 => VkFormat::D24UnormS8Uint,
+            131 => VkFormat::Bc1RgbUnormBlock,
+            132 => VkFormat::Bc1RgbSrgbBlock,
+            133 => VkFormat::Bc1RgbaUnormBlock,
+            134 => VkFormat::Bc1RgbaSrgbBlock,
+            135 => VkFormat::Bc2UnormBlock,
+            136 => VkFormat::Bc2SrgbBlock,
+            137 => VkFormat::Bc3UnormBlock,
+            138 => VkFormat::Bc3SrgbBlock,
+            139 => VkFormat::Bc4UnormBlock,
+            140 => VkFormat::Bc4SnormBlock,
+            141 => VkFormat::Bc5UnormBlock,
+            142 => VkFormat::Bc5SnormBlock,
+            143 => VkFormat::Bc6hUfloatBlock,
+            144 => VkFormat::Bc6hSfloatBlock,
+            145 => VkFormat::Bc7UnormBlock,
+            146 => VkFormat::Bc7SrgbBlock,
+            147 => VkFormat::Etc2R8G8B8UnormBlock,
+            148 => VkFormat::Etc2R8G8B8SrgbBlock,
+            149 => VkFormat::Etc2R8G8B8A1UnormBlock,
+            150 => VkFormat::Etc2R8G8B8A1SrgbBlock,
+            151 => VkFormat::Etc2R8G8B8A8UnormBlock,
+            152 => VkFormat::Etc2R8G8B8A8SrgbBlock,
+            153 => VkFormat::EacR11UnormBlock,
+            154 => VkFormat::EacR11SnormBlock,
+            155 => VkFormat::EacR11G11UnormBlock,
+            156 => VkFormat::EacR11G11SnormBlock,
+            157 => VkFormat::Astc4x4UnormBlock,
+            158 => VkFormat::Astc4x4SrgbBlock,
+            159 => VkFormat::Astc5x4UnormBlock,
+            160 => VkFormat::Astc5x4SrgbBlock,
+            161 => VkFormat::Astc5x5UnormBlock,
+            162 => VkFormat::Astc5x5SrgbBlock,
+            163 => VkFormat::Astc6x5UnormBlock,
+            164 => VkFormat::Astc6x5SrgbBlock,
+            165 => VkFormat::Astc6x6UnormBlock,
+            166 => VkFormat::Astc6x6SrgbBlock,
+            167 => VkFormat::Astc8x5UnormBlock,
+            168 => VkFormat::Astc8x5SrgbBlock,
+            169 => VkFormat::Astc8x6UnormBlock,
+            170 => VkFormat::Astc8x6SrgbBlock,
+            171 => VkFormat::Astc8x8UnormBlock,
+            172 => VkFormat::Astc8x8SrgbBlock,
+            173 => VkFormat::Astc10x5UnormBlock,
+            174 => VkFormat::Astc10x5SrgbBlock,
+            175 => VkFormat::Astc10x6UnormBlock,
+            176 => VkFormat::Astc10x6SrgbBlock,
+            177 => VkFormat::Astc10x8UnormBlock,
+            178 => VkFormat::Astc10x8SrgbBlock,
+            179 => VkFormat::Astc10x10UnormBlock,
+            180 => VkFormat::Astc10x10SrgbBlock,
+            181 => VkFormat::Astc12x10UnormBlock,
+            182 => VkFormat::Astc12x10SrgbBlock,
+            183 => VkFormat::Astc12x12UnormBlock,
+            184 => VkFormat::Astc12x12SrgbBlock,
+            1000054000 => VkFormat::Pvrtc1Bpp2UnormBlockImg,
+            1000054001 => VkFormat::Pvrtc1Bpp4UnormBlockImg,
+            1000054002 => VkFormat::Pvrtc2Bpp2UnormBlockImg,
+            1000054003 => VkFormat::Pvrtc2Bpp4UnormBlockImg,
+            1000054004 => VkFormat::Pvrtc1Bpp2SrgbBlockImg,
+            1000054005 => VkFormat::Pvrtc1Bpp4SrgbBlockImg,
+            1000054006 => VkFormat::Pvrtc2Bpp2SrgbBlockImg,
+            1000054007 => VkFormat::Pvrtc2Bpp4SrgbBlockImg,
+            other => VkFormat::Unknown(other),
+        }
+    }
+}
+
+impl VkFormat {
+    /// Returns the `(UNORM, SRGB)` variants of this format, if it has both.
+    pub(crate) fn srgb_pair(self) -> Option<(VkFormat, VkFormat)> {
+        Some(match self {
+            VkFormat::R8Unorm | VkFormat::R8Srgb => (VkFormat::R8Unorm, VkFormat::R8Srgb),
+            VkFormat::R8G8Unorm | VkFormat::R8G8Srgb => (VkFormat::R8G8Unorm, VkFormat::R8G8Srgb),
+            VkFormat::R8G8B8Unorm | VkFormat::R8G8B8Srgb => {
+                (VkFormat::R8G8B8Unorm, VkFormat::R8G8B8Srgb)
+            }
+            VkFormat::B8G8R8Unorm | VkFormat::B8G8R8Srgb => {
+                (VkFormat::B8G8R8Unorm, VkFormat::B8G8R8Srgb)
+            }
+            VkFormat::R8G8B8A8Unorm | VkFormat::R8G8B8A8Srgb => {
+                (VkFormat::R8G8B8A8Unorm, VkFormat::R8G8B8A8Srgb)
+            }
+            VkFormat::B8G8R8A8Unorm | VkFormat::B8G8R8A8Srgb => {
+                (VkFormat::B8G8R8A8Unorm, VkFormat::B8G8R8A8Srgb)
+            }
+            VkFormat::Bc1RgbUnormBlock | VkFormat::Bc1RgbSrgbBlock => {
+                (VkFormat::Bc1RgbUnormBlock, VkFormat::Bc1RgbSrgbBlock)
+            }
+            VkFormat::Bc1RgbaUnormBlock | VkFormat::Bc1RgbaSrgbBlock => {
+                (VkFormat::Bc1RgbaUnormBlock, VkFormat::Bc1RgbaSrgbBlock)
+            }
+            VkFormat::Bc2UnormBlock | VkFormat::Bc2SrgbBlock => {
+                (VkFormat::Bc2UnormBlock, VkFormat::Bc2SrgbBlock)
+            }
+            VkFormat::Bc3UnormBlock | VkFormat::Bc3SrgbBlock => {
+                (VkFormat::Bc3UnormBlock, VkFormat::Bc3SrgbBlock)
+            }
+            VkFormat::Bc7UnormBlock | VkFormat::Bc7SrgbBlock => {
+                (VkFormat::Bc7UnormBlock, VkFormat::Bc7SrgbBlock)
+            }
+            VkFormat::Etc2R8G8B8UnormBlock | VkFormat::Etc2R8G8B8SrgbBlock => (
+                VkFormat::Etc2R8G8B8UnormBlock,
+                VkFormat::Etc2R8G8B8SrgbBlock,
+            ),
+            VkFormat::Etc2R8G8B8A1UnormBlock | VkFormat::Etc2R8G8B8A1SrgbBlock => (
+                VkFormat::Etc2R8G8B8A1UnormBlock,
+                VkFormat::Etc2R8G8B8A1SrgbBlock,
+            ),
+            VkFormat::Etc2R8G8B8A8UnormBlock | VkFormat::Etc2R8G8B8A8SrgbBlock => (
+                VkFormat::Etc2R8G8B8A8UnormBlock,
+                VkFormat::Etc2R8G8B8A8SrgbBlock,
+            ),
+            VkFormat::Astc4x4UnormBlock | VkFormat::Astc4x4SrgbBlock => {
+                (VkFormat::Astc4x4UnormBlock, VkFormat::Astc4x4SrgbBlock)
+            }
+            VkFormat::Astc5x4UnormBlock | VkFormat::Astc5x4SrgbBlock => {
+                (VkFormat::Astc5x4UnormBlock, VkFormat::Astc5x4SrgbBlock)
+            }
+            VkFormat::Astc5x5UnormBlock | VkFormat::Astc5x5SrgbBlock => {
+                (VkFormat::Astc5x5UnormBlock, VkFormat::Astc5x5SrgbBlock)
+            }
+            VkFormat::Astc6x5UnormBlock | VkFormat::Astc6x5SrgbBlock => {
+                (VkFormat::Astc6x5UnormBlock, VkFormat::Astc6x5SrgbBlock)
+            }
+            VkFormat::Astc6x6UnormBlock | VkFormat::Astc6x6SrgbBlock => {
+                (VkFormat::Astc6x6UnormBlock, VkFormat::Astc6x6SrgbBlock)
+            }
+            VkFormat::Astc8x5UnormBlock | VkFormat::Astc8x5SrgbBlock => {
+                (VkFormat::Astc8x5UnormBlock, VkFormat::Astc8x5SrgbBlock)
+            }
+            VkFormat::Astc8x6UnormBlock | VkFormat::Astc8x6SrgbBlock => {
+                (VkFormat::Astc8x6UnormBlock, VkFormat::Astc8x6SrgbBlock)
+            }
+            VkFormat::Astc8x8UnormBlock | VkFormat::Astc8x8SrgbBlock => {
+                (VkFormat::Astc8x8UnormBlock, VkFormat::Astc8x8SrgbBlock)
+            }
+            VkFormat::Astc10x5UnormBlock | VkFormat::Astc10x5SrgbBlock => {
+                (VkFormat::Astc10x5UnormBlock, VkFormat::Astc10x5SrgbBlock)
+            }
+            VkFormat::Astc10x6UnormBlock | VkFormat::Astc10x6SrgbBlock => {
+                (VkFormat::Astc10x6UnormBlock, VkFormat::Astc10x6SrgbBlock)
+            }
+            VkFormat::Astc10x8UnormBlock | VkFormat::Astc10x8SrgbBlock => {
+                (VkFormat::Astc10x8UnormBlock, VkFormat::Astc10x8SrgbBlock)
+            }
+            VkFormat::Astc10x10UnormBlock | VkFormat::Astc10x10SrgbBlock => {
+                (VkFormat::Astc10x10UnormBlock, VkFormat::Astc10x10SrgbBlock)
+            }
+            VkFormat::Astc12x10UnormBlock | VkFormat::Astc12x10SrgbBlock => {
+                (VkFormat::Astc12x10UnormBlock, VkFormat::Astc12x10SrgbBlock)
+            }
+            VkFormat::Astc12x12UnormBlock | VkFormat::Astc12x12SrgbBlock => {
+                (VkFormat::Astc12x12UnormBlock, VkFormat::Astc12x12SrgbBlock)
+            }
+            VkFormat::Pvrtc1Bpp2UnormBlockImg | VkFormat::Pvrtc1Bpp2SrgbBlockImg => (
+                VkFormat::Pvrtc1Bpp2UnormBlockImg,
+                VkFormat::Pvrtc1Bpp2SrgbBlockImg,
+            ),
+            VkFormat::Pvrtc1Bpp4UnormBlockImg | VkFormat::Pvrtc1Bpp4SrgbBlockImg => (
+                VkFormat::Pvrtc1Bpp4UnormBlockImg,
+                VkFormat::Pvrtc1Bpp4SrgbBlockImg,
+            ),
+            VkFormat::Pvrtc2Bpp2UnormBlockImg | VkFormat::Pvrtc2Bpp2SrgbBlockImg => (
+                VkFormat::Pvrtc2Bpp2UnormBlockImg,
+                VkFormat::Pvrtc2Bpp2SrgbBlockImg,
+            ),
+            VkFormat::Pvrtc2Bpp4UnormBlockImg | VkFormat::Pvrtc2Bpp4SrgbBlockImg => (
+                VkFormat::Pvrtc2Bpp4UnormBlockImg,
+                VkFormat::Pvrtc2Bpp4SrgbBlockImg,
+            ),
+            _ => return None,
+        })
+    }
+}
+
+/// The OpenGL format of the data in a KTX1 [`crate::Texture`] (e.g. `GL_RGBA`).
+///
+/// Only the most common formats are listed explicitly; any other value is kept as-is in [`GlFormat::Other`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum GlFormat {
+    Red,
+    Rg,
+    Rgb,
+    Rgba,
+    Bgr,
+    Bgra,
+    RedInteger,
+    RgInteger,
+    RgbInteger,
+    RgbaInteger,
+    BgrInteger,
+    BgraInteger,
+    Alpha,
+    Luminance,
+    LuminanceAlpha,
+    StencilIndex,
+    DepthComponent,
+    DepthStencil,
+    Other(u32),
+}
+
+impl From<GlFormat> for u32 {
+    fn from(format: GlFormat) -> Self {
+        match format {
+            GlFormat::Red => 0x1903,
+            GlFormat::Rg => 0x8227,
+            GlFormat::Rgb => 0x1907,
+            GlFormat::Rgba => 0x1908,
+            GlFormat::Bgr => 0x80E0,
+            GlFormat::Bgra => 0x80E1,
+            GlFormat::RedInteger => 0x8D94,
+            GlFormat::RgInteger => 0x8228,
+            GlFormat::RgbInteger => 0x8D98,
+            GlFormat::RgbaInteger => 0x8D99,
+            GlFormat::BgrInteger => 0x8D9A,
+            GlFormat::BgraInteger => 0x8D9B,
+            GlFormat::Alpha => 0x1906,
+            GlFormat::Luminance => 0x1909,
+            GlFormat::LuminanceAlpha => 0x190A,
+            GlFormat::StencilIndex => 0x1901,
+            GlFormat::DepthComponent => 0x1902,
+            GlFormat::DepthStencil => 0x84F9,
+            GlFormat::Other(value) => value,
+        }
+    }
+}
+
+impl From<u32> for GlFormat {
+    fn from(format: u32) -> Self {
+        match format {
+            0x1903 => GlFormat::Red,
+            0x8227 => GlFormat::Rg,
+            0x1907 => GlFormat::Rgb,
+            0x1908 => GlFormat::Rgba,
+            0x80E0 => GlFormat::Bgr,
+            0x80E1 => GlFormat::Bgra,
+            0x8D94 => GlFormat::RedInteger,
+            0x8228 => GlFormat::RgInteger,
+            0x8D98 => GlFormat::RgbInteger,
+            0x8D99 => GlFormat::RgbaInteger,
+            0x8D9A => GlFormat::BgrInteger,
+            0x8D9B => GlFormat::BgraInteger,
+            0x1906 => GlFormat::Alpha,
+            0x1909 => GlFormat::Luminance,
+            0x190A => GlFormat::LuminanceAlpha,
+            0x1901 => GlFormat::StencilIndex,
+            0x1902 => GlFormat::DepthComponent,
+            0x84F9 => GlFormat::DepthStencil,
+            other => GlFormat::Other(other),
+        }
+    }
+}
+
+impl GlFormat {
+    /// Returns the number of components (channels) of this format, or `None` if it is not known.
+    pub fn num_components(&self) -> Option<u32> {
+        match self {
+            GlFormat::Red
+            | GlFormat::RedInteger
+            | GlFormat::Alpha
+            | GlFormat::Luminance
+            | GlFormat::StencilIndex
+            | GlFormat::DepthComponent => Some(1),
+            GlFormat::Rg
+            | GlFormat::RgInteger
+            | GlFormat::LuminanceAlpha
+            | GlFormat::DepthStencil => Some(2),
+            GlFormat::Rgb | GlFormat::Bgr | GlFormat::RgbInteger | GlFormat::BgrInteger => Some(3),
+            GlFormat::Rgba | GlFormat::Bgra | GlFormat::RgbaInteger | GlFormat::BgraInteger => {
+                Some(4)
+            }
+            GlFormat::Other(_) => None,
+        }
+    }
+}
+
+/// The OpenGL [base] internal format of the data in a KTX1 [`crate::Texture`] (e.g. `GL_RGBA8`).
+///
+/// Only the most common formats are listed explicitly; any other value is kept as-is in [`GlInternalFormat::Other`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum GlInternalFormat {
+    Red,
+    Rg,
+    Rgb,
+    Rgba,
+    Alpha,
+    Luminance,
+    LuminanceAlpha,
+    DepthComponent,
+    DepthStencil,
+    R8,
+    R8Snorm,
+    R8ui,
+    R8i,
+    R16,
+    R16ui,
+    R16i,
+    R16f,
+    R32ui,
+    R32i,
+    R32f,
+    Rg8,
+    Rg8Snorm,
+    Rg8ui,
+    Rg8i,
+    Rg16,
+    Rg16f,
+    Rg32f,
+    Rgb8,
+    Rgb8Snorm,
+    Srgb8,
+    Rgb16f,
+    Rgb32f,
+    R11fG11fB10f,
+    Rgb9E5,
+    Rgb565,
+    Rgba4,
+    Rgb5A1,
+    Rgba8,
+    Rgba8Snorm,
+    Srgb8Alpha8,
+    Rgb10A2,
+    Rgba16,
+    Rgba16f,
+    Rgba32f,
+    Rgba8ui,
+    Rgba8i,
+    Rgba16ui,
+    Rgba16i,
+    Rgba32ui,
+    Rgba32i,
+    DepthComponent16,
+    DepthComponent24,
+    DepthComponent32f,
+    Depth24Stencil8,
+    Depth32fStencil8,
+    CompressedRgbS3tcDxt1,
+    CompressedRgbaS3tcDxt1,
+    CompressedRgbaS3tcDxt3,
+    CompressedRgbaS3tcDxt5,
+    CompressedSrgbS3tcDxt1,
+    CompressedSrgbAlphaS3tcDxt1,
+    CompressedSrgbAlphaS3tcDxt3,
+    CompressedSrgbAlphaS3tcDxt5,
+    CompressedRedRgtc1,
+    CompressedSignedRedRgtc1,
+    CompressedRgRgtc2,
+    CompressedSignedRgRgtc2,
+    CompressedRgbaBptcUnorm,
+    CompressedSrgbAlphaBptcUnorm,
+    CompressedRgbBptcSignedFloat,
+    CompressedRgbBptcUnsignedFloat,
+    Etc1Rgb8Oes,
+    CompressedR11Eac,
+    CompressedSignedR11Eac,
+    CompressedRg11Eac,
+    CompressedSignedRg11Eac,
+    CompressedRgb8Etc2,
+    CompressedSrgb8Etc2,
+    CompressedRgb8PunchthroughAlpha1Etc2,
+    CompressedSrgb8PunchthroughAlpha1Etc2,
+    CompressedRgba8Etc2Eac,
+    CompressedSrgb8Alpha8Etc2Eac,
+    CompressedRgbaAstc4x4,
+    CompressedRgbaAstc5x4,
+    CompressedRgbaAstc5x5,
+    CompressedRgbaAstc6x5,
+    CompressedRgbaAstc6x6,
+    CompressedRgbaAstc8x5,
+    CompressedRgbaAstc8x6,
+    CompressedRgbaAstc8x8,
+    CompressedRgbaAstc10x5,
+    CompressedRgbaAstc10x6,
+    CompressedRgbaAstc10x8,
+    CompressedRgbaAstc10x10,
+    CompressedRgbaAstc12x10,
+    CompressedRgbaAstc12x12,
+    CompressedSrgb8Alpha8Astc4x4,
+    CompressedSrgb8Alpha8Astc5x4,
+    CompressedSrgb8Alpha8Astc5x5,
+    CompressedSrgb8Alpha8Astc6x5,
+    CompressedSrgb8Alpha8Astc6x6,
+    CompressedSrgb8Alpha8Astc8x5,
+    CompressedSrgb8Alpha8Astc8x6,
+    CompressedSrgb8Alpha8Astc8x8,
+    CompressedSrgb8Alpha8Astc10x5,
+    CompressedSrgb8Alpha8Astc10x6,
+    CompressedSrgb8Alpha8Astc10x8,
+    CompressedSrgb8Alpha8Astc10x10,
+    CompressedSrgb8Alpha8Astc12x10,
+    CompressedSrgb8Alpha8Astc12x12,
+    CompressedRgbPvrtc4Bppv1Img,
+    CompressedRgbPvrtc2Bppv1Img,
+    CompressedRgbaPvrtc4Bppv1Img,
+    CompressedRgbaPvrtc2Bppv1Img,
+    Other(u32),
+}
+
+impl From<GlInternalFormat> for u32 {
+    fn from(format: GlInternalFormat) -> Self {
+        match format {
+            GlInternalFormat::Red => 0x1903,
+            GlInternalFormat::Rg => 0x8227,
+            GlInternalFormat::Rgb => 0x1907,
+            GlInternalFormat::Rgba => 0x1908,
+            GlInternalFormat::Alpha => 0x1906,
+            GlInternalFormat::Luminance => 0x1909,
+            GlInternalFormat::LuminanceAlpha => 0x190A,
+            GlInternalFormat::DepthComponent => 0x1902,
+            GlInternalFormat::DepthStencil => 0x84F9,
+            GlInternalFormat::R8 => 0x8229,
+            GlInternalFormat::R8Snorm => 0x8F94,
+            GlInternalFormat::R8ui => 0x8232,
+            GlInternalFormat::R8i => 0x8231,
+            GlInternalFormat::R16 => 0x822A,
+            GlInternalFormat::R16ui => 0x8234,
+            GlInternalFormat::R16i => 0x8233,
+            GlInternalFormat::R16f => 0x822D,
+            GlInternalFormat::R32ui => 0x8236,
+            GlInternalFormat::R32i => 0x8235,
+            GlInternalFormat::R32f => 0x822E,
+            GlInternalFormat::Rg8 => 0x822B,
+            GlInternalFormat::Rg8Snorm => 0x8F95,
+            GlInternalFormat::Rg8ui => 0x8238,
+            GlInternalFormat::Rg8i => 0x8237,
+            GlInternalFormat::Rg16 => 0x822C,
+            GlInternalFormat::Rg16f => 0x822F,
+            GlInternalFormat::Rg32f => 0x8230,
+            GlInternalFormat::Rgb8 => 0x8051,
+            GlInternalFormat::Rgb8Snorm => 0x8F96,
+            GlInternalFormat::Srgb8 => 0x8C41,
+            GlInternalFormat::Rgb16f => 0x881B,
+            GlInternalFormat::Rgb32f => 0x8815,
+            GlInternalFormat::R11fG11fB10f => 0x8C3A,
+            GlInternalFormat::Rgb9E5 => 0x8C3D,
+            GlInternalFormat::Rgb565 => 0x8D62,
+            GlInternalFormat::Rgba4 => 0x8056,
+            GlInternalFormat::Rgb5A1 => 0x8057,
+            GlInternalFormat::Rgba8 => 0x8058,
+            GlInternalFormat::Rgba8Snorm => 0x8F97,
+            GlInternalFormat::Srgb8Alpha8 => 0x8C43,
+            GlInternalFormat::Rgb10A2 => 0x8059,
+            GlInternalFormat::Rgba16 => 0x805B,
+            GlInternalFormat::Rgba16f => 0x881A,
+            GlInternalFormat::Rgba32f => 0x8814,
+            GlInternalFormat::Rgba8ui => 0x8D7C,
+            GlInternalFormat::Rgba8i => 0x8D8E,
+            GlInternalFormat::Rgba16ui => 0x8D76,
+            GlInternalFormat::Rgba16i => 0x8D88,
+            GlInternalFormat::Rgba32ui => 0x8D70,
+            GlInternalFormat::Rgba32i => 0x8D82,
+            GlInternalFormat::DepthComponent16 => 0x81A5,
+            GlInternalFormat::DepthComponent24 => 0x81A6,
+            GlInternalFormat::DepthComponent32f => 0x8CAC,
+            GlInternalFormat::Depth24Stencil8 => 0x88F0,
+            GlInternalFormat::Depth32fStencil8 => 0x8CAD,
+            GlInternalFormat::CompressedRgbS3tcDxt1 => 0x83F0,
+            GlInternalFormat::CompressedRgbaS3tcDxt1 => 0x83F1,
+            GlInternalFormat::CompressedRgbaS3tcDxt3 => 0x83F2,
+            GlInternalFormat::CompressedRgbaS3tcDxt5 => 0x83F3,
+            GlInternalFormat::CompressedSrgbS3tcDxt1 => 0x8C4C,
+            GlInternalFormat::CompressedSrgbAlphaS3tcDxt1 => 0x8C4D,
+            GlInternalFormat::CompressedSrgbAlphaS3tcDxt3 => 0x8C4E,
+            GlInternalFormat::CompressedSrgbAlphaS3tcDxt5 => 0x8C4F,
+            GlInternalFormat::CompressedRedRgtc1 => 0x8DBB,
+            GlInternalFormat::CompressedSignedRedRgtc1 => 0x8DBC,
+            GlInternalFormat::CompressedRgRgtc2 => 0x8DBD,
+            GlInternalFormat::CompressedSignedRgRgtc2 => 0x8DBE,
+            GlInternalFormat::CompressedRgbaBptcUnorm => 0x8E8C,
+            GlInternalFormat::CompressedSrgbAlphaBptcUnorm => 0x8E8D,
+            GlInternalFormat::CompressedRgbBptcSignedFloat => 0x8E8E,
+            GlInternalFormat::CompressedRgbBptcUnsignedFloat => 0x8E8F,
+            GlInternalFormat::Etc1Rgb8Oes => 0x8D64,
+            GlInternalFormat::CompressedR11Eac => 0x9270,
+            GlInternalFormat::CompressedSignedR11Eac => 0x9271,
+            GlInternalFormat::CompressedRg11Eac => 0x9272,
+            GlInternalFormat::CompressedSignedRg11Eac => 0x9273,
+            GlInternalFormat::CompressedRgb8Etc2 => 0x9274,
+            GlInternalFormat::CompressedSrgb8Etc2 => 0x9275,
+            GlInternalFormat::CompressedRgb8PunchthroughAlpha1Etc2 => 0x9276,
+            GlInternalFormat::CompressedSrgb8PunchthroughAlpha1Etc2 => 0x9277,
+            GlInternalFormat::CompressedRgba8Etc2Eac => 0x9278,
+            GlInternalFormat::CompressedSrgb8Alpha8Etc2Eac => 0x9279,
+            GlInternalFormat::CompressedRgbaAstc4x4 => 0x93B0,
+            GlInternalFormat::CompressedRgbaAstc5x4 => 0x93B1,
+            GlInternalFormat::CompressedRgbaAstc5x5 => 0x93B2,
+            GlInternalFormat::CompressedRgbaAstc6x5 => 0x93B3,
+            GlInternalFormat::CompressedRgbaAstc6x6 => 0x93B4,
+            GlInternalFormat::CompressedRgbaAstc8x5 => 0x93B5,
+            GlInternalFormat::CompressedRgbaAstc8x6 => 0x93B6,
+            GlInternalFormat::CompressedRgbaAstc8x8 => 0x93B7,
+            GlInternalFormat::CompressedRgbaAstc10x5 => 0x93B8,
+            GlInternalFormat::CompressedRgbaAstc10x6 => 0x93B9,
+            GlInternalFormat::CompressedRgbaAstc10x8 => 0x93BA,
+            GlInternalFormat::CompressedRgbaAstc10x10 => 0x93BB,
+            GlInternalFormat::CompressedRgbaAstc12x10 => 0x93BC,
+            GlInternalFormat::CompressedRgbaAstc12x12 => 0x93BD,
+            GlInternalFormat::CompressedSrgb8Alpha8Astc4x4 => 0x93D0,
+            GlInternalFormat::CompressedSrgb8Alpha8Astc5x4 => 0x93D1,
+            GlInternalFormat::CompressedSrgb8Alpha8Astc5x5 => 0x93D2,
+            GlInternalFormat::CompressedSrgb8Alpha8Astc6x5 => 0x93D3,
+            GlInternalFormat::CompressedSrgb8Alpha8Astc6x6 => 0x93D4,
+            GlInternalFormat::CompressedSrgb8Alpha8Astc8x5 => 0x93D5,
+            GlInternalFormat::CompressedSrgb8Alpha8Astc8x6 => 0x93D6,
+            GlInternalFormat::CompressedSrgb8Alpha8Astc8x8 => 0x93D7,
+            GlInternalFormat::CompressedSrgb8Alpha8Astc10x5 => 0x93D8,
+            GlInternalFormat::CompressedSrgb8Alpha8Astc10x6 => 0x93D9,
+            GlInternalFormat::CompressedSrgb8Alpha8Astc10x8 => 0x93DA,
+            GlInternalFormat::CompressedSrgb8Alpha8Astc10x10 => 0x93DB,
+            GlInternalFormat::CompressedSrgb8Alpha8Astc12x10 => 0x93DC,
+            GlInternalFormat::CompressedSrgb8Alpha8Astc12x12 => 0x93DD,
+            GlInternalFormat::CompressedRgbPvrtc4Bppv1Img => 0x8C00,
+            GlInternalFormat::CompressedRgbPvrtc2Bppv1Img => 0x8C01,
+            GlInternalFormat::CompressedRgbaPvrtc4Bppv1Img => 0x8C02,
+            GlInternalFormat::CompressedRgbaPvrtc2Bppv1Img => 0x8C03,
+            GlInternalFormat::Other(value) => value,
+        }
+    }
+}
+
+impl From<u32> for GlInternalFormat {
+    fn from(format: u32) -> Self {
+        match format {
+            0x1903 => GlInternalFormat::Red,
+            0x8227 => GlInternalFormat::Rg,
+            0x1907 => GlInternalFormat::Rgb,
+            0x1908 => GlInternalFormat::Rgba,
+            0x1906 => GlInternalFormat::Alpha,
+            0x1909 => GlInternalFormat::Luminance,
+            0x190A => GlInternalFormat::LuminanceAlpha,
+            0x1902 => GlInternalFormat::DepthComponent,
+            0x84F9 => GlInternalFormat::DepthStencil,
+            0x8229 => GlInternalFormat::R8,
+            0x8F94 => GlInternalFormat::R8Snorm,
+            0x8232 => GlInternalFormat::R8ui,
+            0x8231 => GlInternalFormat::R8i,
+            0x822A => GlInternalFormat::R16,
+            0x8234 => GlInternalFormat::R16ui,
+            0x8233 => GlInternalFormat::R16i,
+            0x822D => GlInternalFormat::R16f,
+            0x8236 => GlInternalFormat::R32ui,
+            0x8235 => GlInternalFormat::R32i,
+            0x822E => GlInternalFormat::R32f,
+            0x822B => GlInternalFormat::Rg8,
+            0x8F95 => GlInternalFormat::Rg8Snorm,
+            0x8238 => GlInternalFormat::Rg8ui,
+            0x8237 => GlInternalFormat::Rg8i,
+            0x822C => GlInternalFormat::Rg16,
+            0x822F => GlInternalFormat::Rg16f,
+            0x8230 => GlInternalFormat::Rg32f,
+            0x8051 => GlInternalFormat::Rgb8,
+            0x8F96 => GlInternalFormat::Rgb8Snorm,
+            0x8C41 => GlInternalFormat::Srgb8,
+            0x881B => GlInternalFormat::Rgb16f,
+            0x8815 => GlInternalFormat::Rgb32f,
+            0x8C3A => GlInternalFormat::R11fG11fB10f,
+            0x8C3D => GlInternalFormat::Rgb9E5,
+            0x8D62 => GlInternalFormat::Rgb565,
+            0x8056 => GlInternalFormat::Rgba4,
+            0x8057 => GlInternalFormat::Rgb5A1,
+            0x8058 => GlInternalFormat::Rgba8,
+            0x8F97 => GlInternalFormat::Rgba8Snorm,
+            0x8C43 => GlInternalFormat::Srgb8Alpha8,
+            0x8059 => GlInternalFormat::Rgb10A2,
+            0x805B => GlInternalFormat::Rgba16,
+            0x881A => GlInternalFormat::Rgba16f,
+            0x8814 => GlInternalFormat::Rgba32f,
+            0x8D7C => GlInternalFormat::Rgba8ui,
+            0x8D8E => GlInternalFormat::Rgba8i,
+            0x8D76 => GlInternalFormat::Rgba16ui,
+            0x8D88 => GlInternalFormat::Rgba16i,
+            0x8D70 => GlInternalFormat::Rgba32ui,
+            0x8D82 => GlInternalFormat::Rgba32i,
+            0x81A5 => GlInternalFormat::DepthComponent16,
+            0x81A6 => GlInternalFormat::DepthComponent24,
+            0x8CAC => GlInternalFormat::DepthComponent32f,
+            0x88F0 => GlInternalFormat::Depth24Stencil8,
+            0x8CAD => GlInternalFormat::Depth32fStencil8,
+            0x83F0 => GlInternalFormat::CompressedRgbS3tcDxt1,
+            0x83F1 => GlInternalFormat::CompressedRgbaS3tcDxt1,
+            0x83F2 => GlInternalFormat::CompressedRgbaS3tcDxt3,
+            0x83F3 => GlInternalFormat::CompressedRgbaS3tcDxt5,
+            0x8C4C => GlInternalFormat::CompressedSrgbS3tcDxt1,
+            0x8C4D => GlInternalFormat::CompressedSrgbAlphaS3tcDxt1,
+            0x8C4E => GlInternalFormat::CompressedSrgbAlphaS3tcDxt3,
+            0x8C4F => GlInternalFormat::CompressedSrgbAlphaS3tcDxt5,
+            0x8DBB => GlInternalFormat::CompressedRedRgtc1,
+            0x8DBC => GlInternalFormat::CompressedSignedRedRgtc1,
+            0x8DBD => GlInternalFormat::CompressedRgRgtc2,
+            0x8DBE => GlInternalFormat::CompressedSignedRgRgtc2,
+            0x8E8C => GlInternalFormat::CompressedRgbaBptcUnorm,
+            0x8E8D => GlInternalFormat::CompressedSrgbAlphaBptcUnorm,
+            0x8E8E => GlInternalFormat::CompressedRgbBptcSignedFloat,
+            0x8E8F => GlInternalFormat::CompressedRgbBptcUnsignedFloat,
+            0x8D64 => GlInternalFormat::Etc1Rgb8Oes,
+            0x9270 => GlInternalFormat::CompressedR11Eac,
+            0x9271 => GlInternalFormat::CompressedSignedR11Eac,
+            0x9272 => GlInternalFormat::CompressedRg11Eac,
+            0x9273 => GlInternalFormat::CompressedSignedRg11Eac,
+            0x9274 => GlInternalFormat::CompressedRgb8Etc2,
+            0x9275 => GlInternalFormat::CompressedSrgb8Etc2,
+            0x9276 => GlInternalFormat::CompressedRgb8PunchthroughAlpha1Etc2,
+            0x9277 => GlInternalFormat::CompressedSrgb8PunchthroughAlpha1Etc2,
+            0x9278 => GlInternalFormat::CompressedRgba8Etc2Eac,
+            0x9279 => GlInternalFormat::CompressedSrgb8Alpha8Etc2Eac,
+            0x93B0 => GlInternalFormat::CompressedRgbaAstc4x4,
+            0x93B1 => GlInternalFormat::CompressedRgbaAstc5x4,
+            0x93B2 => GlInternalFormat::CompressedRgbaAstc5x5,
+            0x93B3 => GlInternalFormat::CompressedRgbaAstc6x5,
+            0x93B4 => GlInternalFormat::CompressedRgbaAstc6x6,
+            0x93B5 => GlInternalFormat::CompressedRgbaAstc8x5,
+            0x93B6 => GlInternalFormat::CompressedRgbaAstc8x6,
+            0x93B7 => GlInternalFormat::CompressedRgbaAstc8x8,
+            0x93B8 => GlInternalFormat::CompressedRgbaAstc10x5,
+            0x93B9 => GlInternalFormat::CompressedRgbaAstc10x6,
+            0x93BA => GlInternalFormat::CompressedRgbaAstc10x8,
+            0x93BB => GlInternalFormat::CompressedRgbaAstc10x10,
+            0x93BC => GlInternalFormat::CompressedRgbaAstc12x10,
+            0x93BD => GlInternalFormat::CompressedRgbaAstc12x12,
+            0x93D0 => GlInternalFormat::CompressedSrgb8Alpha8Astc4x4,
+            0x93D1 => GlInternalFormat::CompressedSrgb8Alpha8Astc5x4,
+            0x93D2 => GlInternalFormat::CompressedSrgb8Alpha8Astc5x5,
+            0x93D3 => GlInternalFormat::CompressedSrgb8Alpha8Astc6x5,
+            0x93D4 => GlInternalFormat::CompressedSrgb8Alpha8Astc6x6,
+            0x93D5 => GlInternalFormat::CompressedSrgb8Alpha8Astc8x5,
+            0x93D6 => GlInternalFormat::CompressedSrgb8Alpha8Astc8x6,
+            0x93D7 => GlInternalFormat::CompressedSrgb8Alpha8Astc8x8,
+            0x93D8 => GlInternalFormat::CompressedSrgb8Alpha8Astc10x5,
+            0x93D9 => GlInternalFormat::CompressedSrgb8Alpha8Astc10x6,
+            0x93DA => GlInternalFormat::CompressedSrgb8Alpha8Astc10x8,
+            0x93DB => GlInternalFormat::CompressedSrgb8Alpha8Astc10x10,
+            0x93DC => GlInternalFormat::CompressedSrgb8Alpha8Astc12x10,
+            0x93DD => GlInternalFormat::CompressedSrgb8Alpha8Astc12x12,
+            0x8C00 => GlInternalFormat::CompressedRgbPvrtc4Bppv1Img,
+            0x8C01 => GlInternalFormat::CompressedRgbPvrtc2Bppv1Img,
+            0x8C02 => GlInternalFormat::CompressedRgbaPvrtc4Bppv1Img,
+            0x8C03 => GlInternalFormat::CompressedRgbaPvrtc2Bppv1Img,
+            other => GlInternalFormat::Other(other),
+        }
+    }
+}
+
+impl GlInternalFormat {
+    /// Returns `true` if this is a sRGB-encoded format.
+    pub fn is_srgb(&self) -> bool {
+        matches!(
+            self,
+            GlInternalFormat::Srgb8
+                | GlInternalFormat::Srgb8Alpha8
+                | GlInternalFormat::CompressedSrgbS3tcDxt1
+                | GlInternalFormat::CompressedSrgbAlphaS3tcDxt1
+                | GlInternalFormat::CompressedSrgbAlphaS3tcDxt3
+                | GlInternalFormat::CompressedSrgbAlphaS3tcDxt5
+                | GlInternalFormat::CompressedSrgbAlphaBptcUnorm
+                | GlInternalFormat::CompressedSrgb8Etc2
+                | GlInternalFormat::CompressedSrgb8PunchthroughAlpha1Etc2
+                | GlInternalFormat::CompressedSrgb8Alpha8Etc2Eac
+                | GlInternalFormat::CompressedSrgb8Alpha8Astc4x4
+                | GlInternalFormat::CompressedSrgb8Alpha8Astc5x4
+                | GlInternalFormat::CompressedSrgb8Alpha8Astc5x5
+                | GlInternalFormat::CompressedSrgb8Alpha8Astc6x5
+                | GlInternalFormat::CompressedSrgb8Alpha8Astc6x6
+                | GlInternalFormat::CompressedSrgb8Alpha8Astc8x5
+                | GlInternalFormat::CompressedSrgb8Alpha8Astc8x6
+                | GlInternalFormat::CompressedSrgb8Alpha8Astc8x8
+                | GlInternalFormat::CompressedSrgb8Alpha8Astc10x5
+                | GlInternalFormat::CompressedSrgb8Alpha8Astc10x6
+                | GlInternalFormat::CompressedSrgb8Alpha8Astc10x8
+                | GlInternalFormat::CompressedSrgb8Alpha8Astc10x10
+                | GlInternalFormat::CompressedSrgb8Alpha8Astc12x10
+                | GlInternalFormat::CompressedSrgb8Alpha8Astc12x12
+        )
+    }
+}
+
+/// The OpenGL datatype of the data in a KTX1 [`crate::Texture`] (e.g. `GL_UNSIGNED_BYTE`).
+///
+/// Only the most common types are listed explicitly; any other value is kept as-is in [`GlType::Other`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum GlType {
+    Byte,
+    UnsignedByte,
+    Short,
+    UnsignedShort,
+    Int,
+    UnsignedInt,
+    Float,
+    HalfFloat,
+    UnsignedShort565,
+    UnsignedShort4444,
+    UnsignedShort5551,
+    UnsignedInt2101010Rev,
+    UnsignedInt10f11f11fRev,
+    UnsignedInt5999Rev,
+    UnsignedInt248,
+    Float32UnsignedInt248Rev,
+    Other(u32),
+}
+
+impl From<GlType> for u32 {
+    fn from(format: GlType) -> Self {
+        match format {
+            GlType::Byte => 0x1400,
+            GlType::UnsignedByte => 0x1401,
+            GlType::Short => 0x1402,
+            GlType::UnsignedShort => 0x1403,
+            GlType::Int => 0x1404,
+            GlType::UnsignedInt => 0x1405,
+            GlType::Float => 0x1406,
+            GlType::HalfFloat => 0x140B,
+            GlType::UnsignedShort565 => 0x8363,
+            GlType::UnsignedShort4444 => 0x8033,
+            GlType::UnsignedShort5551 => 0x8034,
+            GlType::UnsignedInt2101010Rev => 0x8368,
+            GlType::UnsignedInt10f11f11fRev => 0x8C3B,
+            GlType::UnsignedInt5999Rev => 0x8C3E,
+            GlType::UnsignedInt248 => 0x84FA,
+            GlType::Float32UnsignedInt248Rev => 0x8DAD,
+            GlType::Other(value) => value,
+        }
+    }
+}
+
+impl From<u32> for GlType {
+    fn from(format: u32) -> Self {
+        match format {
+            0x1400 => GlType::Byte,
+            0x1401 => GlType::UnsignedByte,
+            0x1402 => GlType::Short,
+            0x1403 => GlType::UnsignedShort,
+            0x1404 => GlType::Int,
+            0x1405 => GlType::UnsignedInt,
+            0x1406 => GlType::Float,
+            0x140B => GlType::HalfFloat,
+            0x8363 => GlType::UnsignedShort565,
+            0x8033 => GlType::UnsignedShort4444,
+            0x8034 => GlType::UnsignedShort5551,
+            0x8368 => GlType::UnsignedInt2101010Rev,
+            0x8C3B => GlType::UnsignedInt10f11f11fRev,
+            0x8C3E => GlType::UnsignedInt5999Rev,
+            0x84FA => GlType::UnsignedInt248,
+            0x8DAD => GlType::Float32UnsignedInt248Rev,
+            other => GlType::Other(other),
+        }
+    }
+}
+
 /// [`crate::Texture`] storage creation flags.
 ///
 /// See [`sys::ktxTextureCreateStorageEnum`].
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 #[repr(u32)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CreateStorage {
     NoStorage = sys::ktxTextureCreateStorageEnum_KTX_TEXTURE_CREATE_NO_STORAGE,
     AllocStorage = sys::ktxTextureCreateStorageEnum_KTX_TEXTURE_CREATE_ALLOC_STORAGE,
@@ -153,6 +1453,7 @@ bitflags! {
     ///
     /// See [`sys::ktxTextureCreateFlags`].
     #[derive(Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct TextureCreateFlags: u32 {
         const LOAD_IMAGE_DATA = sys::ktxTextureCreateFlagBits_KTX_TEXTURE_CREATE_LOAD_IMAGE_DATA_BIT;
         const RAW_KVDATA = sys::ktxTextureCreateFlagBits_KTX_TEXTURE_CREATE_RAW_KVDATA_BIT;
@@ -228,7 +1529,72 @@ pub struct Orientations {
     pub z: OrientationZ,
 }
 
+impl Default for Orientations {
+    /// The default orientation of KTX textures: `rdo` (right, down, out).
+    fn default() -> Self {
+        Orientations {
+            x: OrientationX::Right,
+            y: OrientationY::Down,
+            z: OrientationZ::Out,
+        }
+    }
+}
+
+impl Display for Orientations {
+    /// Formats the orientations in the `KTXorientation` form used by KTX2 (e.g. `rdi`).
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // The C enums' values are the ASCII characters used in `KTXorientation`
+        let chars = [self.x as u32, self.y as u32, self.z as u32];
+        for ch in chars.iter().filter_map(|ch| char::from_u32(*ch)) {
+            write!(f, "{}", ch)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Orientations {
+    type Err = &'static str;
+
+    /// Parses a `KTXorientation` string, either in its KTX2 (e.g. `rd`, `rdi`)
+    /// or in its KTX1 (e.g. `S=r,T=d`, `S=r,T=d,R=i`) form.  
+    /// Directions that are not specified are left to their default (see [`Orientations::default`]).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim_end_matches('\0');
+        let chars: Vec<char> = if s.contains('=') {
+            s.split(',')
+                .map(|part| part.rsplit('=').next().unwrap_or(part).trim())
+                .map(|value| {
+                    let mut chars = value.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(ch), None) => Ok(ch),
+                        _ => Err("Not a KTXorientation value"),
+                    }
+                })
+                .collect::<Result<_, _>>()?
+        } else {
+            s.chars().collect()
+        };
+        if chars.is_empty() || chars.len() > 3 {
+            return Err("Not a KTXorientation string");
+        }
+
+        let default = Orientations::default();
+        Ok(Orientations {
+            x: OrientationX::try_from(chars[0] as sys::ktxOrientationX)?,
+            y: match chars.get(1) {
+                Some(&y) => OrientationY::try_from(y as sys::ktxOrientationY)?,
+                None => default.y,
+            },
+            z: match chars.get(2) {
+                Some(&z) => OrientationZ::try_from(z as sys::ktxOrientationZ)?,
+                None => default.z,
+            },
+        })
+    }
+}
+
 bitflags! {
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct PackUastcFlags: u32 {
         const LEVEL_FASTEST  = sys::ktx_pack_uastc_flag_bits_e_KTX_PACK_UASTC_LEVEL_FASTEST;
         const LEVEL_FASTER   = sys::ktx_pack_uastc_flag_bits_e_KTX_PACK_UASTC_LEVEL_FASTER;
@@ -244,273 +1610,286 @@ bitflags! {
 }
 }
 
-/// The destination format for transcoding a [`crate::texture::Ktx2`] via Basis Universal.
-///
-/// See [`sys::ktx_transcode_fmt_e`].
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-#[repr(u32)]
-pub enum TranscodeFormat {
-    // ETC
-    Etc1Rgb = sys::ktx_transcode_fmt_e_KTX_TTF_ETC1_RGB,
-    Etc2Rgba = sys::ktx_transcode_fmt_e_KTX_TTF_ETC2_RGBA,
-    // BC
-    Bc1Rgb = sys::ktx_transcode_fmt_e_KTX_TTF_BC1_RGB,
-    Bc3Rgba = sys::ktx_transcode_fmt_e_KTX_TTF_BC3_RGBA,
-    Bc3R = sys::ktx_transcode_fmt_e_KTX_TTF_BC4_R,
-    Bc5Rg = sys::ktx_transcode_fmt_e_KTX_TTF_BC5_RG,
-    Bc7Rgba = sys::ktx_transcode_fmt_e_KTX_TTF_BC7_RGBA,
-    // PVRTC 1
-    Pvrtc14Rgb = sys::ktx_transcode_fmt_e_KTX_TTF_PVRTC1_4_RGB,
-    Pvrtc14Rgba = sys::ktx_transcode_fmt_e_KTX_TTF_PVRTC1_4_RGBA,
-    // ASTC
-    Astc4x4Rgba = sys::ktx_transcode_fmt_e_KTX_TTF_ASTC_4x4_RGBA,
-    // PVRTC 2
-    Pvrtc24Rgb = sys::ktx_transcode_fmt_e_KTX_TTF_PVRTC2_4_RGB,
-    Pvrtc24Rgba = sys::ktx_transcode_fmt_e_KTX_TTF_PVRTC2_4_RGBA,
-    // EAC
-    Etc2EacR11 = sys::ktx_transcode_fmt_e_KTX_TTF_ETC2_EAC_R11,
-    Etc2EacRg11 = sys::ktx_transcode_fmt_e_KTX_TTF_ETC2_EAC_RG11,
-    // Uncompressed (raw)
-    Rgba32 = sys::ktx_transcode_fmt_e_KTX_TTF_RGBA32,
-    Rgb565 = sys::ktx_transcode_fmt_e_KTX_TTF_RGB565,
-    Bgr565 = sys::ktx_transcode_fmt_e_KTX_TTF_BGR565,
-    Rgba4444 = sys::ktx_transcode_fmt_e_KTX_TTF_RGBA4444,
-    // Automatic selection
-    Etc = sys::ktx_transcode_fmt_e_KTX_TTF_ETC,
-    Bc1or3 = sys::ktx_transcode_fmt_e_KTX_TTF_BC1_OR_3,
-    // Misc.
-    NoSelection = sys::ktx_transcode_fmt_e_KTX_TTF_NOSELECTION,
-}
-
-impl TryFrom<u32> for TranscodeFormat {
-    type Error = &'static str;
+impl_try_from! {
+    /// A UASTC encoding quality level, i.e. one of the mutually exclusive `LEVEL_*` values of [`PackUastcFlags`].
+    ///
+    /// Higher levels are slower, but produce higher-quality results.
+    #[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub enum PackUastcLevel: "Not a ktx_pack_uastc_flag_bits_e level" {
+        Fastest = sys::ktx_pack_uastc_flag_bits_e_KTX_PACK_UASTC_LEVEL_FASTEST,
+        Faster = sys::ktx_pack_uastc_flag_bits_e_KTX_PACK_UASTC_LEVEL_FASTER,
+        #[default]
+        Default = sys::ktx_pack_uastc_flag_bits_e_KTX_PACK_UASTC_LEVEL_DEFAULT,
+        Slower = sys::ktx_pack_uastc_flag_bits_e_KTX_PACK_UASTC_LEVEL_SLOWER,
+        VerySlow = sys::ktx_pack_uastc_flag_bits_e_KTX_PACK_UASTC_LEVEL_VERYSLOW,
+    }
+}
 
-    fn try_from(value: u32) -> Result<Self, Self::Error> {
-        // TODO: A bit ugly (but still manageable), convert to a macro?
-        Ok(match value {
-            sys::ktx_transcode_fmt_e_KTX_TTF_ETC1_RGB => Self::Etc1Rgb,
-            sys::ktx_transcode_fmt_e_KTX_TTF_ETC2_RGBA => Self::Etc2Rgba,
-            // BC
-            sys::ktx_transcode_fmt_e_KTX_TTF_BC1_RGB => Self::Bc1Rgb,
-            sys::ktx_transcode_fmt_e_KTX_TTF_BC3_RGBA => Self::Bc3Rgba,
-            sys::ktx_transcode_fmt_e_KTX_TTF_BC4_R => Self::Bc3R,
-            sys::ktx_transcode_fmt_e_KTX_TTF_BC5_RG => Self::Bc5Rg,
-            sys::ktx_transcode_fmt_e_KTX_TTF_BC7_RGBA => Self::Bc7Rgba,
-            // PVRTC 1
-            sys::ktx_transcode_fmt_e_KTX_TTF_PVRTC1_4_RGB => Self::Pvrtc14Rgb,
-            sys::ktx_transcode_fmt_e_KTX_TTF_PVRTC1_4_RGBA => Self::Pvrtc14Rgba,
-            // ASTC
-            sys::ktx_transcode_fmt_e_KTX_TTF_ASTC_4x4_RGBA => Self::Astc4x4Rgba,
-            // PVRTC 2
-            sys::ktx_transcode_fmt_e_KTX_TTF_PVRTC2_4_RGB => Self::Pvrtc24Rgb,
-            sys::ktx_transcode_fmt_e_KTX_TTF_PVRTC2_4_RGBA => Self::Pvrtc24Rgba,
-            // EAC
-            sys::ktx_transcode_fmt_e_KTX_TTF_ETC2_EAC_R11 => Self::Etc2EacR11,
-            sys::ktx_transcode_fmt_e_KTX_TTF_ETC2_EAC_RG11 => Self::Etc2EacRg11,
-            // Uncompressed (raw)
-            sys::ktx_transcode_fmt_e_KTX_TTF_RGBA32 => Self::Rgba32,
-            sys::ktx_transcode_fmt_e_KTX_TTF_RGB565 => Self::Rgb565,
-            sys::ktx_transcode_fmt_e_KTX_TTF_BGR565 => Self::Bgr565,
-            sys::ktx_transcode_fmt_e_KTX_TTF_RGBA4444 => Self::Rgba4444,
-            // Automatic selection
-            sys::ktx_transcode_fmt_e_KTX_TTF_ETC => Self::Etc,
-            sys::ktx_transcode_fmt_e_KTX_TTF_BC1_OR_3 => Self::Bc1or3,
-            // Misc.
-            sys::ktx_transcode_fmt_e_KTX_TTF_NOSELECTION => Self::NoSelection,
-            _ => return Err("Not a KTX_ error variant"),
-        })
+impl PackUastcFlags {
+    /// Returns these flags with their quality level replaced by `level`.  
+    /// Any level bits previously set in `LEVEL_MASK` are cleared first, so levels can never be OR-ed together.
+    pub fn with_level(self, level: PackUastcLevel) -> Self {
+        (self & !Self::LEVEL_MASK) | Self::from_bits_truncate(level as u32)
+    }
+
+    /// Returns the quality level encoded in these flags, or `None` if the level bits are not a valid level.
+    pub fn level(self) -> Option<PackUastcLevel> {
+        PackUastcLevel::try_from((self & Self::LEVEL_MASK).bits()).ok()
     }
 }
 
-/// Quality level for ASTC compression.
-///
-/// This only applies to Arm's ASTC encoder, which is in `libktx-rs-sys/build/KTX-Software/lib/astc-encoder`.
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-#[repr(u32)]
-pub enum PackAstcQualityLevel {
-    Fastest = sys::ktx_pack_astc_quality_levels_e_KTX_PACK_ASTC_QUALITY_LEVEL_FASTEST,
-    Fast = sys::ktx_pack_astc_quality_levels_e_KTX_PACK_ASTC_QUALITY_LEVEL_FAST,
-    Medium = sys::ktx_pack_astc_quality_levels_e_KTX_PACK_ASTC_QUALITY_LEVEL_MEDIUM,
-    Thorough = sys::ktx_pack_astc_quality_levels_e_KTX_PACK_ASTC_QUALITY_LEVEL_THOROUGH,
-    Exhaustive = sys::ktx_pack_astc_quality_levels_e_KTX_PACK_ASTC_QUALITY_LEVEL_EXHAUSTIVE,
+impl Default for PackUastcFlags {
+    fn default() -> Self {
+        PackUastcFlags::LEVEL_DEFAULT
+    }
 }
 
-/// Block dimensions for ASTC compression.
-///
-/// This only applies to Arm's ASTC encoder, which is in `libktx-rs-sys/build/KTX-Software/lib/astc-encoder`.
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-#[repr(u32)]
-pub enum PackAstcBlockDimension {
-    /// 2D, 8.0 bpp
-    Dim4x4 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_4x4,
-    /// 2D, 6.40 bpp
-    Dim5x4 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_5x4,
-    /// 2D, 5.12 bpp
-    Dim5x5 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_5x5,
-    /// 2D, 4.27 bpp
-    Dim6x5 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_6x5,
-    /// 2D, 3.56 bpp
-    Dim6x6 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_6x6,
-    /// 2D, 3.20 bpp
-    Dim8x5 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_8x5,
-    /// 2D, 2.67 bpp
-    Dim8x6 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_8x6,
-    /// 2D, 2.56 bpp
-    Dim10x5 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_10x5,
-    /// 2D, 2.13 bpp
-    Dim10x6 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_10x6,
-    /// 2D, 2.00 bpp
-    Dim8x8 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_8x8,
-    /// 2D, 1.60 bpp
-    Dim10x8 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_10x8,
-    /// 2D, 1.28 bpp
-    Dim10x10 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_10x10,
-    /// 2D, 1.07 bpp
-    Dim12x10 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_12x10,
-    /// 2D, 0.89 bpp
-    Dim12x12 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_12x12,
-
-    /// 3D, 4.74 bpp
-    Dim3x3x3 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_3x3x3,
-    /// 3D, 3.56 bpp
-    Dim4x3x3 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_4x3x3,
-    /// 3D, 2.67 bpp
-    Dim4x4x3 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_4x4x3,
-    /// 3D, 2.00 bpp
-    Dim4x4x4 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_4x4x4,
-    /// 3D, 1.60 bpp
-    Dim5x4x4 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_5x4x4,
-    /// 3D, 1.28 bpp
-    Dim5x5x4 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_5x5x4,
-    /// 3D, 1.02 bpp
-    Dim5x5x5 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_5x5x5,
-    /// 3D, 0.85 bpp
-    Dim6x5x5 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_6x5x5,
-    /// 3D, 0.71 bpp
-    Dim6x6x5 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_6x6x5,
-    /// 3D, 0.59 bpp
-    Dim6x6x6 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_6x6x6,
-}
-
-impl TryFrom<u32> for PackAstcBlockDimension {
-    type Error = &'static str;
+impl_try_from! {
+    /// The destination format for transcoding a [`crate::texture::Ktx2`] via Basis Universal.
+    ///
+    /// See [`sys::ktx_transcode_fmt_e`]. ATC and FXT1 are not available, as `ktx_transcode_fmt_e` defines no values for them
+    /// (even though Basis Universal itself can transcode to both).
+    #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub enum TranscodeFormat: "Not a ktx_transcode_fmt_e variant" {
+        // ETC
+        Etc1Rgb = sys::ktx_transcode_fmt_e_KTX_TTF_ETC1_RGB,
+        Etc2Rgba = sys::ktx_transcode_fmt_e_KTX_TTF_ETC2_RGBA,
+        // BC
+        Bc1Rgb = sys::ktx_transcode_fmt_e_KTX_TTF_BC1_RGB,
+        Bc3Rgba = sys::ktx_transcode_fmt_e_KTX_TTF_BC3_RGBA,
+        Bc4R = sys::ktx_transcode_fmt_e_KTX_TTF_BC4_R,
+        Bc5Rg = sys::ktx_transcode_fmt_e_KTX_TTF_BC5_RG,
+        Bc7Rgba = sys::ktx_transcode_fmt_e_KTX_TTF_BC7_RGBA,
+        // PVRTC 1
+        Pvrtc14Rgb = sys::ktx_transcode_fmt_e_KTX_TTF_PVRTC1_4_RGB,
+        Pvrtc14Rgba = sys::ktx_transcode_fmt_e_KTX_TTF_PVRTC1_4_RGBA,
+        // ASTC
+        Astc4x4Rgba = sys::ktx_transcode_fmt_e_KTX_TTF_ASTC_4x4_RGBA,
+        // PVRTC 2
+        Pvrtc24Rgb = sys::ktx_transcode_fmt_e_KTX_TTF_PVRTC2_4_RGB,
+        Pvrtc24Rgba = sys::ktx_transcode_fmt_e_KTX_TTF_PVRTC2_4_RGBA,
+        // EAC
+        Etc2EacR11 = sys::ktx_transcode_fmt_e_KTX_TTF_ETC2_EAC_R11,
+        Etc2EacRg11 = sys::ktx_transcode_fmt_e_KTX_TTF_ETC2_EAC_RG11,
+        // Uncompressed (raw)
+        Rgba32 = sys::ktx_transcode_fmt_e_KTX_TTF_RGBA32,
+        Rgb565 = sys::ktx_transcode_fmt_e_KTX_TTF_RGB565,
+        Bgr565 = sys::ktx_transcode_fmt_e_KTX_TTF_BGR565,
+        Rgba4444 = sys::ktx_transcode_fmt_e_KTX_TTF_RGBA4444,
+        // Automatic selection
+        Etc = sys::ktx_transcode_fmt_e_KTX_TTF_ETC,
+        Bc1or3 = sys::ktx_transcode_fmt_e_KTX_TTF_BC1_OR_3,
+        // Misc.
+        NoSelection = sys::ktx_transcode_fmt_e_KTX_TTF_NOSELECTION,
+    }
+}
 
-    fn try_from(value: u32) -> Result<Self, Self::Error> {
-        // TODO: A bit ugly (but still manageable), convert to a macro?
-        Ok(match value {
-            // 2D
-            sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_4x4 => Self::Dim4x4,
-            sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_5x4 => Self::Dim5x4,
-            sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_5x5 => Self::Dim5x5,
-            sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_6x5 => Self::Dim6x5,
-            sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_6x6 => Self::Dim6x6,
-            sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_8x5 => Self::Dim8x5,
-            sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_8x6 => Self::Dim8x6,
-            sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_10x5 => {
-                Self::Dim10x5
-            }
-            sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_10x6 => {
-                Self::Dim10x6
-            }
-            sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_8x8 => Self::Dim8x8,
-            sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_10x8 => {
-                Self::Dim10x8
-            }
-            sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_10x10 => {
-                Self::Dim10x10
-            }
-            sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_12x10 => {
-                Self::Dim12x10
-            }
-            sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_12x12 => {
-                Self::Dim12x12
-            }
+impl TranscodeFormat {
+    /// The former (mislabeled) name of [`TranscodeFormat::Bc4R`].
+    #[deprecated(note = "Use `TranscodeFormat::Bc4R` instead")]
+    #[allow(non_upper_case_globals)]
+    pub const Bc3R: TranscodeFormat = TranscodeFormat::Bc4R;
 
-            // 3D
-            sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_3x3x3 => {
-                Self::Dim3x3x3
-            }
-            sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_4x3x3 => {
-                Self::Dim4x3x3
-            }
-            sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_4x4x3 => {
-                Self::Dim4x4x3
-            }
-            sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_4x4x4 => {
-                Self::Dim4x4x4
-            }
-            sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_5x4x4 => {
-                Self::Dim5x4x4
-            }
-            sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_5x5x4 => {
-                Self::Dim5x5x4
-            }
-            sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_5x5x5 => {
-                Self::Dim5x5x5
-            }
-            sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_6x5x5 => {
-                Self::Dim6x5x5
+    /// Returns `true` if this is an uncompressed (raw) format, like [`TranscodeFormat::Rgba32`].
+    pub fn is_uncompressed(self) -> bool {
+        matches!(
+            self,
+            Self::Rgba32 | Self::Rgb565 | Self::Bgr565 | Self::Rgba4444
+        )
+    }
+
+    /// Returns the `(width, height)` (in pixels) of a block of this block-compressed format.
+    ///
+    /// Returns `None` for uncompressed formats, and for [`TranscodeFormat::NoSelection`].
+    pub fn block_dimensions(self) -> Option<(u32, u32)> {
+        match self {
+            Self::Rgba32 | Self::Rgb565 | Self::Bgr565 | Self::Rgba4444 | Self::NoSelection => None,
+            // All other formats (including automatically-selected ones) have 4x4 blocks
+            _ => Some((4, 4)),
+        }
+    }
+
+    /// Returns the size (in bytes) of a block of this block-compressed format.
+    ///
+    /// Returns `None` for uncompressed formats, and for formats whose actual block size
+    /// depends on an automatic selection (like [`TranscodeFormat::Etc`]).
+    pub fn bytes_per_block(self) -> Option<u32> {
+        match self {
+            Self::Etc1Rgb
+            | Self::Bc1Rgb
+            | Self::Bc4R
+            | Self::Pvrtc14Rgb
+            | Self::Pvrtc14Rgba
+            | Self::Pvrtc24Rgb
+            | Self::Pvrtc24Rgba
+            | Self::Etc2EacR11 => Some(8),
+            Self::Etc2Rgba
+            | Self::Bc3Rgba
+            | Self::Bc5Rg
+            | Self::Bc7Rgba
+            | Self::Astc4x4Rgba
+            | Self::Etc2EacRg11 => Some(16),
+            Self::Rgba32
+            | Self::Rgb565
+            | Self::Bgr565
+            | Self::Rgba4444
+            | Self::Etc
+            | Self::Bc1or3
+            | Self::NoSelection => None,
+        }
+    }
+
+    /// Recommends a format to transcode a texture with `num_components` components to,
+    /// on a GPU with the given `features`; `has_alpha` tells whether the last component is alpha.
+    ///
+    /// Following the Basis Universal guidance, this picks the smallest format that keeps all channels:
+    /// - 1 channel: [`Self::Bc4R`] or [`Self::Etc2EacR11`];
+    /// - 2 channels (without alpha): [`Self::Bc5Rg`] or [`Self::Etc2EacRg11`];
+    /// - RGB: [`Self::Bc1Rgb`] or [`Self::Etc1Rgb`];
+    /// - anything with alpha: [`Self::Bc7Rgba`] or [`Self::Etc2Rgba`].
+    ///
+    /// ASTC and PVRTC1 have no single- or dual-channel variants, so [`Self::Astc4x4Rgba`] and
+    /// [`Self::Pvrtc14Rgb`]/[`Self::Pvrtc14Rgba`] are used for those.  
+    /// If `features` contains several families, the first of BC, ETC2, ASTC and PVRTC1 wins;
+    /// [`crate::texture::Ktx2::select_transcode_format`] instead passes one family at a time, in its own order.
+    /// If it is empty, this falls back to uncompressed [`Self::Rgba32`].
+    pub fn preferred_for_components(
+        num_components: u32,
+        has_alpha: bool,
+        features: GpuFeatures,
+    ) -> TranscodeFormat {
+        let num_channels = match (num_components, has_alpha) {
+            (_, true) => 4,
+            (1, false) | (2, false) => num_components,
+            _ => 3,
+        };
+        if features.contains(GpuFeatures::BC7) {
+            match num_channels {
+                1 => Self::Bc4R,
+                2 => Self::Bc5Rg,
+                3 => Self::Bc1Rgb,
+                _ => Self::Bc7Rgba,
             }
-            sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_6x6x5 => {
-                Self::Dim6x6x5
+        } else if features.contains(GpuFeatures::ETC2) {
+            match num_channels {
+                1 => Self::Etc2EacR11,
+                2 => Self::Etc2EacRg11,
+                3 => Self::Etc1Rgb,
+                _ => Self::Etc2Rgba,
             }
-            sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_6x6x6 => {
-                Self::Dim6x6x6
+        } else if features.contains(GpuFeatures::ASTC) {
+            Self::Astc4x4Rgba
+        } else if features.contains(GpuFeatures::PVRTC) {
+            if has_alpha {
+                Self::Pvrtc14Rgba
+            } else {
+                Self::Pvrtc14Rgb
             }
-            _ => return Err("Not a ASTC block dimension enumerant"),
-        })
+        } else {
+            Self::Rgba32
+        }
     }
 }
 
-/// ASTC encoder profile function.
-///
-/// This only applies to Arm's ASTC encoder, which is in `libktx-rs-sys/build/KTX-Software/lib/astc-encoder`.
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-#[repr(u32)]
-pub enum PackAstcEncoderFunction {
-    Unknown = sys::ktx_pack_astc_encoder_function_e_KTX_PACK_ASTC_ENCODER_FUNCTION_UNKNOWN,
-    Srgb = sys::ktx_pack_astc_encoder_function_e_KTX_PACK_ASTC_ENCODER_FUNCTION_SRGB,
-    Linear = sys::ktx_pack_astc_encoder_function_e_KTX_PACK_ASTC_ENCODER_FUNCTION_LINEAR,
+impl_try_from! {
+    /// Quality level for ASTC compression.
+    ///
+    /// This only applies to Arm's ASTC encoder, which is in `libktx-rs-sys/build/KTX-Software/lib/astc-encoder`.
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub enum PackAstcQualityLevel: "Not a ktx_pack_astc_quality_levels_e variant" {
+        Fastest = sys::ktx_pack_astc_quality_levels_e_KTX_PACK_ASTC_QUALITY_LEVEL_FASTEST,
+        Fast = sys::ktx_pack_astc_quality_levels_e_KTX_PACK_ASTC_QUALITY_LEVEL_FAST,
+        Medium = sys::ktx_pack_astc_quality_levels_e_KTX_PACK_ASTC_QUALITY_LEVEL_MEDIUM,
+        Thorough = sys::ktx_pack_astc_quality_levels_e_KTX_PACK_ASTC_QUALITY_LEVEL_THOROUGH,
+        Exhaustive = sys::ktx_pack_astc_quality_levels_e_KTX_PACK_ASTC_QUALITY_LEVEL_EXHAUSTIVE,
+    }
 }
 
-impl TryFrom<u32> for PackAstcEncoderFunction {
-    type Error = &'static str;
+impl_try_from! {
+    /// Block dimensions for ASTC compression.
+    ///
+    /// This only applies to Arm's ASTC encoder, which is in `libktx-rs-sys/build/KTX-Software/lib/astc-encoder`.
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub enum PackAstcBlockDimension: "Not a ktx_pack_astc_block_dimension_e variant" {
+        /// 2D, 8.0 bpp
+        Dim4x4 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_4x4,
+        /// 2D, 6.40 bpp
+        Dim5x4 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_5x4,
+        /// 2D, 5.12 bpp
+        Dim5x5 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_5x5,
+        /// 2D, 4.27 bpp
+        Dim6x5 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_6x5,
+        /// 2D, 3.56 bpp
+        Dim6x6 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_6x6,
+        /// 2D, 3.20 bpp
+        Dim8x5 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_8x5,
+        /// 2D, 2.67 bpp
+        Dim8x6 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_8x6,
+        /// 2D, 2.56 bpp
+        Dim10x5 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_10x5,
+        /// 2D, 2.13 bpp
+        Dim10x6 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_10x6,
+        /// 2D, 2.00 bpp
+        Dim8x8 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_8x8,
+        /// 2D, 1.60 bpp
+        Dim10x8 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_10x8,
+        /// 2D, 1.28 bpp
+        Dim10x10 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_10x10,
+        /// 2D, 1.07 bpp
+        Dim12x10 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_12x10,
+        /// 2D, 0.89 bpp
+        Dim12x12 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_12x12,
 
-    fn try_from(value: u32) -> Result<Self, Self::Error> {
-        Ok(match value {
-            sys::ktx_pack_astc_encoder_function_e_KTX_PACK_ASTC_ENCODER_FUNCTION_UNKNOWN => {
-                Self::Unknown
-            }
-            sys::ktx_pack_astc_encoder_function_e_KTX_PACK_ASTC_ENCODER_FUNCTION_SRGB => Self::Srgb,
-            sys::ktx_pack_astc_encoder_function_e_KTX_PACK_ASTC_ENCODER_FUNCTION_LINEAR => {
-                Self::Linear
-            }
-            _ => return Err("Not a ASTC encoder function enumerant"),
-        })
+        /// 3D, 4.74 bpp
+        Dim3x3x3 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_3x3x3,
+        /// 3D, 3.56 bpp
+        Dim4x3x3 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_4x3x3,
+        /// 3D, 2.67 bpp
+        Dim4x4x3 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_4x4x3,
+        /// 3D, 2.00 bpp
+        Dim4x4x4 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_4x4x4,
+        /// 3D, 1.60 bpp
+        Dim5x4x4 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_5x4x4,
+        /// 3D, 1.28 bpp
+        Dim5x5x4 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_5x5x4,
+        /// 3D, 1.02 bpp
+        Dim5x5x5 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_5x5x5,
+        /// 3D, 0.85 bpp
+        Dim6x5x5 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_6x5x5,
+        /// 3D, 0.71 bpp
+        Dim6x6x5 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_6x6x5,
+        /// 3D, 0.59 bpp
+        Dim6x6x6 = sys::ktx_pack_astc_block_dimension_e_KTX_PACK_ASTC_BLOCK_DIMENSION_6x6x6,
     }
 }
 
-/// ASTC encoder mode.
-///
-/// This only applies to Arm's ASTC encoder, which is in `libktx-rs-sys/build/KTX-Software/lib/astc-encoder`.
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-#[repr(u32)]
-pub enum PackAstcEncoderMode {
-    Default = sys::ktx_pack_astc_encoder_mode_e_KTX_PACK_ASTC_ENCODER_MODE_DEFAULT,
-    Ldr = sys::ktx_pack_astc_encoder_mode_e_KTX_PACK_ASTC_ENCODER_MODE_LDR,
-    Hdr = sys::ktx_pack_astc_encoder_mode_e_KTX_PACK_ASTC_ENCODER_MODE_HDR,
+impl_try_from! {
+    /// ASTC encoder profile function.
+    ///
+    /// This only applies to Arm's ASTC encoder, which is in `libktx-rs-sys/build/KTX-Software/lib/astc-encoder`.
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub enum PackAstcEncoderFunction: "Not a ktx_pack_astc_encoder_function_e variant" {
+        Unknown = sys::ktx_pack_astc_encoder_function_e_KTX_PACK_ASTC_ENCODER_FUNCTION_UNKNOWN,
+        Srgb = sys::ktx_pack_astc_encoder_function_e_KTX_PACK_ASTC_ENCODER_FUNCTION_SRGB,
+        Linear = sys::ktx_pack_astc_encoder_function_e_KTX_PACK_ASTC_ENCODER_FUNCTION_LINEAR,
+    }
 }
 
-impl TryFrom<u32> for PackAstcEncoderMode {
-    type Error = &'static str;
-
-    fn try_from(value: u32) -> Result<Self, Self::Error> {
-        Ok(match value {
-            sys::ktx_pack_astc_encoder_mode_e_KTX_PACK_ASTC_ENCODER_MODE_DEFAULT => Self::Default,
-            sys::ktx_pack_astc_encoder_mode_e_KTX_PACK_ASTC_ENCODER_MODE_LDR => Self::Ldr,
-            sys::ktx_pack_astc_encoder_mode_e_KTX_PACK_ASTC_ENCODER_MODE_HDR => Self::Hdr,
-            _ => return Err("Not a ASTC encoder mode enumerant"),
-        })
+impl_try_from! {
+    /// ASTC encoder mode.
+    ///
+    /// This only applies to Arm's ASTC encoder, which is in `libktx-rs-sys/build/KTX-Software/lib/astc-encoder`.
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub enum PackAstcEncoderMode: "Not a ktx_pack_astc_encoder_mode_e variant" {
+        Default = sys::ktx_pack_astc_encoder_mode_e_KTX_PACK_ASTC_ENCODER_MODE_DEFAULT,
+        Ldr = sys::ktx_pack_astc_encoder_mode_e_KTX_PACK_ASTC_ENCODER_MODE_LDR,
+        Hdr = sys::ktx_pack_astc_encoder_mode_e_KTX_PACK_ASTC_ENCODER_MODE_HDR,
     }
 }
 
@@ -519,9 +1898,27 @@ bitflags! {
     ///
     /// See [`sys::ktx_transcode_flags`].
     #[derive(Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct TranscodeFlags: u32 {
         const PVRTC_DECODE_TO_NEXT_POW2 = sys::ktx_transcode_flag_bits_e_KTX_TF_PVRTC_DECODE_TO_NEXT_POW2;
         const TRANSCODE_ALPHA_DATA_TO_OPAQUE_FORMATS = sys::ktx_transcode_flag_bits_e_KTX_TF_TRANSCODE_ALPHA_DATA_TO_OPAQUE_FORMATS;
         const HIGH_QUALITY = sys::ktx_transcode_flag_bits_e_KTX_TF_HIGH_QUALITY;
     }
 }
+
+bitflags! {
+    /// The compressed texture formats supported by a target GPU, for choosing a [`TranscodeFormat`];
+    /// see [`crate::texture::Ktx2::transcode_for`].
+    #[derive(Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct GpuFeatures: u32 {
+        /// BC7 (BPTC), e.g. `textureCompressionBC` in Vulkan; common on desktop GPUs.
+        const BC7 = 1 << 0;
+        /// ASTC LDR, e.g. `textureCompressionASTC_LDR` in Vulkan; common on recent mobile GPUs.
+        const ASTC = 1 << 1;
+        /// ETC2 and EAC, e.g. `textureCompressionETC2` in Vulkan; common on mobile GPUs.
+        const ETC2 = 1 << 2;
+        /// PVRTC1, as found on PowerVR (and older iOS) GPUs.
+        const PVRTC = 1 << 3;
+    }
+}
@@ -0,0 +1,79 @@
+// Copyright (C) 2021 Paolo Jovon <paolo.jovon@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Structured errors, wrapping the low-level [`KtxError`] codes of the underlying C library.
+
+use crate::KtxError;
+use std::fmt::{Display, Formatter};
+
+/// An error returned by a high-level operation on [`crate::Texture`]s.
+///
+/// This wraps the low-level [`KtxError`] code, plus a description of the operation that failed (if known);
+/// the [`Display`] message reads like `"transcode_basis(Rgba32) failed: <C error message>"`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    code: KtxError,
+    context: Option<String>,
+}
+
+impl Error {
+    /// Creates a new error with the given `code`, that happened while performing the `context` operation.
+    pub fn new<C: Into<String>>(code: KtxError, context: C) -> Self {
+        Error {
+            code,
+            context: Some(context.into()),
+        }
+    }
+
+    /// Returns the low-level error code.
+    pub fn code(&self) -> KtxError {
+        self.code
+    }
+
+    /// Returns a description of the operation that failed, if known.
+    pub fn context(&self) -> Option<&str> {
+        self.context.as_deref()
+    }
+}
+
+impl From<KtxError> for Error {
+    fn from(code: KtxError) -> Self {
+        Error {
+            code,
+            context: None,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.context {
+            Some(context) => write!(f, "{} failed: {}", context, self.code),
+            None => write!(f, "{}", self.code),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.code)
+    }
+}
+
+impl From<Error> for std::io::Error {
+    fn from(error: Error) -> Self {
+        let kind = std::io::Error::from(error.code).kind();
+        std::io::Error::new(kind, error)
+    }
+}
+
+/// Adds context to a low-level [`KtxError`] result, turning it into a [`Error`] one.
+pub(crate) trait ResultExt<T> {
+    fn context<C: Into<String>, F: FnOnce() -> C>(self, context: F) -> Result<T, Error>;
+}
+
+impl<T> ResultExt<T> for Result<T, KtxError> {
+    fn context<C: Into<String>, F: FnOnce() -> C>(self, context: F) -> Result<T, Error> {
+        self.map_err(|code| Error::new(code, context()))
+    }
+}
@@ -0,0 +1,58 @@
+// Copyright (C) 2021 Paolo Jovon <paolo.jovon@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Conversions between [`crate::Texture`]s and [`image`] buffers.
+
+#[cfg(feature = "write")]
+use crate::{sources::Ktx2CreateInfo, CreateStorage, Texture};
+use crate::{texture::Ktx2, KtxError, VkFormat};
+use image::RgbaImage;
+
+#[cfg(feature = "write")]
+impl Texture<'static> {
+    /// Attempts to create a new single-level 2D KTX2 texture, and to copy `img`'s pixels into it.
+    ///
+    /// The texture's format is [`VkFormat::R8G8B8A8Srgb`] if `vk_srgb` is true,
+    /// or [`VkFormat::R8G8B8A8Unorm`] otherwise.
+    pub fn from_rgba8_image(img: &RgbaImage, vk_srgb: bool) -> Result<Self, KtxError> {
+        let vk_format = if vk_srgb {
+            VkFormat::R8G8B8A8Srgb
+        } else {
+            VkFormat::R8G8B8A8Unorm
+        };
+        let create_info = Ktx2CreateInfo::builder()
+            .vk_format(vk_format)
+            .storage(CreateStorage::AllocStorage)
+            .dimensions(img.width(), img.height(), 1)
+            .build()?;
+        let mut texture = Texture::new(create_info).map_err(|err| err.code())?;
+        texture.set_image(0, 0, 0, img.as_raw())?;
+        Ok(texture)
+    }
+}
+
+impl<'a, 'b: 'a> Ktx2<'a, 'b> {
+    /// Attempts to copy the image at the given mip level, array layer, and slice into a new [`RgbaImage`].  
+    /// `slice` is either a cubemap's face or a 3D texture's depth slice.
+    ///
+    /// Fails with [`KtxError::UnsupportedFeature`] unless the texture's format is
+    /// [`VkFormat::R8G8B8A8Unorm`] or [`VkFormat::R8G8B8A8Srgb`]; Basis Universal textures
+    /// can be brought to the latter by transcoding them to [`crate::TranscodeFormat::Rgba32`] first.
+    pub fn to_rgba8_image(
+        &self,
+        level: u32,
+        layer: u32,
+        slice: u32,
+    ) -> Result<RgbaImage, KtxError> {
+        if !matches!(
+            self.vk_format(),
+            VkFormat::R8G8B8A8Unorm | VkFormat::R8G8B8A8Srgb
+        ) {
+            return Err(KtxError::UnsupportedFeature);
+        }
+        let data = self.texture.get_image_data(level, layer, slice)?;
+        let extent = self.texture.extent_at_level(level);
+        RgbaImage::from_raw(extent.width as u32, extent.height as u32, data.to_vec())
+            .ok_or(KtxError::InvalidValue)
+    }
+}
@@ -0,0 +1,10 @@
+// Copyright (C) 2021 Paolo Jovon <paolo.jovon@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Interoperability with other crates, each behind an optional feature of the same name.
+
+#[cfg(feature = "image")]
+pub mod image;
+
+#[cfg(feature = "wgpu")]
+pub mod wgpu;
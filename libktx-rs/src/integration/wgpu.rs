@@ -0,0 +1,143 @@
+// Copyright (C) 2021 Paolo Jovon <paolo.jovon@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Mapping KTX2 textures to [`wgpu`] (i.e. `wgpu-types`) formats and texture descriptors.
+
+use crate::{texture::Ktx2, VkFormat};
+
+/// Returns the [`wgpu::TextureFormat`] that corresponds exactly to `vk_format`, if any.
+///
+/// Formats with no exact wgpu counterpart (e.g. 3-channel, packed, or PVRTC formats) yield `None`.
+pub fn wgpu_texture_format(vk_format: VkFormat) -> Option<wgpu::TextureFormat> {
+    use wgpu::TextureFormat as Wf;
+    use VkFormat as Vf;
+    Some(match vk_format {
+        Vf::R8Unorm => Wf::R8Unorm,
+        Vf::R8Snorm => Wf::R8Snorm,
+        Vf::R8Uint => Wf::R8Uint,
+        Vf::R8Sint => Wf::R8Sint,
+        Vf::R8G8Unorm => Wf::Rg8Unorm,
+        Vf::R8G8Snorm => Wf::Rg8Snorm,
+        Vf::R8G8Uint => Wf::Rg8Uint,
+        Vf::R8G8Sint => Wf::Rg8Sint,
+        Vf::R8G8B8A8Unorm => Wf::Rgba8Unorm,
+        Vf::R8G8B8A8Snorm => Wf::Rgba8Snorm,
+        Vf::R8G8B8A8Uint => Wf::Rgba8Uint,
+        Vf::R8G8B8A8Sint => Wf::Rgba8Sint,
+        Vf::R8G8B8A8Srgb => Wf::Rgba8UnormSrgb,
+        Vf::B8G8R8A8Unorm => Wf::Bgra8Unorm,
+        Vf::B8G8R8A8Srgb => Wf::Bgra8UnormSrgb,
+        Vf::A2B10G10R10UnormPack32 => Wf::Rgb10a2Unorm,
+        Vf::R16Unorm => Wf::R16Unorm,
+        Vf::R16Uint => Wf::R16Uint,
+        Vf::R16Sint => Wf::R16Sint,
+        Vf::R16Sfloat => Wf::R16Float,
+        Vf::R16G16Unorm => Wf::Rg16Unorm,
+        Vf::R16G16Sfloat => Wf::Rg16Float,
+        Vf::R16G16B16A16Unorm => Wf::Rgba16Unorm,
+        Vf::R16G16B16A16Uint => Wf::Rgba16Uint,
+        Vf::R16G16B16A16Sint => Wf::Rgba16Sint,
+        Vf::R16G16B16A16Sfloat => Wf::Rgba16Float,
+        Vf::R32Uint => Wf::R32Uint,
+        Vf::R32Sint => Wf::R32Sint,
+        Vf::R32Sfloat => Wf::R32Float,
+        Vf::R32G32Uint => Wf::Rg32Uint,
+        Vf::R32G32Sint => Wf::Rg32Sint,
+        Vf::R32G32Sfloat => Wf::Rg32Float,
+        Vf::R32G32B32A32Uint => Wf::Rgba32Uint,
+        Vf::R32G32B32A32Sint => Wf::Rgba32Sint,
+        Vf::R32G32B32A32Sfloat => Wf::Rgba32Float,
+        Vf::B10G11R11UfloatPack32 => Wf::Rg11b10Float,
+        Vf::E5B9G9R9UfloatPack32 => Wf::Rgb9e5Ufloat,
+        Vf::D32Sfloat => Wf::Depth32Float,
+        Vf::Bc1RgbaUnormBlock => Wf::Bc1RgbaUnorm,
+        Vf::Bc1RgbaSrgbBlock => Wf::Bc1RgbaUnormSrgb,
+        Vf::Bc2UnormBlock => Wf::Bc2RgbaUnorm,
+        Vf::Bc2SrgbBlock => Wf::Bc2RgbaUnormSrgb,
+        Vf::Bc3UnormBlock => Wf::Bc3RgbaUnorm,
+        Vf::Bc3SrgbBlock => Wf::Bc3RgbaUnormSrgb,
+        Vf::Bc4UnormBlock => Wf::Bc4RUnorm,
+        Vf::Bc4SnormBlock => Wf::Bc4RSnorm,
+        Vf::Bc5UnormBlock => Wf::Bc5RgUnorm,
+        Vf::Bc5SnormBlock => Wf::Bc5RgSnorm,
+        Vf::Bc6hUfloatBlock => Wf::Bc6hRgbUfloat,
+        Vf::Bc6hSfloatBlock => Wf::Bc6hRgbSfloat,
+        Vf::Bc7UnormBlock => Wf::Bc7RgbaUnorm,
+        Vf::Bc7SrgbBlock => Wf::Bc7RgbaUnormSrgb,
+        Vf::Etc2R8G8B8UnormBlock => Wf::Etc2Rgb8Unorm,
+        Vf::Etc2R8G8B8SrgbBlock => Wf::Etc2Rgb8UnormSrgb,
+        Vf::Etc2R8G8B8A1UnormBlock => Wf::Etc2Rgb8A1Unorm,
+        Vf::Etc2R8G8B8A1SrgbBlock => Wf::Etc2Rgb8A1UnormSrgb,
+        Vf::Etc2R8G8B8A8UnormBlock => Wf::Etc2Rgba8Unorm,
+        Vf::Etc2R8G8B8A8SrgbBlock => Wf::Etc2Rgba8UnormSrgb,
+        Vf::EacR11UnormBlock => Wf::EacR11Unorm,
+        Vf::EacR11SnormBlock => Wf::EacR11Snorm,
+        Vf::EacR11G11UnormBlock => Wf::EacRg11Unorm,
+        Vf::EacR11G11SnormBlock => Wf::EacRg11Snorm,
+        Vf::Astc4x4UnormBlock => Wf::Astc4x4RgbaUnorm,
+        Vf::Astc4x4SrgbBlock => Wf::Astc4x4RgbaUnormSrgb,
+        Vf::Astc5x4UnormBlock => Wf::Astc5x4RgbaUnorm,
+        Vf::Astc5x4SrgbBlock => Wf::Astc5x4RgbaUnormSrgb,
+        Vf::Astc5x5UnormBlock => Wf::Astc5x5RgbaUnorm,
+        Vf::Astc5x5SrgbBlock => Wf::Astc5x5RgbaUnormSrgb,
+        Vf::Astc6x5UnormBlock => Wf::Astc6x5RgbaUnorm,
+        Vf::Astc6x5SrgbBlock => Wf::Astc6x5RgbaUnormSrgb,
+        Vf::Astc6x6UnormBlock => Wf::Astc6x6RgbaUnorm,
+        Vf::Astc6x6SrgbBlock => Wf::Astc6x6RgbaUnormSrgb,
+        Vf::Astc8x5UnormBlock => Wf::Astc8x5RgbaUnorm,
+        Vf::Astc8x5SrgbBlock => Wf::Astc8x5RgbaUnormSrgb,
+        Vf::Astc8x6UnormBlock => Wf::Astc8x6RgbaUnorm,
+        Vf::Astc8x6SrgbBlock => Wf::Astc8x6RgbaUnormSrgb,
+        Vf::Astc8x8UnormBlock => Wf::Astc8x8RgbaUnorm,
+        Vf::Astc8x8SrgbBlock => Wf::Astc8x8RgbaUnormSrgb,
+        Vf::Astc10x5UnormBlock => Wf::Astc10x5RgbaUnorm,
+        Vf::Astc10x5SrgbBlock => Wf::Astc10x5RgbaUnormSrgb,
+        Vf::Astc10x6UnormBlock => Wf::Astc10x6RgbaUnorm,
+        Vf::Astc10x6SrgbBlock => Wf::Astc10x6RgbaUnormSrgb,
+        Vf::Astc10x8UnormBlock => Wf::Astc10x8RgbaUnorm,
+        Vf::Astc10x8SrgbBlock => Wf::Astc10x8RgbaUnormSrgb,
+        Vf::Astc10x10UnormBlock => Wf::Astc10x10RgbaUnorm,
+        Vf::Astc10x10SrgbBlock => Wf::Astc10x10RgbaUnormSrgb,
+        Vf::Astc12x10UnormBlock => Wf::Astc12x10RgbaUnorm,
+        Vf::Astc12x10SrgbBlock => Wf::Astc12x10RgbaUnormSrgb,
+        Vf::Astc12x12UnormBlock => Wf::Astc12x12RgbaUnorm,
+        Vf::Astc12x12SrgbBlock => Wf::Astc12x12RgbaUnormSrgb,
+        _ => return None,
+    })
+}
+
+impl<'a, 'b: 'a> Ktx2<'a, 'b> {
+    /// Returns a [`wgpu::TextureDescriptor`] for a wgpu texture that can hold all of this KTX2's images,
+    /// or `None` if its format has no wgpu counterpart (see [`wgpu_texture_format`]).
+    /// This is also `None` for 1D array textures, as wgpu has no 1D arrays.
+    ///
+    /// Array layers and cubemap faces are both mapped to `depth_or_array_layers`. The descriptor has no label,
+    /// and its usage is `TEXTURE_BINDING | COPY_DST`.
+    pub fn wgpu_texture_descriptor(&self) -> Option<wgpu::TextureDescriptor<Option<&'static str>>> {
+        let format = wgpu_texture_format(self.vk_format())?;
+        let texture = &self.texture;
+        let dimensions = texture.dimensions();
+        let (dimension, depth_or_array_layers) = match texture.num_dimensions() {
+            1 if texture.num_layers() > 1 => return None,
+            1 => (wgpu::TextureDimension::D1, 1),
+            3 => (wgpu::TextureDimension::D3, dimensions.depth),
+            _ => (
+                wgpu::TextureDimension::D2,
+                texture.num_layers() * texture.num_faces(),
+            ),
+        };
+        Some(wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width: dimensions.width as u32,
+                height: dimensions.height as u32,
+                depth_or_array_layers: depth_or_array_layers as u32,
+            },
+            mip_level_count: texture.num_levels() as u32,
+            sample_count: 1,
+            dimension,
+            format,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+        })
+    }
+}
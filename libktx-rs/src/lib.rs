@@ -6,15 +6,87 @@
 
 pub use libktx_rs_sys as sys;
 
+/// Logs an error via [`log::error!`] if the `log` feature is enabled; otherwise, does nothing
+/// (but still type-checks the arguments, so that they are not reported as unused).
+macro_rules! log_error {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "log")]
+        log::error!($($arg)*);
+        #[cfg(not(feature = "log"))]
+        let _ = format_args!($($arg)*);
+    }};
+}
+
+/// Returns the version of the underlying libKTX (e.g. `v4.0.0`); see [`sys::LIBKTX_VERSION`].
+pub fn version() -> &'static str {
+    sys::LIBKTX_VERSION
+}
+
+/// Returns `true` if the underlying libKTX is linked statically, `false` if dynamically.
+pub fn is_statically_linked() -> bool {
+    sys::LIBKTX_STATIC
+}
+
 pub mod enums;
 pub use enums::*;
 
+/// The 12-byte identifier that KTX1 files start with.
+pub const KTX1_IDENTIFIER: [u8; 12] = [
+    0xAB, 0x4B, 0x54, 0x58, 0x20, 0x31, 0x31, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A,
+];
+
+/// The 12-byte identifier that KTX2 files start with.
+pub const KTX2_IDENTIFIER: [u8; 12] = [
+    0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A,
+];
+
+/// Detects whether `bytes` are the start of a KTX1 or KTX2 file, by checking their identifier.  
+/// Returns `None` if neither matches (including if `bytes` are shorter than the identifier).
+///
+/// This does not validate the rest of the header; it is meant for routing data before creating a [`Texture`].
+pub fn detect_format(bytes: &[u8]) -> Option<TextureClass> {
+    if bytes.starts_with(&KTX1_IDENTIFIER) {
+        Some(TextureClass::Ktx1)
+    } else if bytes.starts_with(&KTX2_IDENTIFIER) {
+        Some(TextureClass::Ktx2)
+    } else {
+        None
+    }
+}
+
+/// Like [`detect_format`], but reads (only) the identifier from the file at `path`.  
+/// Files shorter than the identifier yield `Ok(None)`.
+pub fn detect_file_format<P: AsRef<std::path::Path>>(
+    path: P,
+) -> std::io::Result<Option<TextureClass>> {
+    use std::io::Read;
+
+    let mut identifier = Vec::with_capacity(KTX1_IDENTIFIER.len());
+    std::fs::File::open(path)?
+        .take(KTX1_IDENTIFIER.len() as u64)
+        .read_to_end(&mut identifier)?;
+    Ok(detect_format(&identifier))
+}
+
+pub mod error;
+pub use error::Error;
+
 pub mod texture;
 pub use texture::{Texture, TextureSource};
 
+pub mod metadata;
+pub use metadata::Metadata;
+
+pub mod dfd;
+pub use dfd::{DfdBuilder, DfdHeader};
+
 pub mod stream;
-pub use stream::{RWSeekable, RustKtxStream};
+pub use stream::{RWSeekable, ReadSeekable, RustKtxStream, StreamContainer};
 
+pub mod integration;
+pub mod prelude;
 #[cfg(feature = "write")]
 pub mod sinks;
 pub mod sources;
+#[cfg(feature = "vulkan")]
+pub mod vulkan;
@@ -0,0 +1,132 @@
+// Copyright (C) 2021 Paolo Jovon <paolo.jovon@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Key/value metadata (KVData) stored in KTX [`crate::Texture`]s.
+
+use crate::sys;
+use std::{ffi::CString, marker::PhantomData};
+
+/// A read-only view on the key/value metadata of a [`crate::Texture`].
+///
+/// This wraps the texture's underlying [`sys::ktxHashList`].
+/// Note that textures created with [`crate::TextureCreateFlags::SKIP_KVDATA`] have no hash list,
+/// hence their metadata will always be empty.
+#[derive(Debug, Clone, Copy)]
+pub struct Metadata<'a> {
+    head: *mut sys::ktxHashList,
+    head_phantom: PhantomData<&'a sys::ktxHashList>,
+}
+
+impl<'a> Metadata<'a> {
+    /// Wraps the given `head` of a hash list.
+    ///
+    /// **SAFETY**: `head` must be a valid pointer to a [`sys::ktxHashList`] that outlives `'a`.
+    pub(crate) unsafe fn from_head(head: *mut sys::ktxHashList) -> Self {
+        Metadata {
+            head,
+            head_phantom: PhantomData,
+        }
+    }
+
+    /// Returns `true` if there is no metadata at all.
+    pub fn is_empty(&self) -> bool {
+        // SAFETY: Safe if `self.head` is sane.
+        unsafe { (*self.head).is_null() }
+    }
+
+    /// Looks up the value associated to `key`, if any.
+    pub fn get(&self, key: &str) -> Option<&'a [u8]> {
+        if self.is_empty() {
+            return None;
+        }
+        // Keys with interior NULs can't be stored in the C hash list anyways
+        let c_key = CString::new(key).ok()?;
+
+        let mut value_len: std::os::raw::c_uint = 0;
+        let mut value_ptr: *mut std::ffi::c_void = std::ptr::null_mut();
+        // SAFETY: Safe if `self.head` is sane.
+        let err = unsafe {
+            sys::ktxHashList_FindValue(self.head, c_key.as_ptr(), &mut value_len, &mut value_ptr)
+        };
+        if err != sys::ktx_error_code_e_KTX_SUCCESS {
+            return None;
+        }
+        // SAFETY: The C library returned a valid pointer + length into the hash list entry.
+        Some(unsafe { value_slice(value_ptr, value_len) })
+    }
+
+    /// Returns an iterator over all `(key, value)` pairs in this metadata.
+    pub fn iter(&self) -> MetadataIter<'a> {
+        MetadataIter {
+            // SAFETY: Safe if `self.head` is sane. The head of the list is its first entry.
+            entry: unsafe { *self.head },
+            entry_phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a> IntoIterator for Metadata<'a> {
+    type Item = (&'a [u8], &'a [u8]);
+    type IntoIter = MetadataIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &Metadata<'a> {
+    type Item = (&'a [u8], &'a [u8]);
+    type IntoIter = MetadataIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the `(key, value)` pairs of a [`Metadata`].
+///
+/// Keys are yielded as raw bytes, without their NUL terminator.
+#[derive(Debug, Clone)]
+pub struct MetadataIter<'a> {
+    entry: *mut sys::ktxHashListEntry,
+    entry_phantom: PhantomData<&'a sys::ktxHashListEntry>,
+}
+
+impl<'a> Iterator for MetadataIter<'a> {
+    type Item = (&'a [u8], &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.entry.is_null() {
+            return None;
+        }
+
+        let mut key_len: std::os::raw::c_uint = 0;
+        let mut key_ptr: *mut std::os::raw::c_char = std::ptr::null_mut();
+        let mut value_len: std::os::raw::c_uint = 0;
+        let mut value_ptr: *mut std::ffi::c_void = std::ptr::null_mut();
+        // SAFETY: Safe if `self.entry` is sane - which it is, as it comes from the C hash list.
+        unsafe {
+            sys::ktxHashListEntry_GetKey(self.entry, &mut key_len, &mut key_ptr);
+            sys::ktxHashListEntry_GetValue(self.entry, &mut value_len, &mut value_ptr);
+            self.entry = sys::ktxHashList_Next(self.entry);
+        }
+
+        // SAFETY: Pointers + lengths come straight from the C hash list entry.
+        let key = unsafe { value_slice(key_ptr as *mut std::ffi::c_void, key_len) };
+        // The key length reported by the C library includes the NUL terminator
+        let key = key.strip_suffix(&[0u8]).unwrap_or(key);
+        let value = unsafe { value_slice(value_ptr, value_len) };
+        Some((key, value))
+    }
+}
+
+/// Builds a slice out of a (possibly null) C pointer + length pair.
+///
+/// **SAFETY**: `ptr` must be either null or point to at least `len` valid bytes that outlive `'a`.
+unsafe fn value_slice<'a>(ptr: *mut std::ffi::c_void, len: std::os::raw::c_uint) -> &'a [u8] {
+    if ptr.is_null() || len == 0 {
+        &[]
+    } else {
+        std::slice::from_raw_parts(ptr as *const u8, len as usize)
+    }
+}
@@ -0,0 +1,23 @@
+// Copyright (C) 2021 Paolo Jovon <paolo.jovon@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Re-exports the types that are needed most often, for glob-importing:
+//! ```rust,ignore
+//! use libktx_rs::prelude::*;
+//! ```
+
+pub use crate::{
+    error::Error,
+    sources::{
+        BufferedSource, FileSource, Ktx1CreateInfo, Ktx2CreateInfo, MemorySource, StreamSource,
+    },
+    stream::RustKtxStream,
+    texture::{Ktx1, Ktx2, Texture, TextureSource},
+    KtxError, TextureCreateFlags, TranscodeFlags, TranscodeFormat,
+};
+#[cfg(feature = "write")]
+pub use crate::{
+    sinks::{FileSink, MemorySink, StreamSink},
+    sources::RawDataSource,
+    texture::TextureSink,
+};
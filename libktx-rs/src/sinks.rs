@@ -5,48 +5,240 @@
 //! [`crate::texture::TextureSink`] implementations for writing [`Texture`]s out to.
 
 use crate::{
-    enums::ktx_result,
-    stream::{RWSeekable, RustKtxStream},
+    enums::{ktx_error, ktx_result},
+    error::{Error, ResultExt},
+    sources::path_to_cstring,
+    stream::{RWSeekable, RustKtxStream, StreamContainer},
+    sys,
     texture::{Texture, TextureSink},
     KtxError,
 };
-use std::sync::{Arc, Mutex};
+use std::{
+    io::{self, Read, Seek, SeekFrom, Write},
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
 
 /// A [`TextureSink`] that writes to a [`RustKtxStream`].
+///
+/// The stream is held in a [`StreamContainer`] `C`; this is a `Arc<Mutex<RustKtxStream>>` by default,
+/// but a plain [`RustKtxStream`] can be used instead when the stream is not shared (e.g. for single-threaded use).
 #[derive(Debug)]
-pub struct StreamSink<'a, T: RWSeekable + ?Sized + 'a> {
-    stream: Arc<Mutex<RustKtxStream<'a, T>>>,
+pub struct StreamSink<
+    'a,
+    T: RWSeekable + ?Sized + 'a,
+    C: StreamContainer<'a, Inner = T> = Arc<Mutex<RustKtxStream<'a, T>>>,
+> {
+    pub(crate) stream: C,
+    stream_phantom: PhantomData<&'a T>,
 }
 
-impl<'a, T: RWSeekable + ?Sized + 'a> StreamSink<'a, T> {
-    /// Creates a new stream sink that will write to the given `inner` stream.
-    pub fn new(inner: Arc<Mutex<RustKtxStream<'a, T>>>) -> Self {
-        StreamSink { stream: inner }
+impl<'a, T: RWSeekable + ?Sized + 'a, C: StreamContainer<'a, Inner = T>> StreamSink<'a, T, C> {
+    /// Creates a new stream sink that will write to the given `inner` stream (container).
+    pub fn new(inner: C) -> Self {
+        StreamSink {
+            stream: inner,
+            stream_phantom: PhantomData,
+        }
     }
 
-    /// Destroys this stream sink, giving back the underlying `inner` stream.
-    pub fn into_inner(self) -> Arc<Mutex<RustKtxStream<'a, T>>> {
+    /// Destroys this stream sink, giving back the underlying `inner` stream (container).
+    pub fn into_inner(self) -> C {
         self.stream
     }
 }
 
-impl<'a, T: RWSeekable + ?Sized + 'a> TextureSink for StreamSink<'a, T> {
-    fn write_texture(&mut self, texture: &Texture) -> Result<(), KtxError> {
+impl<'a, T: RWSeekable + 'a> StreamSink<'a, T> {
+    /// Creates a new stream sink that will write to `inner`.
+    ///
+    /// This wraps `inner` in a [`RustKtxStream`] internally; see [`Self::new`] for sharing the stream.
+    pub fn from_rw_seekable(inner: T) -> Result<Self, KtxError> {
+        let stream = RustKtxStream::from_value(inner).map_err(ktx_error)?;
+        Ok(Self::new(Arc::new(Mutex::new(stream))))
+    }
+
+    /// Attempts to destroy this stream sink, giving back the `T` the underlying [`RustKtxStream`] wraps.
+    ///
+    /// Fails (giving back `self`) if the stream is shared with someone else.
+    pub fn try_unwrap(self) -> Result<T, Self> {
+        match Arc::try_unwrap(self.stream) {
+            Ok(mutex) => Ok(*mutex
+                .into_inner()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .into_inner()),
+            Err(stream) => Err(StreamSink::new(stream)),
+        }
+    }
+}
+
+impl<'a, T: RWSeekable + ?Sized + 'a, C: StreamContainer<'a, Inner = T>> TextureSink
+    for StreamSink<'a, T, C>
+{
+    fn write_texture(&mut self, texture: &Texture) -> Result<(), Error> {
         // SAFETY: Safe if `texture.handle` is sound.
         let vtbl = unsafe { (*texture.handle).vtbl };
         let write_pfn = match unsafe { (*vtbl).WriteToStream } {
             Some(pfn) => pfn,
-            None => return Err(KtxError::InvalidValue),
+            None => {
+                return Err(Error::new(
+                    KtxError::InvalidValue,
+                    "StreamSink::write_texture",
+                ))
+            }
+        };
+        let err = self
+            .stream
+            .with_ktx_stream(|ktx_stream| unsafe { write_pfn(texture.handle, ktx_stream) })
+            .context(|| "StreamSink::write_texture")?;
+        ktx_result(err, ()).context(|| "StreamSink::write_texture")
+    }
+}
+
+extern "C" {
+    // Buffers allocated by the C library must be freed by the C allocator.
+    fn free(ptr: *mut std::ffi::c_void);
+}
+
+/// A [`TextureSink`] that writes to an in-memory buffer.
+///
+/// Each write replaces the contents of the buffer with the newly-serialized texture.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MemorySink {
+    bytes: Vec<u8>,
+}
+
+impl MemorySink {
+    /// Creates a new, empty memory sink.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Returns the bytes that were last written to this sink.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Destroys this memory sink, giving back the bytes that were last written to it.
+    pub fn into_inner(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+impl TextureSink for MemorySink {
+    fn write_texture(&mut self, texture: &Texture) -> Result<(), Error> {
+        // SAFETY: Safe if `texture.handle` is sound.
+        let vtbl = unsafe { (*texture.handle).vtbl };
+        let write_pfn = match unsafe { (*vtbl).WriteToMemory } {
+            Some(pfn) => pfn,
+            None => {
+                return Err(Error::new(
+                    KtxError::InvalidValue,
+                    "MemorySink::write_texture",
+                ))
+            }
+        };
+
+        let mut c_bytes: *mut u8 = std::ptr::null_mut();
+        let mut c_size: sys::ktx_size_t = 0;
+        let err = unsafe { write_pfn(texture.handle, &mut c_bytes, &mut c_size) };
+        ktx_result(err, ()).context(|| "MemorySink::write_texture")?;
+        if c_bytes.is_null() {
+            return Err(Error::new(
+                KtxError::OutOfMemory,
+                "MemorySink::write_texture",
+            ));
+        }
+
+        // SAFETY: The C library allocated `c_size` bytes at `c_bytes`.
+        //         Copy them out to the Rust-managed Vec, then free the C-allocated buffer.
+        unsafe {
+            self.bytes.clear();
+            self.bytes
+                .extend_from_slice(std::slice::from_raw_parts(c_bytes, c_size));
+            free(c_bytes as *mut std::ffi::c_void);
+        }
+        Ok(())
+    }
+}
+
+/// A [`TextureSink`] that writes to a file on disk.
+///
+/// This lets the C library create and write the file by itself (with buffered I/O), without going through a [`RustKtxStream`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileSink {
+    pub path: PathBuf,
+}
+
+impl FileSink {
+    /// Creates a new file sink that will write to the file at `path`.
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        FileSink {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+}
+
+impl TextureSink for FileSink {
+    fn write_texture(&mut self, texture: &Texture) -> Result<(), Error> {
+        let context = || format!("FileSink::write_texture({})", self.path.display());
+        let c_path = path_to_cstring(&self.path).context(context)?;
+
+        // SAFETY: Safe if `texture.handle` is sound.
+        let vtbl = unsafe { (*texture.handle).vtbl };
+        let write_pfn = match unsafe { (*vtbl).WriteToNamedFile } {
+            Some(pfn) => pfn,
+            None => return Err(Error::new(KtxError::InvalidValue, context())),
         };
-        let err = unsafe {
-            write_pfn(
-                texture.handle,
-                self.stream
-                    .lock()
-                    .expect("Poisoned self.stream lock")
-                    .ktx_stream(),
-            )
+        let err = unsafe { write_pfn(texture.handle, c_path.as_ptr()) };
+        match ktx_result(err, ()) {
+            // The C library reports a file that could not be created for writing as a failure to open it
+            Err(KtxError::FileOpenFailed) => Err(KtxError::FileWriteError),
+            other => other,
+        }
+        .context(context)
+    }
+}
+
+/// A write-only, seekable stream that discards what is written to it, only keeping track of its length.  
+/// Used for measuring serialized textures (see [`Texture::serialized_len`]).
+#[derive(Debug, Default)]
+pub(crate) struct LenCounter {
+    position: u64,
+    pub(crate) len: u64,
+}
+
+impl Read for LenCounter {
+    fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "LenCounter is write-only",
+        ))
+    }
+}
+
+impl Write for LenCounter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.position += buf.len() as u64;
+        self.len = self.len.max(self.position);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for LenCounter {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let position = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::Current(offset) => self.position.checked_add_signed(offset),
+            SeekFrom::End(offset) => self.len.checked_add_signed(offset),
         };
-        ktx_result(err, ())
+        self.position = position.ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "seek to a negative position")
+        })?;
+        Ok(self.position)
     }
 }
@@ -4,20 +4,29 @@
 //! [`crate::texture::TextureSource`] implementations for reading (or creating) [`Texture`]s from.
 
 use crate::{
-    enums::{CreateStorage, TextureCreateFlags},
-    stream::{RWSeekable, RustKtxStream},
+    dfd::DfdBuilder,
+    enums::{ktx_error, ktx_result, CreateStorage, GlInternalFormat, TextureCreateFlags, VkFormat},
+    error::{Error, ResultExt},
+    stream::{RWSeekable, ReadSeekable, RustKtxStream, StreamContainer},
     sys::{self},
     texture::{Texture, TextureSource},
     KtxError,
 };
+#[cfg(feature = "write")]
+use std::collections::HashSet;
 use std::{
-    convert::TryInto,
+    cell::Cell,
+    ffi::CString,
+    io::Read,
     marker::PhantomData,
+    path::{Path, PathBuf},
     sync::{Arc, Mutex},
 };
 
 /// [`Texture`] creation info common to KTX1 and KTX2.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct CommonCreateInfo {
     pub create_storage: CreateStorage,
     pub base_width: u32,
@@ -48,9 +57,74 @@ impl Default for CommonCreateInfo {
     }
 }
 
+impl CommonCreateInfo {
+    /// Checks `self` for obviously invalid values, returning [`KtxError::InvalidValue`] if any are found.
+    pub(crate) fn validate(&self) -> Result<(), KtxError> {
+        match self.validation_error() {
+            None => Ok(()),
+            Some(_) => Err(KtxError::InvalidValue),
+        }
+    }
+
+    /// Like [`Self::validate`], but returns an error with a description of what is invalid.  
+    /// `context` is prefixed to the description.
+    pub(crate) fn validate_with_context(&self, context: &str) -> Result<(), Error> {
+        match self.validation_error() {
+            None => Ok(()),
+            Some(problem) => Err(Error::new(
+                KtxError::InvalidValue,
+                format!("{}: {}", context, problem),
+            )),
+        }
+    }
+
+    /// Returns a description of the first invalid value (or combination of values) in `self`, if any.
+    fn validation_error(&self) -> Option<String> {
+        if self.base_width == 0 || self.base_height == 0 || self.base_depth == 0 {
+            return Some(format!(
+                "base dimensions {}x{}x{} must be non-zero",
+                self.base_width, self.base_height, self.base_depth
+            ));
+        }
+        if !(1..=3).contains(&self.num_dimensions) {
+            return Some(format!(
+                "num_dimensions = {} must be 1, 2 or 3",
+                self.num_dimensions
+            ));
+        }
+        if self.num_layers == 0 {
+            return Some("num_layers must be non-zero (even for arrays)".to_owned());
+        }
+        if self.num_faces != 1 && self.num_faces != 6 {
+            return Some(format!("num_faces = {} must be 1 or 6", self.num_faces));
+        }
+        if self.num_faces == 6
+            && (self.num_dimensions != 2
+                || self.base_width != self.base_height
+                || self.base_depth != 1)
+        {
+            return Some(format!(
+                "cubemaps must be 2D and square, not {}D and {}x{}x{}",
+                self.num_dimensions, self.base_width, self.base_height, self.base_depth
+            ));
+        }
+        let max_dimension = self.base_width.max(self.base_height).max(self.base_depth);
+        let max_levels = u32::BITS - max_dimension.leading_zeros();
+        if self.num_levels == 0 || self.num_levels > max_levels {
+            return Some(format!(
+                "num_levels = {} must be between 1 and {} for {}x{}x{}",
+                self.num_levels, max_levels, self.base_width, self.base_height, self.base_depth
+            ));
+        }
+        None
+    }
+}
+
 /// [`Texture`] creation info for KTX1 textures ([`crate::texture::Ktx1`]).  
 /// This is also a [`TextureSource`], which creates a new KTX1 texture according to `self`.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Ktx1CreateInfo {
     pub gl_internal_format: u32,
     pub common: CommonCreateInfo,
@@ -65,25 +139,118 @@ impl Default for Ktx1CreateInfo {
     }
 }
 
+impl Ktx1CreateInfo {
+    /// Returns a new [`Ktx1CreateInfoBuilder`], starting from the default creation info.
+    pub fn builder() -> Ktx1CreateInfoBuilder {
+        Ktx1CreateInfoBuilder {
+            info: Default::default(),
+        }
+    }
+}
+
+/// A builder for [`Ktx1CreateInfo`]s.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ktx1CreateInfoBuilder {
+    info: Ktx1CreateInfo,
+}
+
+impl Ktx1CreateInfoBuilder {
+    /// Sets the OpenGL internal format of the texture's data.
+    pub fn gl_internal_format(mut self, gl_internal_format: GlInternalFormat) -> Self {
+        self.info.gl_internal_format = gl_internal_format.into();
+        self
+    }
+
+    /// Sets the storage to (not) allocate for the texture's images.
+    pub fn storage(mut self, create_storage: CreateStorage) -> Self {
+        self.info.common.create_storage = create_storage;
+        self
+    }
+
+    /// Sets the base width, height and depth of the texture.  
+    /// The number of dimensions is inferred from them (see [`Self::num_dimensions`] to override it).
+    pub fn dimensions(mut self, width: u32, height: u32, depth: u32) -> Self {
+        self.info.common.base_width = width;
+        self.info.common.base_height = height;
+        self.info.common.base_depth = depth;
+        self.info.common.num_dimensions = if depth > 1 {
+            3
+        } else if height > 1 {
+            2
+        } else {
+            1
+        };
+        self
+    }
+
+    /// Sets the number of dimensions of the texture (1, 2 or 3).
+    pub fn num_dimensions(mut self, num_dimensions: u32) -> Self {
+        self.info.common.num_dimensions = num_dimensions;
+        self
+    }
+
+    /// Sets the number of mip levels of the texture.
+    pub fn levels(mut self, num_levels: u32) -> Self {
+        self.info.common.num_levels = num_levels;
+        self
+    }
+
+    /// Sets the number of array layers of the texture.
+    pub fn layers(mut self, num_layers: u32) -> Self {
+        self.info.common.num_layers = num_layers;
+        self
+    }
+
+    /// Sets the number of faces of the texture (1, or 6 for cubemaps).
+    pub fn faces(mut self, num_faces: u32) -> Self {
+        self.info.common.num_faces = num_faces;
+        self
+    }
+
+    /// Sets whether the texture is an array texture.
+    pub fn array(mut self, is_array: bool) -> Self {
+        self.info.common.is_array = is_array;
+        self
+    }
+
+    /// Sets whether mipmaps should be generated for the texture when it is loaded.
+    pub fn generate_mipmaps(mut self, generate_mipmaps: bool) -> Self {
+        self.info.common.generate_mipmaps = generate_mipmaps;
+        self
+    }
+
+    /// Attempts to build the creation info, returning [`KtxError::InvalidValue`] if it is obviously invalid.
+    pub fn build(self) -> Result<Ktx1CreateInfo, KtxError> {
+        self.info.common.validate()?;
+        Ok(self.info)
+    }
+}
+
 fn try_create_texture<'a, S, C>(source: S, create_fn: C) -> Result<Texture<'a>, KtxError>
 where
     S: TextureSource<'a> + 'a,
     C: FnOnce(S) -> (S, sys::ktx_error_code_e, *mut sys::ktxTexture),
 {
     let (source, err, handle) = (create_fn)(source);
-    if err == sys::ktx_error_code_e_KTX_SUCCESS && !handle.is_null() {
+    ktx_result(err, ())?;
+    if !handle.is_null() {
         Ok(Texture {
             source: Box::new(source),
             handle,
             handle_phantom: PhantomData,
+            source_consumed: false,
+            raw_kv_list: Cell::new(std::ptr::null_mut()),
         })
     } else {
-        Err(err.try_into().unwrap_or(KtxError::InvalidOperation))
+        // The C library reported success, but did not create anything
+        Err(KtxError::InvalidOperation)
     }
 }
 
 impl<'a> TextureSource<'a> for Ktx1CreateInfo {
-    fn create_texture(self) -> Result<Texture<'a>, KtxError> {
+    fn create_texture(self) -> Result<Texture<'a>, Error> {
+        self.common
+            .validate_with_context("Ktx1CreateInfo::create_texture")?;
         let mut sys_create_info = sys::ktxTextureCreateInfo {
             glInternalformat: self.gl_internal_format,
             vkFormat: 0,
@@ -112,14 +279,17 @@ impl<'a> TextureSource<'a> for Ktx1CreateInfo {
             };
             (source, err, handle)
         })
+        .context(|| "Ktx1CreateInfo::create_texture")
     }
 }
 
 /// [`Texture`] creation info for KTX2 textures ([`crate::texture::Ktx2`]).  
 /// This is also a [`TextureSource`], which creates a new KTX2 texture according to `self`.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Ktx2CreateInfo {
-    pub vk_format: u32,
+    pub vk_format: VkFormat,
     pub dfd: Option<Vec<u32>>,
     pub common: CommonCreateInfo,
 }
@@ -127,15 +297,118 @@ pub struct Ktx2CreateInfo {
 impl Default for Ktx2CreateInfo {
     fn default() -> Self {
         Ktx2CreateInfo {
-            vk_format: 37, // VK_R8G8B8A8_UNORM
+            vk_format: VkFormat::R8G8B8A8Unorm,
             dfd: None,
             common: Default::default(),
         }
     }
 }
 
+impl Ktx2CreateInfo {
+    /// Returns a new [`Ktx2CreateInfoBuilder`], starting from the default creation info.
+    pub fn builder() -> Ktx2CreateInfoBuilder {
+        Ktx2CreateInfoBuilder {
+            info: Default::default(),
+        }
+    }
+
+    /// Attempts to set [`Self::dfd`] to the one built by `dfd`; see [`DfdBuilder::build`] for possible errors.  
+    /// This also sets [`Self::vk_format`] to [`VkFormat::Undefined`], as the C library ignores the DFD otherwise.
+    pub fn with_dfd(mut self, dfd: &DfdBuilder) -> Result<Self, KtxError> {
+        self.dfd = Some(dfd.build()?);
+        self.vk_format = VkFormat::Undefined;
+        Ok(self)
+    }
+}
+
+/// A builder for [`Ktx2CreateInfo`]s.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ktx2CreateInfoBuilder {
+    info: Ktx2CreateInfo,
+}
+
+impl Ktx2CreateInfoBuilder {
+    /// Sets the Vulkan format of the texture's data.
+    pub fn vk_format(mut self, vk_format: VkFormat) -> Self {
+        self.info.vk_format = vk_format;
+        self
+    }
+
+    /// Sets the Data Format Descriptor of the texture's data.
+    pub fn dfd(mut self, dfd: Vec<u32>) -> Self {
+        self.info.dfd = Some(dfd);
+        self
+    }
+
+    /// Sets the storage to (not) allocate for the texture's images.
+    pub fn storage(mut self, create_storage: CreateStorage) -> Self {
+        self.info.common.create_storage = create_storage;
+        self
+    }
+
+    /// Sets the base width, height and depth of the texture.  
+    /// The number of dimensions is inferred from them (see [`Self::num_dimensions`] to override it).
+    pub fn dimensions(mut self, width: u32, height: u32, depth: u32) -> Self {
+        self.info.common.base_width = width;
+        self.info.common.base_height = height;
+        self.info.common.base_depth = depth;
+        self.info.common.num_dimensions = if depth > 1 {
+            3
+        } else if height > 1 {
+            2
+        } else {
+            1
+        };
+        self
+    }
+
+    /// Sets the number of dimensions of the texture (1, 2 or 3).
+    pub fn num_dimensions(mut self, num_dimensions: u32) -> Self {
+        self.info.common.num_dimensions = num_dimensions;
+        self
+    }
+
+    /// Sets the number of mip levels of the texture.
+    pub fn levels(mut self, num_levels: u32) -> Self {
+        self.info.common.num_levels = num_levels;
+        self
+    }
+
+    /// Sets the number of array layers of the texture.
+    pub fn layers(mut self, num_layers: u32) -> Self {
+        self.info.common.num_layers = num_layers;
+        self
+    }
+
+    /// Sets the number of faces of the texture (1, or 6 for cubemaps).
+    pub fn faces(mut self, num_faces: u32) -> Self {
+        self.info.common.num_faces = num_faces;
+        self
+    }
+
+    /// Sets whether the texture is an array texture.
+    pub fn array(mut self, is_array: bool) -> Self {
+        self.info.common.is_array = is_array;
+        self
+    }
+
+    /// Sets whether mipmaps should be generated for the texture when it is loaded.
+    pub fn generate_mipmaps(mut self, generate_mipmaps: bool) -> Self {
+        self.info.common.generate_mipmaps = generate_mipmaps;
+        self
+    }
+
+    /// Attempts to build the creation info, returning [`KtxError::InvalidValue`] if it is obviously invalid.
+    pub fn build(self) -> Result<Ktx2CreateInfo, KtxError> {
+        self.info.common.validate()?;
+        Ok(self.info)
+    }
+}
+
 impl<'a> TextureSource<'a> for Ktx2CreateInfo {
-    fn create_texture(mut self) -> Result<Texture<'a>, KtxError> {
+    fn create_texture(mut self) -> Result<Texture<'a>, Error> {
+        self.common
+            .validate_with_context("Ktx2CreateInfo::create_texture")?;
         // SAFETY: the contents of the Vec will not change or move around memory
         // - libKTX does not modify the given DFD pointer
         //   (but then, why no `const` in the C API pointer?)
@@ -147,7 +420,7 @@ impl<'a> TextureSource<'a> for Ktx2CreateInfo {
 
         let mut sys_create_info = sys::ktxTextureCreateInfo {
             glInternalformat: 0,
-            vkFormat: self.vk_format,
+            vkFormat: self.vk_format.into(),
             pDfd: dfd_ptr,
             baseWidth: self.common.base_width,
             baseHeight: self.common.base_height,
@@ -173,52 +446,388 @@ impl<'a> TextureSource<'a> for Ktx2CreateInfo {
             };
             (source, err, handle)
         })
+        .context(|| "Ktx2CreateInfo::create_texture")
     }
 }
 
 /// [`TextureSource`] for reading a texture from a [`RustKtxStream`].
+///
+/// The stream is held in a [`StreamContainer`] `C`; this is a `Arc<Mutex<RustKtxStream>>` by default,
+/// but a plain [`RustKtxStream`] can be used instead when the stream is not shared (e.g. for single-threaded use).
 #[derive(Debug)]
-pub struct StreamSource<'a, T: RWSeekable + ?Sized + 'a> {
-    stream: Arc<Mutex<RustKtxStream<'a, T>>>,
+pub struct StreamSource<
+    'a,
+    T: ReadSeekable + ?Sized + 'a,
+    C: StreamContainer<'a, Inner = T> = Arc<Mutex<RustKtxStream<'a, T>>>,
+> {
+    stream: C,
     texture_create_flags: TextureCreateFlags,
+    generate_mipmaps_on_load: bool,
+    stream_phantom: PhantomData<&'a T>,
 }
 
-impl<'a, T: RWSeekable + ?Sized + 'a> StreamSource<'a, T> {
-    /// Creates a new stream texture source from the given [`RustKtxStream`] and texture creation flags.
-    pub fn new(
-        inner: Arc<Mutex<RustKtxStream<'a, T>>>,
-        texture_create_flags: TextureCreateFlags,
-    ) -> Self {
+impl<'a, T: ReadSeekable + ?Sized + 'a, C: StreamContainer<'a, Inner = T>> StreamSource<'a, T, C> {
+    /// Creates a new stream texture source from the given [`RustKtxStream`] (container) and texture creation flags.
+    pub fn new(inner: C, texture_create_flags: TextureCreateFlags) -> Self {
         StreamSource {
             stream: inner,
             texture_create_flags,
+            generate_mipmaps_on_load: false,
+            stream_phantom: PhantomData,
         }
     }
 
-    /// Destroys `self`, giving back the inner [`RustKtxStream`] that was passed on construction.
-    pub fn into_inner(self) -> Arc<Mutex<RustKtxStream<'a, T>>> {
+    /// Sets whether to generate the full mip chain after loading, if the texture only has one level;
+    /// see [`Texture::generate_mipmaps`]. This is disabled by default.
+    pub fn generate_mipmaps_on_load(mut self, generate_mipmaps_on_load: bool) -> Self {
+        self.generate_mipmaps_on_load = generate_mipmaps_on_load;
+        self
+    }
+
+    /// Destroys `self`, giving back the inner [`RustKtxStream`] (container) that was passed on construction.
+    pub fn into_inner(self) -> C {
         self.stream
     }
 }
 
-impl<'a, T: RWSeekable + ?Sized + 'a> TextureSource<'a> for StreamSource<'a, T> {
-    fn create_texture(self) -> Result<Texture<'a>, KtxError> {
+impl<'a, T: ReadSeekable + 'a> StreamSource<'a, T> {
+    /// Creates a new stream texture source that reads from `inner`, with the given texture creation flags.
+    ///
+    /// This wraps `inner` in a [`RustKtxStream`] internally; see [`Self::new`] for sharing the stream.
+    pub fn from_rw_seekable(
+        inner: T,
+        texture_create_flags: TextureCreateFlags,
+    ) -> Result<Self, KtxError>
+    where
+        T: RWSeekable,
+    {
+        let stream = RustKtxStream::from_value(inner).map_err(ktx_error)?;
+        Ok(Self::new(
+            Arc::new(Mutex::new(stream)),
+            texture_create_flags,
+        ))
+    }
+
+    /// Like [`Self::from_rw_seekable`], but for read-only streams.
+    pub fn from_read_seekable(
+        inner: T,
+        texture_create_flags: TextureCreateFlags,
+    ) -> Result<Self, KtxError> {
+        let stream = RustKtxStream::new_read_only(Box::new(inner)).map_err(ktx_error)?;
+        Ok(Self::new(
+            Arc::new(Mutex::new(stream)),
+            texture_create_flags,
+        ))
+    }
+
+    /// Attempts to destroy `self`, giving back the `T` the underlying [`RustKtxStream`] wraps.
+    ///
+    /// Fails (giving back `self`) if the stream is shared with someone else.
+    pub fn try_unwrap(self) -> Result<T, Self> {
+        match Arc::try_unwrap(self.stream) {
+            Ok(mutex) => Ok(*mutex
+                .into_inner()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .into_inner()),
+            Err(stream) => Err(StreamSource::new(stream, self.texture_create_flags)
+                .generate_mipmaps_on_load(self.generate_mipmaps_on_load)),
+        }
+    }
+}
+
+impl<'a, T, C> TextureSource<'a> for StreamSource<'a, T, C>
+where
+    T: ReadSeekable + ?Sized + 'a,
+    C: StreamContainer<'a, Inner = T> + 'a,
+{
+    fn create_texture(self) -> Result<Texture<'a>, Error> {
+        let generate_mipmaps_on_load = self.generate_mipmaps_on_load;
+        try_create_texture(self, |source| {
+            let mut handle: *mut sys::ktxTexture = std::ptr::null_mut();
+            let handle_ptr: *mut *mut sys::ktxTexture = &mut handle;
+
+            let err = source
+                .stream
+                .with_ktx_stream(|ktx_stream| unsafe {
+                    sys::ktxTexture_CreateFromStream(
+                        ktx_stream,
+                        source.texture_create_flags.bits(),
+                        handle_ptr,
+                    )
+                })
+                .unwrap_or_else(u32::from);
+            (source, err, handle)
+        })
+        .and_then(|texture| ensure_mipmaps(texture, generate_mipmaps_on_load))
+        .context(|| "StreamSource::create_texture")
+    }
+}
+
+/// If `enabled` and `texture` only has one mip level, generates the rest of its mip chain.
+fn ensure_mipmaps(mut texture: Texture, enabled: bool) -> Result<Texture, KtxError> {
+    if enabled && texture.num_levels() <= 1 {
+        texture.generate_mipmaps()?;
+    }
+    Ok(texture)
+}
+
+/// Converts `path` to a C string, to be passed to the underlying C library.
+pub(crate) fn path_to_cstring(path: &Path) -> Result<CString, KtxError> {
+    #[cfg(unix)]
+    let bytes = {
+        use std::os::unix::ffi::OsStrExt;
+        path.as_os_str().as_bytes()
+    };
+    #[cfg(not(unix))]
+    let bytes = path.to_str().ok_or(KtxError::InvalidValue)?.as_bytes();
+
+    CString::new(bytes).map_err(|_| KtxError::InvalidValue)
+}
+
+/// [`TextureSource`] for reading a texture from a file on disk.
+///
+/// This lets the C library open and read the file by itself, without going through a [`RustKtxStream`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileSource {
+    pub path: PathBuf,
+    pub create_flags: TextureCreateFlags,
+    /// Whether to generate the full mip chain after loading, if the texture only has one level;
+    /// see [`Texture::generate_mipmaps`].
+    pub generate_mipmaps_on_load: bool,
+}
+
+impl FileSource {
+    /// Creates a new file texture source from the given path and texture creation flags.
+    pub fn new<P: AsRef<Path>>(path: P, create_flags: TextureCreateFlags) -> Self {
+        FileSource {
+            path: path.as_ref().to_path_buf(),
+            create_flags,
+            generate_mipmaps_on_load: false,
+        }
+    }
+
+    /// Sets [`Self::generate_mipmaps_on_load`].
+    pub fn generate_mipmaps_on_load(mut self, generate_mipmaps_on_load: bool) -> Self {
+        self.generate_mipmaps_on_load = generate_mipmaps_on_load;
+        self
+    }
+}
+
+impl<'a> TextureSource<'a> for FileSource {
+    fn create_texture(self) -> Result<Texture<'a>, Error> {
+        let context = format!("FileSource::create_texture({})", self.path.display());
+        let c_path = path_to_cstring(&self.path).context(|| context.clone())?;
+        let generate_mipmaps_on_load = self.generate_mipmaps_on_load;
+
+        try_create_texture(self, |source| {
+            let mut handle: *mut sys::ktxTexture = std::ptr::null_mut();
+            let handle_ptr: *mut *mut sys::ktxTexture = &mut handle;
+
+            // A missing file is reported by the C library as `KTX_FILE_OPEN_FAILED`
+            let err = unsafe {
+                sys::ktxTexture_CreateFromNamedFile(
+                    c_path.as_ptr(),
+                    source.create_flags.bits(),
+                    handle_ptr,
+                )
+            };
+            (source, err, handle)
+        })
+        .and_then(|texture| ensure_mipmaps(texture, generate_mipmaps_on_load))
+        .context(|| context)
+    }
+}
+
+/// [`TextureSource`] for reading a texture from an in-memory buffer.
+///
+/// The C library reads from `bytes` directly, without copying them first.  
+/// Since the texture may keep reading from the buffer after creation (i.e. when it is created without
+/// [`TextureCreateFlags::LOAD_IMAGE_DATA`]), **`bytes` must outlive the returned [`Texture`]**;
+/// this is enforced by the `'a` lifetime.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemorySource<'a> {
+    pub bytes: &'a [u8],
+    pub create_flags: TextureCreateFlags,
+}
+
+impl<'a> MemorySource<'a> {
+    /// Creates a new memory texture source from the given bytes and texture creation flags.
+    pub fn new(bytes: &'a [u8], create_flags: TextureCreateFlags) -> Self {
+        MemorySource {
+            bytes,
+            create_flags,
+        }
+    }
+}
+
+impl<'a> TextureSource<'a> for MemorySource<'a> {
+    fn create_texture(self) -> Result<Texture<'a>, Error> {
+        try_create_texture(self, |source| {
+            let mut handle: *mut sys::ktxTexture = std::ptr::null_mut();
+            let handle_ptr: *mut *mut sys::ktxTexture = &mut handle;
+
+            let err = unsafe {
+                sys::ktxTexture_CreateFromMemory(
+                    source.bytes.as_ptr(),
+                    source.bytes.len() as sys::ktx_size_t,
+                    source.create_flags.bits(),
+                    handle_ptr,
+                )
+            };
+            (source, err, handle)
+        })
+        .context(|| "MemorySource::create_texture")
+    }
+}
+
+/// [`TextureSource`] for reading a texture from a non-seekable reader, such as a pipe, socket or stdin.
+///
+/// [`RustKtxStream`]s need to seek, so they fail with [`KtxError::FileIsPipe`] on these readers;
+/// this source reads all of `reader` into memory first instead, then creates the texture from there.
+#[derive(Debug)]
+pub struct BufferedSource<R: Read> {
+    pub reader: R,
+    pub create_flags: TextureCreateFlags,
+}
+
+impl<R: Read> BufferedSource<R> {
+    /// Creates a new buffered texture source from the given reader and texture creation flags.
+    pub fn new(reader: R, create_flags: TextureCreateFlags) -> Self {
+        BufferedSource {
+            reader,
+            create_flags,
+        }
+    }
+}
+
+impl<'a, R: Read> TextureSource<'a> for BufferedSource<R> {
+    fn create_texture(mut self) -> Result<Texture<'a>, Error> {
+        let mut bytes = Vec::new();
+        if let Err(err) = self.reader.read_to_end(&mut bytes) {
+            log_error!("BufferedSource::create_texture: {}", err);
+            return Err(Error::new(
+                KtxError::FileReadError,
+                format!("BufferedSource::create_texture: {}", err),
+            ));
+        }
+        OwnedMemorySource {
+            bytes,
+            create_flags: self.create_flags,
+        }
+        .create_texture()
+        .map_err(|err| {
+            let context = match err.context() {
+                Some(inner) => format!("BufferedSource::create_texture: {}", inner),
+                None => "BufferedSource::create_texture".to_string(),
+            };
+            Error::new(err.code(), context)
+        })
+    }
+}
+
+/// [`TextureSource`] for building a KTX2 texture directly from raw image data, without serializing it to KTX first.
+///
+/// The texture is created from `create_info` (always with [`CreateStorage::AllocStorage`]), then each of `images`
+/// is copied into it with [`Texture::set_image`]. Each image is a `(level, layer, slice, bytes)` tuple,
+/// where `slice` is either a cubemap's face or a 3D texture's depth slice.  
+/// Creation fails with [`KtxError::InvalidValue`] unless `images` cover every level, layer and slice declared
+/// by `create_info` exactly once, each with exactly as many bytes as an image at its level (see [`Texture::get_image_size`]).
+#[cfg(feature = "write")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawDataSource {
+    pub create_info: Ktx2CreateInfo,
+    pub images: Vec<(u32, u32, u32, Vec<u8>)>,
+}
+
+#[cfg(feature = "write")]
+impl RawDataSource {
+    /// Creates a new raw data texture source from the given creation info and images.
+    pub fn new(create_info: Ktx2CreateInfo, images: Vec<(u32, u32, u32, Vec<u8>)>) -> Self {
+        RawDataSource {
+            create_info,
+            images,
+        }
+    }
+}
+
+#[cfg(feature = "write")]
+impl<'a> TextureSource<'a> for RawDataSource {
+    fn create_texture(self) -> Result<Texture<'a>, Error> {
+        let context = || "RawDataSource::create_texture";
+        let mut create_info = self.create_info;
+        create_info.common.create_storage = CreateStorage::AllocStorage;
+        let mut texture = create_info.create_texture()?;
+
+        let expected_images: usize = (0..texture.num_levels() as u32)
+            .map(|level| {
+                let slices = if texture.is_cubemap() {
+                    texture.num_faces()
+                } else {
+                    texture.extent_at_level(level).depth
+                };
+                texture.num_layers() * slices
+            })
+            .sum();
+        let mut seen = HashSet::with_capacity(self.images.len());
+        for (level, layer, slice, _) in self.images.iter() {
+            if !seen.insert((*level, *layer, *slice)) {
+                return Err(KtxError::InvalidValue).context(context);
+            }
+        }
+        if seen.len() != expected_images {
+            return Err(KtxError::InvalidValue).context(context);
+        }
+
+        for (level, layer, slice, bytes) in self.images.iter() {
+            // Out-of-range images (and wrongly-sized ones) are rejected here
+            texture
+                .image_data_range(*level, *layer, *slice)
+                .and_then(|_| texture.set_image(*level, *layer, *slice, bytes))
+                .context(context)?;
+        }
+        Ok(texture)
+    }
+}
+
+/// [`TextureSource`] for reading a texture from an owned in-memory buffer.  
+/// Used for detaching textures from their original source (see [`Texture::into_owned`]).
+#[derive(Debug)]
+pub(crate) struct OwnedMemorySource {
+    pub(crate) bytes: Vec<u8>,
+    pub(crate) create_flags: TextureCreateFlags,
+}
+
+impl<'a> TextureSource<'a> for OwnedMemorySource {
+    fn create_texture(self) -> Result<Texture<'a>, Error> {
+        // The heap buffer of `source.bytes` does not move when `source` is moved into the texture
         try_create_texture(self, |source| {
             let mut handle: *mut sys::ktxTexture = std::ptr::null_mut();
             let handle_ptr: *mut *mut sys::ktxTexture = &mut handle;
 
             let err = unsafe {
-                sys::ktxTexture_CreateFromStream(
-                    source
-                        .stream
-                        .lock()
-                        .expect("Inner stream is poisoned")
-                        .ktx_stream(),
-                    source.texture_create_flags.bits(),
+                sys::ktxTexture_CreateFromMemory(
+                    source.bytes.as_ptr(),
+                    source.bytes.len() as sys::ktx_size_t,
+                    source.create_flags.bits(),
                     handle_ptr,
                 )
             };
             (source, err, handle)
         })
+        .context(|| "OwnedMemorySource::create_texture")
+    }
+}
+
+/// Placeholder [`TextureSource`] for textures adopted from a raw handle (see [`Texture::from_raw`]),
+/// which were created elsewhere.
+#[derive(Debug)]
+pub(crate) struct ForeignSource;
+
+impl<'a> TextureSource<'a> for ForeignSource {
+    fn create_texture(self) -> Result<Texture<'a>, Error> {
+        Err(Error::new(
+            KtxError::InvalidOperation,
+            "ForeignSource::create_texture",
+        ))
     }
 }
@@ -3,17 +3,40 @@
 
 //! A Rust-based KTX-Software I/O stream.
 
-use crate::sys::*;
-use log;
+use crate::{sys::*, KtxError};
 use std::{
+    cell::RefCell,
     ffi::c_void,
     fmt::Debug,
-    io::{Read, Seek, SeekFrom, Write},
+    io::{ErrorKind, Read, Seek, SeekFrom, Write},
     marker::PhantomData,
+    rc::Rc,
+    sync::{Arc, Mutex},
 };
 
+/// Represents a read-only Rust byte stream, i.e. something [`Read`] and [`Seek`].
+pub trait ReadSeekable: Read + Seek {
+    /// Upcasts self to a `ReadSeekable` reference.
+    ///
+    /// This is required for getting a fat pointer to `self` to be stored in the
+    /// C-managed [`ktxStream`].
+    fn as_mut_dyn_read(&mut self) -> &mut dyn ReadSeekable;
+}
+
+impl<T: Read + Seek> ReadSeekable for T {
+    fn as_mut_dyn_read(&mut self) -> &mut dyn ReadSeekable {
+        self
+    }
+}
+
+impl<'a> Debug for dyn ReadSeekable + 'a {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ReadSeekable({:p})", self)
+    }
+}
+
 /// Represents a Rust byte stream, i.e. something [`Read`], [`Write`] and [`Seek`].
-pub trait RWSeekable: Read + Write + Seek {
+pub trait RWSeekable: ReadSeekable + Write {
     /// Upcasts self to a `RWSeekable` reference.
     ///
     /// This is required for getting a fat pointer to `self` to be stored in the
@@ -33,24 +56,74 @@ impl<'a> Debug for dyn RWSeekable + 'a {
     }
 }
 
-/// A Rust-based `ktxStream`, for reading from / writing to [`RWSeekable`]s.
+/// Marks a [`RustKtxStream`] whose `custom_ptr` points to a [`RWSeekable`].
+const STREAM_READ_WRITE: ktx_size_t = 1;
+/// Marks a [`RustKtxStream`] whose `custom_ptr` points to a (read-only) [`ReadSeekable`].
+const STREAM_READ_ONLY: ktx_size_t = 0;
+
+/// State shared between a [`RustKtxStream`] and its `ktxRustStream_*` callbacks.  
+/// `ktxStream.data.custom_ptr.size` points to this.
+struct StreamState {
+    /// Either [`STREAM_READ_WRITE`] or [`STREAM_READ_ONLY`].
+    mode: ktx_size_t,
+    /// The last I/O error that a callback ran into, if any.
+    last_io_error: Option<std::io::Error>,
+}
+
+/// A Rust-based `ktxStream`, for reading from [`ReadSeekable`]s / writing to [`RWSeekable`]s.
 #[allow(unused)]
-pub struct RustKtxStream<'a, T: RWSeekable + ?Sized + 'a> {
+pub struct RustKtxStream<'a, T: ReadSeekable + ?Sized + 'a> {
     inner_ptr: Option<*mut T>,
+    state_ptr: Option<*mut StreamState>,
     ktx_stream: Option<Box<ktxStream>>,
     ktx_phantom: PhantomData<&'a ktxStream>,
 }
 
-impl<'a, T: RWSeekable + ?Sized + 'a> RustKtxStream<'a, T> {
+impl<'a, T: ReadSeekable + ?Sized + 'a> RustKtxStream<'a, T> {
+    /// Attempts to create a new Rust-based `ktxStream`, wrapping the given `inner` [`RWSeekable`] value.  
+    /// This is a shorthand for [`Self::new`] for sized `T`s, which boxes `inner` internally.
+    pub fn from_value(inner: T) -> Result<Self, ktx_error_code_e>
+    where
+        T: RWSeekable + Sized,
+    {
+        Self::new(Box::new(inner))
+    }
+
     /// Attempts to create a new Rust-based `ktxStream`, wrapping the given `inner` [`RWSeekable`].
-    pub fn new(inner: Box<T>) -> Result<Self, ktx_error_code_e> {
+    pub fn new(inner: Box<T>) -> Result<Self, ktx_error_code_e>
+    where
+        T: RWSeekable,
+    {
         let inner_ptr = Box::into_raw(inner);
         // SAFETY: Safe, we just destructed a Box
         let inner_rwseekable_ptr = unsafe { (*inner_ptr).as_mut_dyn() } as *mut dyn RWSeekable;
         // SAFETY: Here be (rustc-version-dependent) dragons
-        let (t_addr, vtable_addr): (*mut c_void, *mut c_void) =
+        let fat_ptr: (*mut c_void, *mut c_void) =
             unsafe { std::mem::transmute(inner_rwseekable_ptr) };
+        Self::from_raw_parts(inner_ptr, fat_ptr, STREAM_READ_WRITE)
+    }
+
+    /// Attempts to create a new read-only Rust-based `ktxStream`, wrapping the given `inner` [`ReadSeekable`].
+    ///
+    /// Any attempt to write to the stream will fail with [`ktx_error_code_e_KTX_FILE_WRITE_ERROR`].
+    pub fn new_read_only(inner: Box<T>) -> Result<Self, ktx_error_code_e> {
+        let inner_ptr = Box::into_raw(inner);
+        // SAFETY: Safe, we just destructed a Box
+        let inner_readseekable_ptr =
+            unsafe { (*inner_ptr).as_mut_dyn_read() } as *mut dyn ReadSeekable;
+        // SAFETY: Here be (rustc-version-dependent) dragons
+        let fat_ptr: (*mut c_void, *mut c_void) =
+            unsafe { std::mem::transmute(inner_readseekable_ptr) };
+        Self::from_raw_parts(inner_ptr, fat_ptr, STREAM_READ_ONLY)
+    }
 
+    /// Builds the `ktxStream` for `inner_ptr`, whose fat `dyn` pointer is `fat_ptr`.
+    /// `mode` is either [`STREAM_READ_WRITE`] or [`STREAM_READ_ONLY`], depending on the `dyn` type.
+    fn from_raw_parts(
+        inner_ptr: *mut T,
+        (t_addr, vtable_addr): (*mut c_void, *mut c_void),
+        mode: ktx_size_t,
+    ) -> Result<Self, ktx_error_code_e> {
         let mut ktx_stream = Box::new(ktxStream {
             read: Some(ktxRustStream_read),
             skip: Some(ktxRustStream_skip),
@@ -66,13 +139,18 @@ impl<'a, T: RWSeekable + ?Sized + 'a> RustKtxStream<'a, T> {
             data: unsafe { std::mem::zeroed() },
             readpos: 0,
         });
+        let state_ptr = Box::into_raw(Box::new(StreamState {
+            mode,
+            last_io_error: None,
+        }));
         let custom_ptr = unsafe { ktx_stream.data.custom_ptr.as_mut() };
         custom_ptr.address = t_addr;
         custom_ptr.allocatorAddress = vtable_addr;
-        custom_ptr.size = 0;
+        custom_ptr.size = state_ptr as ktx_size_t;
 
         Ok(Self {
             inner_ptr: Some(inner_ptr),
+            state_ptr: Some(state_ptr),
             ktx_stream: Some(ktx_stream),
             ktx_phantom: PhantomData,
         })
@@ -92,41 +170,84 @@ impl<'a, T: RWSeekable + ?Sized + 'a> RustKtxStream<'a, T> {
         }
     }
 
-    /// Returns a reference to the inner [`RWSeekable`].
+    /// Returns a reference to the inner [`ReadSeekable`].
     pub fn inner(&self) -> &T {
         // SAFETY: Safe if self has not been dropped
         unsafe { &*self.inner_ptr.expect("Self was destroyed") as &T }
     }
 
-    /// Returns a mutable reference to the inner [`RWSeekable`].
+    /// Returns a mutable reference to the inner [`ReadSeekable`].
     pub fn inner_mut(&mut self) -> &mut T {
         // SAFETY: Safe if self has not been dropped
         unsafe { &mut *self.inner_ptr.expect("Self was destroyed") as &mut T }
     }
 
-    /// Zero out [`self.inner_ptr`], and re-box it to where it was before `new()`.
+    /// Returns the current position of the inner stream, from its start.
+    pub fn position(&mut self) -> std::io::Result<u64> {
+        self.inner_mut().stream_position()
+    }
+
+    /// Returns the total length of the inner stream, in bytes.  
+    /// This seeks to the end of the stream internally, but restores the current position afterwards.
+    pub fn stream_len(&mut self) -> std::io::Result<u64> {
+        stream_len(self.inner_mut().as_mut_dyn_read())
+    }
+
+    /// Seeks the inner stream back to its start; e.g. to read back what was just written to it.
+    pub fn rewind(&mut self) -> std::io::Result<()> {
+        self.inner_mut().seek(SeekFrom::Start(0)).map(|_| ())
+    }
+
+    /// Returns the last I/O error that the inner stream returned while libKTX was using it, if any.
+    ///
+    /// Errors cross the C boundary as a [`KtxError`] (e.g. [`KtxError::FileReadError`]);
+    /// after an operation fails, this is the original [`std::io::Error`] behind it (e.g. [`ErrorKind::PermissionDenied`]).  
+    /// Each failure overwrites the previous error; see [`Self::take_last_io_error`] to clear it.
+    pub fn last_io_error(&self) -> Option<&std::io::Error> {
+        // SAFETY: Safe if self has not been dropped
+        unsafe {
+            (*self.state_ptr.expect("Self was destroyed"))
+                .last_io_error
+                .as_ref()
+        }
+    }
+
+    /// Takes the last I/O error that the inner stream returned while libKTX was using it, if any,
+    /// leaving `None` in its place. Also see [`Self::last_io_error`].
+    pub fn take_last_io_error(&mut self) -> Option<std::io::Error> {
+        // SAFETY: Safe if self has not been dropped
+        unsafe {
+            (*self.state_ptr.expect("Self was destroyed"))
+                .last_io_error
+                .take()
+        }
+    }
+
+    /// Take [`self.inner_ptr`] (leaving `None` behind), and re-box it to where it was before `new()`.
+    ///
+    /// Since `inner_ptr` is `None` afterwards, `drop()` will not free the inner stream a second time.
     fn rebox_inner_ptr(&mut self) -> Box<T> {
-        // SAFETY: Safe-ish - a zeroed-out pointer is a null pointer in all supported platforms
-        let moved_t = std::mem::replace(&mut self.inner_ptr, unsafe { std::mem::zeroed() });
+        let moved_t = self.inner_ptr.take();
         unsafe {
             // SAFETY: Safe - we're just reconstructing the box that was destructed in Self::new()
             Box::from_raw(moved_t.expect("Self was already destroyed"))
         }
     }
 
-    /// Destroys self, giving back the boxed [`RWSeekable`] that was passed to [`Self::new`].
+    /// Destroys self, giving back the boxed stream that was passed to [`Self::new`] or [`Self::new_read_only`].
     pub fn into_inner(mut self) -> Box<T> {
         self.rebox_inner_ptr()
     }
 }
 
-impl<'a, T: RWSeekable + ?Sized + 'a> Drop for RustKtxStream<'a, T> {
+impl<'a, T: ReadSeekable + ?Sized + 'a> Drop for RustKtxStream<'a, T> {
     fn drop(&mut self) {
         // Firstly, this swaps self with a dummy
         let mut moved_self = std::mem::replace(
             self,
             RustKtxStream {
                 inner_ptr: None,
+                state_ptr: None,
                 ktx_stream: None,
                 ktx_phantom: PhantomData,
             },
@@ -146,6 +267,10 @@ impl<'a, T: RWSeekable + ?Sized + 'a> Drop for RustKtxStream<'a, T> {
         if let Some(_) = moved_self.inner_ptr {
             std::mem::drop(moved_self.rebox_inner_ptr())
         }
+        if let Some(state_ptr) = moved_self.state_ptr.take() {
+            // SAFETY: Safe - we're just reconstructing the box that was destructed in Self::from_raw_parts()
+            std::mem::drop(unsafe { Box::from_raw(state_ptr) })
+        }
 
         // Finally, this prevents a drop cycle - IMPORTANT!
         // Note that we manually destroyed all fields above
@@ -153,6 +278,57 @@ impl<'a, T: RWSeekable + ?Sized + 'a> Drop for RustKtxStream<'a, T> {
     }
 }
 
+/// Something that holds a [`RustKtxStream`], possibly sharing it with others.
+///
+/// Stream-based sources and sinks (e.g. [`crate::sources::StreamSource`]) are generic over this; it is implemented for:
+/// - [`RustKtxStream`] itself, for exclusive, single-threaded use (no synchronization overhead);
+/// - `Rc<RefCell<RustKtxStream>>`, for shared, single-threaded use;
+/// - `Arc<Mutex<RustKtxStream>>`, for shared, multi-threaded use.
+pub trait StreamContainer<'a> {
+    /// The Rust stream wrapped by the contained [`RustKtxStream`].
+    type Inner: ReadSeekable + ?Sized + 'a;
+
+    /// Invokes `f` with the [`ktxStream`] handle of the contained [`RustKtxStream`],
+    /// keeping it exclusively borrowed (or locked) for the duration of the call.  
+    /// Fails with [`KtxError::InvalidOperation`] (without invoking `f`) if the stream can't be borrowed or locked.
+    fn with_ktx_stream<R>(&self, f: impl FnOnce(*mut ktxStream) -> R) -> Result<R, KtxError>;
+}
+
+impl<'a, T: ReadSeekable + ?Sized + 'a> StreamContainer<'a> for RustKtxStream<'a, T> {
+    type Inner = T;
+
+    fn with_ktx_stream<R>(&self, f: impl FnOnce(*mut ktxStream) -> R) -> Result<R, KtxError> {
+        Ok(f(self.ktx_stream()))
+    }
+}
+
+impl<'a, T: ReadSeekable + ?Sized + 'a> StreamContainer<'a> for Rc<RefCell<RustKtxStream<'a, T>>> {
+    type Inner = T;
+
+    /// Fails with [`KtxError::InvalidOperation`] if the stream is already borrowed elsewhere.
+    fn with_ktx_stream<R>(&self, f: impl FnOnce(*mut ktxStream) -> R) -> Result<R, KtxError> {
+        let stream = self.try_borrow_mut().map_err(|err| {
+            log_error!("RustKtxStream is already borrowed: {}", err);
+            KtxError::InvalidOperation
+        })?;
+        Ok(f(stream.ktx_stream()))
+    }
+}
+
+impl<'a, T: ReadSeekable + ?Sized + 'a> StreamContainer<'a> for Arc<Mutex<RustKtxStream<'a, T>>> {
+    type Inner = T;
+
+    /// Fails with [`KtxError::InvalidOperation`] if the stream's lock is poisoned
+    /// (i.e. another thread panicked while holding it, possibly leaving the stream in an inconsistent state).
+    fn with_ktx_stream<R>(&self, f: impl FnOnce(*mut ktxStream) -> R) -> Result<R, KtxError> {
+        let stream = self.lock().map_err(|err| {
+            log_error!("Poisoned RustKtxStream lock: {}", err);
+            KtxError::InvalidOperation
+        })?;
+        Ok(f(stream.ktx_stream()))
+    }
+}
+
 fn format_option_ptr<T>(f: &mut std::fmt::Formatter<'_>, option: &Option<T>) -> std::fmt::Result {
     match option {
         Some(t) => write!(f, "{:p}", t),
@@ -160,7 +336,7 @@ fn format_option_ptr<T>(f: &mut std::fmt::Formatter<'_>, option: &Option<T>) ->
     }
 }
 
-impl<'a, T: RWSeekable + ?Sized + 'a> Debug for RustKtxStream<'a, T> {
+impl<'a, T: ReadSeekable + ?Sized + 'a> Debug for RustKtxStream<'a, T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "RustKtxStream(inner=")?;
         format_option_ptr(f, &self.inner_ptr)?;
@@ -170,19 +346,78 @@ impl<'a, T: RWSeekable + ?Sized + 'a> Debug for RustKtxStream<'a, T> {
     }
 }
 
+/// Get back a reference to the [`StreamState`] we put in `ktxStream.data.custom_ptr` on RustKtxStream construction.
+/// SAFETY: UB if `str` is not actually a pointer to a [`RustKtxStream`].
+unsafe fn stream_state<'a>(str: *mut ktxStream) -> &'a mut StreamState {
+    let custom_ptr = (*str).data.custom_ptr.as_ref();
+    &mut *(custom_ptr.size as *mut StreamState)
+}
+
+/// Logs a failed I/O operation on a stream, and stores it as its [`RustKtxStream::last_io_error`].
+/// SAFETY: UB if `str` is not actually a pointer to a [`RustKtxStream`].
+unsafe fn record_io_error(str: *mut ktxStream, op: &str, err: std::io::Error) {
+    log_error!("{}: {}", op, err);
+    stream_state(str).last_io_error = Some(err);
+}
+
 /// Get back a reference to the [`RWSeekable`] we put in `ktxStream.data.custom_ptr`. on RustKtxStream construction.
+/// Returns `None` if the stream was created via [`RustKtxStream::new_read_only`].
 /// SAFETY: UB if `str` is not actually a pointer to a [`RustKtxStream`].
-unsafe fn inner_rwseekable<'a>(str: *mut ktxStream) -> &'a mut dyn RWSeekable {
+unsafe fn inner_rwseekable<'a>(str: *mut ktxStream) -> Option<&'a mut dyn RWSeekable> {
+    if stream_state(str).mode != STREAM_READ_WRITE {
+        return None;
+    }
+    let custom_ptr = (*str).data.custom_ptr.as_ref();
+    let fat_t_ptr = (custom_ptr.address, custom_ptr.allocatorAddress);
+    let inner_ref: *mut dyn RWSeekable = std::mem::transmute(fat_t_ptr);
+    Some(&mut *inner_ref)
+}
+
+/// Get back a reference to the [`ReadSeekable`] we put in `ktxStream.data.custom_ptr`. on RustKtxStream construction.
+/// SAFETY: UB if `str` is not actually a pointer to a [`RustKtxStream`].
+unsafe fn inner_readseekable<'a>(str: *mut ktxStream) -> &'a mut dyn ReadSeekable {
+    if let Some(inner) = inner_rwseekable(str) {
+        return inner.as_mut_dyn_read();
+    }
     let fat_t_ptr = {
         let custom_ptr = (*str).data.custom_ptr.as_ref();
         (custom_ptr.address, custom_ptr.allocatorAddress)
     };
-    let inner_ref: *mut dyn RWSeekable = std::mem::transmute(fat_t_ptr);
+    let inner_ref: *mut dyn ReadSeekable = std::mem::transmute(fat_t_ptr);
     &mut *inner_ref
 }
 
+/// Maps a failed seek on a stream to a C error code, logging and recording it (see [`record_io_error`]).  
+/// Errors caused by the stream not being seekable at all (i.e. of kind [`ErrorKind::Unsupported`],
+/// or `ESPIPE` from the OS for pipes, FIFOs and the like) map to `KTX_FILE_ISPIPE`.
+/// SAFETY: UB if `str` is not actually a pointer to a [`RustKtxStream`].
+unsafe fn seek_error(str: *mut ktxStream, op: &str, err: std::io::Error) -> ktx_error_code_e {
+    if err.kind() == ErrorKind::Unsupported || is_espipe(&err) {
+        log_error!(
+            "{}: stream is not seekable ({}); consider reading it via a `BufferedSource`",
+            op,
+            err
+        );
+        stream_state(str).last_io_error = Some(err);
+        ktx_error_code_e_KTX_FILE_ISPIPE
+    } else {
+        record_io_error(str, op, err);
+        ktx_error_code_e_KTX_FILE_SEEK_ERROR
+    }
+}
+
+#[cfg(unix)]
+fn is_espipe(err: &std::io::Error) -> bool {
+    err.raw_os_error() == Some(libc::ESPIPE)
+}
+
+#[cfg(not(unix))]
+fn is_espipe(_err: &std::io::Error) -> bool {
+    false
+}
+
 // Since `#[feature(seek_stream_len)]` is unstable...
-fn stream_len(seek: &mut dyn RWSeekable) -> std::io::Result<u64> {
+fn stream_len(seek: &mut dyn ReadSeekable) -> std::io::Result<u64> {
     let old_pos = seek.stream_position()?;
     let size = seek.seek(SeekFrom::End(0))?;
     seek.seek(SeekFrom::Start(old_pos))?;
@@ -195,12 +430,12 @@ unsafe extern "C" fn ktxRustStream_read(
     dst: *mut c_void,
     count: ktx_size_t,
 ) -> ktx_error_code_e {
-    let inner = inner_rwseekable(str);
+    let inner = inner_readseekable(str);
     let buf = std::slice::from_raw_parts_mut(dst as *mut u8, count as usize);
     match inner.read_exact(buf) {
         Ok(_) => ktx_error_code_e_KTX_SUCCESS,
         Err(err) => {
-            log::error!("ktxRustStream_read: {}", err);
+            record_io_error(str, "ktxRustStream_read", err);
             ktx_error_code_e_KTX_FILE_READ_ERROR
         }
     }
@@ -211,13 +446,10 @@ unsafe extern "C" fn ktxRustStream_skip(
     str: *mut ktxStream,
     count: ktx_size_t,
 ) -> ktx_error_code_e {
-    let inner = inner_rwseekable(str);
+    let inner = inner_readseekable(str);
     match inner.seek(SeekFrom::Current(count as i64)) {
         Ok(_) => ktx_error_code_e_KTX_SUCCESS,
-        Err(err) => {
-            log::error!("ktxRustStream_skip: {}", err);
-            ktx_error_code_e_KTX_FILE_SEEK_ERROR
-        }
+        Err(err) => seek_error(str, "ktxRustStream_skip", err),
     }
 }
 
@@ -228,13 +460,19 @@ unsafe extern "C" fn ktxRustStream_write(
     size: ktx_size_t,
     count: ktx_size_t,
 ) -> ktx_error_code_e {
-    let inner = inner_rwseekable(str);
+    let inner = match inner_rwseekable(str) {
+        Some(inner) => inner,
+        None => {
+            log_error!("ktxRustStream_write: stream is read-only");
+            return ktx_error_code_e_KTX_FILE_WRITE_ERROR;
+        }
+    };
     let len = (size * count) as usize;
     let buf = std::slice::from_raw_parts(src as *const u8, len);
     match inner.write_all(buf) {
         Ok(_) => ktx_error_code_e_KTX_SUCCESS,
         Err(err) => {
-            log::error!("ktxRustStream_write: {}", err);
+            record_io_error(str, "ktxRustStream_write", err);
             ktx_error_code_e_KTX_FILE_WRITE_ERROR
         }
     }
@@ -245,28 +483,22 @@ unsafe extern "C" fn ktxRustStream_getpos(
     str: *mut ktxStream,
     pos: *mut ktx_off_t,
 ) -> ktx_error_code_e {
-    let inner = inner_rwseekable(str);
+    let inner = inner_readseekable(str);
     match inner.stream_position() {
         Ok(cur) => {
             *pos = cur as ktx_off_t;
             ktx_error_code_e_KTX_SUCCESS
         }
-        Err(err) => {
-            log::error!("ktxRustStream_getpos: {}", err);
-            ktx_error_code_e_KTX_FILE_SEEK_ERROR
-        }
+        Err(err) => seek_error(str, "ktxRustStream_getpos", err),
     }
 }
 
 #[no_mangle]
 unsafe extern "C" fn ktxRustStream_setpos(str: *mut ktxStream, off: ktx_off_t) -> ktx_error_code_e {
-    let inner = inner_rwseekable(str);
+    let inner = inner_readseekable(str);
     match inner.seek(SeekFrom::Start(off as u64)) {
         Ok(_) => ktx_error_code_e_KTX_SUCCESS,
-        Err(err) => {
-            log::error!("ktxRustStream_setpos: {}", err);
-            ktx_error_code_e_KTX_FILE_SEEK_ERROR
-        }
+        Err(err) => seek_error(str, "ktxRustStream_setpos", err),
     }
 }
 
@@ -275,16 +507,13 @@ unsafe extern "C" fn ktxRustStream_getsize(
     str: *mut ktxStream,
     size: *mut ktx_size_t,
 ) -> ktx_error_code_e {
-    let inner = inner_rwseekable(str);
+    let inner = inner_readseekable(str);
     match stream_len(inner) {
         Ok(len) => {
             *size = len as ktx_size_t;
             ktx_error_code_e_KTX_SUCCESS
         }
-        Err(err) => {
-            log::error!("ktxRustStream_getsize: {}", err);
-            ktx_error_code_e_KTX_FILE_SEEK_ERROR
-        }
+        Err(err) => seek_error(str, "ktxRustStream_getsize", err),
     }
 }
 
@@ -4,34 +4,62 @@
 //! Core types involving KTX [`Texture`]s.
 
 use crate::{
+    dfd::{self, DfdHeader},
     enums::{
-        ktx_result, Orientations, PackAstcBlockDimension, PackAstcEncoderFunction,
-        PackAstcEncoderMode, PackAstcQualityLevel, SuperCompressionScheme, TranscodeFlags,
-        TranscodeFormat,
+        ktx_result, BasisEncoding, Channel, ColorModel, ColorPrimaries, CreateStorage, GlFormat,
+        GlInternalFormat, GlType, GpuFeatures, OrientationY, Orientations, PackAstcBlockDimension,
+        PackAstcEncoderFunction, PackAstcEncoderMode, PackAstcQualityLevel, PackUastcFlags,
+        SuperCompressionScheme, TextureClass, TranscodeFlags, TranscodeFormat, TransferFunction,
+        VkFormat,
     },
-    sys, KtxError,
+    error::{Error, ResultExt},
+    metadata::Metadata,
+    sources::{CommonCreateInfo, FileSource, ForeignSource, Ktx1CreateInfo, Ktx2CreateInfo},
+    sys, KtxError, TextureCreateFlags,
+};
+#[cfg(feature = "write")]
+use crate::{
+    sinks::StreamSink,
+    sources::OwnedMemorySource,
+    stream::{RWSeekable, RustKtxStream, StreamContainer},
+};
+use std::{
+    borrow::Cow,
+    cell::Cell,
+    convert::{TryFrom, TryInto},
+    ffi::{CStr, CString},
+    fmt,
+    marker::PhantomData,
+    ops::Deref,
+    path::Path,
 };
-use std::{convert::TryInto, marker::PhantomData};
+
+/// The default writer identification for [`Texture::set_writer`], i.e. `libktx-rs <version>`.
+pub const DEFAULT_WRITER: &str = concat!("libktx-rs ", env!("CARGO_PKG_VERSION"));
 
 /// A source of [`Texture`]s.
 pub trait TextureSource<'a> {
     /// Attempts to create a new texture by consuming `self`.  
-    fn create_texture(self) -> Result<Texture<'a>, KtxError>;
+    fn create_texture(self) -> Result<Texture<'a>, Error>;
 }
 
 /// A sink of [`Texture`]s, e.g. something they can be written to.
 #[cfg(feature = "write")]
 pub trait TextureSink {
     /// Attempts to write `texture` to `self`.
-    fn write_texture(&mut self, texture: &Texture) -> Result<(), KtxError>;
+    fn write_texture(&mut self, texture: &Texture) -> Result<(), Error>;
 }
 
 /// Parameters for ASTC compression.
 ///
 /// This only applies to Arm's ASTC encoder, which is in `libktx-rs-sys/build/KTX-Software/lib/astc-encoder`.  
 /// See [`sys::ktxAstcParams`] for information on the various fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct AstcParams {
     pub verbose: bool,
+    /// The number of threads to encode with; 0 means "auto" (see [`std::thread::available_parallelism`]).
     pub thread_count: u32,
     pub block_dimension: PackAstcBlockDimension,
     pub function: PackAstcEncoderFunction,
@@ -41,6 +69,112 @@ pub struct AstcParams {
     pub input_swizzle: [char; 4],
 }
 
+impl Default for AstcParams {
+    /// Medium-quality, 4x4-block, multi-threaded compression of a non-normal map with an `rgba` swizzle.
+    fn default() -> Self {
+        AstcParams {
+            verbose: false,
+            thread_count: 0,
+            block_dimension: PackAstcBlockDimension::Dim4x4,
+            function: PackAstcEncoderFunction::Unknown,
+            mode: PackAstcEncoderMode::Default,
+            quality_level: PackAstcQualityLevel::Medium,
+            normal_map: false,
+            input_swizzle: ['r', 'g', 'b', 'a'],
+        }
+    }
+}
+
+impl AstcParams {
+    /// Attempts to set [`Self::input_swizzle`] from a 4-character string, like `"rgba"` or `"rrr1"`.
+    ///
+    /// Fails with [`KtxError::InvalidValue`] if `swizzle` is not exactly 4 characters long,
+    /// or if any of them is not one of `r`, `g`, `b`, `a`, `0` or `1`.
+    pub fn with_swizzle(mut self, swizzle: &str) -> Result<Self, KtxError> {
+        self.input_swizzle = parse_swizzle(swizzle)?;
+        Ok(self)
+    }
+}
+
+/// Parameters for Basis Universal compression (either ETC1S/BasisLZ or UASTC).
+///
+/// See [`sys::ktxBasisParams`] for information on the various fields.  
+/// Zeroed-out fields (i.e. the [`Default`]) make the C library use its own defaults.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct BasisParams {
+    /// Encode to UASTC instead of ETC1S/BasisLZ?
+    pub uastc: bool,
+    pub verbose: bool,
+    pub no_sse: bool,
+    /// The number of threads to encode with; 0 means "auto" (see [`std::thread::available_parallelism`]).
+    pub thread_count: u32,
+    // ETC1S/BasisLZ-only parameters
+    pub compression_level: u32,
+    pub quality_level: u32,
+    pub max_endpoints: u32,
+    pub endpoint_rdo_threshold: f32,
+    pub max_selectors: u32,
+    pub selector_rdo_threshold: f32,
+    pub input_swizzle: [char; 4],
+    pub normal_map: bool,
+    pub separate_rg_to_rgb_a: bool,
+    pub pre_swizzle: bool,
+    pub no_endpoint_rdo: bool,
+    pub no_selector_rdo: bool,
+    // UASTC-only parameters
+    pub uastc_flags: PackUastcFlags,
+    pub uastc_rdo: bool,
+    pub rdo_quality: f32,
+    pub uastc_rdo_dict_size: u32,
+    pub uastc_rdo_max_smooth_block_error_scale: f32,
+    pub uastc_rdo_max_smooth_block_std_dev: f32,
+    pub uastc_rdo_dont_favor_simpler_modes: bool,
+    pub uastc_rdo_no_multithreading: bool,
+}
+
+impl Default for BasisParams {
+    fn default() -> Self {
+        BasisParams {
+            uastc: false,
+            verbose: false,
+            no_sse: false,
+            thread_count: 0,
+            compression_level: 0,
+            quality_level: 0,
+            max_endpoints: 0,
+            endpoint_rdo_threshold: 0.0,
+            max_selectors: 0,
+            selector_rdo_threshold: 0.0,
+            input_swizzle: ['\0'; 4],
+            normal_map: false,
+            separate_rg_to_rgb_a: false,
+            pre_swizzle: false,
+            no_endpoint_rdo: false,
+            no_selector_rdo: false,
+            uastc_flags: PackUastcFlags::empty(),
+            uastc_rdo: false,
+            rdo_quality: 0.0,
+            uastc_rdo_dict_size: 0,
+            uastc_rdo_max_smooth_block_error_scale: 0.0,
+            uastc_rdo_max_smooth_block_std_dev: 0.0,
+            uastc_rdo_dont_favor_simpler_modes: false,
+            uastc_rdo_no_multithreading: false,
+        }
+    }
+}
+
+/// Returns `thread_count`, or the available parallelism if it is 0 ("auto").
+fn resolve_thread_count(thread_count: u32) -> u32 {
+    match thread_count {
+        0 => std::thread::available_parallelism()
+            .map(|count| count.get() as u32)
+            .unwrap_or(1),
+        _ => thread_count,
+    }
+}
+
 /// A KTX (1 or 2) texture.
 ///
 /// This wraps both a [`sys::ktxTexture`] handle, and the [`TextureSource`] it was created from.
@@ -50,23 +184,200 @@ pub struct Texture<'a> {
     pub(crate) source: Box<dyn TextureSource<'a> + 'a>,
     pub(crate) handle: *mut sys::ktxTexture,
     pub(crate) handle_phantom: PhantomData<&'a sys::ktxTexture>,
+    // libKTX tears down the source stream after `IterateLoadLevelFaces`, so it can only be used once
+    pub(crate) source_consumed: bool,
+    // Hash list parsed on demand from the raw KVData of textures created with `RAW_KVDATA`
+    pub(crate) raw_kv_list: Cell<sys::ktxHashList>,
 }
 
 impl<'a> Texture<'a> {
     /// Attempts to create a new texture, consuming the given [`TextureSource`].
-    pub fn new<S>(source: S) -> Result<Self, KtxError>
+    pub fn new<S>(source: S) -> Result<Self, Error>
     where
         S: TextureSource<'a>,
     {
         source.create_texture()
     }
 
+    /// Adopts a texture created elsewhere (e.g. by other C code) from its raw handle.  
+    /// The texture will be destroyed (via its `Destroy` vtable function) when the returned [`Texture`] is dropped.
+    ///
+    /// # Safety
+    /// `handle` must be a valid, non-null pointer to a [`sys::ktxTexture1`] or [`sys::ktxTexture2`] created by libKTX,
+    /// which is not owned (nor destroyed) by anyone else afterwards.  
+    /// Whatever it references (e.g. the memory or stream it was read from, if its image data was not loaded)
+    /// must outlive the returned texture.
+    pub unsafe fn from_raw(handle: *mut sys::ktxTexture) -> Texture<'static> {
+        Texture {
+            source: Box::new(ForeignSource),
+            handle,
+            handle_phantom: PhantomData,
+            source_consumed: false,
+            raw_kv_list: Cell::new(std::ptr::null_mut()),
+        }
+    }
+
+    /// Attempts to create a new texture from `source`, and to get it ready for uploading:
+    /// its image data is loaded (if `source` did not already do so, see [`TextureCreateFlags::LOAD_IMAGE_DATA`]),
+    /// then KTX2s that [need transcoding](Ktx2::needs_transcoding) are transcoded to `format`
+    /// (see [`Ktx2::transcode_basis`]).
+    ///
+    /// KTX1s and KTX2s that need no transcoding are only loaded; use [`Self::ktx2`] and [`Ktx2::vk_format`]
+    /// to find out which format the texture ended up in.
+    pub fn load_transcoded<S>(
+        source: S,
+        format: TranscodeFormat,
+        flags: TranscodeFlags,
+    ) -> Result<Self, Error>
+    where
+        S: TextureSource<'a>,
+    {
+        let mut texture = Self::new(source)?;
+        if texture.data_checked().is_none() {
+            texture
+                .load_image_data()
+                .context(|| "Texture::load_transcoded")?;
+        }
+        if let Some(mut ktx2) = texture.ktx2() {
+            if ktx2.needs_transcoding() {
+                ktx2.transcode_basis(format, flags)?;
+            }
+        }
+        Ok(texture)
+    }
+
+    /// Attempts to read a texture from the file at `path`.  
+    /// This is a shorthand for creating a texture from a [`FileSource`].
+    pub fn from_file<P: AsRef<Path>>(
+        path: P,
+        create_flags: TextureCreateFlags,
+    ) -> Result<Self, Error> {
+        Self::new(FileSource::new(path, create_flags))
+    }
+
+    /// Attempts to read only the header (and metadata) of the texture in the file at `path`,
+    /// without loading its image data.  
+    /// This is a shorthand for [`Self::from_file`] with empty [`TextureCreateFlags`].
+    ///
+    /// All header accessors (dimensions, levels, format, supercompression...) and [`Self::metadata`] work as usual,
+    /// while [`Self::data_checked`] returns `None` until [`Self::load_image_data`] is called.  
+    /// The C library has no "read the header, then close" path: the file stays open until either
+    /// the image data is loaded or the texture is dropped. Any other [`TextureSource`] created with empty flags
+    /// (e.g. [`crate::sources::StreamSource`]) behaves the same way.
+    pub fn read_header<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Self::from_file(path, TextureCreateFlags::empty())
+    }
+
     /// Attempts to write the texture (in its native format, either KTX1 or KTX2) to `sink`.
     #[cfg(feature = "write")]
-    pub fn write_to<T: TextureSink>(&self, sink: &mut T) -> Result<(), KtxError> {
+    pub fn write_to<T: TextureSink>(&self, sink: &mut T) -> Result<(), Error> {
         sink.write_texture(self)
     }
 
+    /// Attempts to write the texture (in its native format, either KTX1 or KTX2) to an in-memory buffer.  
+    /// This is a shorthand for writing to a [`crate::sinks::MemorySink`].
+    #[cfg(feature = "write")]
+    pub fn write_to_memory(&self) -> Result<Vec<u8>, Error> {
+        let mut sink = crate::sinks::MemorySink::new();
+        self.write_to(&mut sink)?;
+        Ok(sink.into_inner())
+    }
+
+    /// Attempts to compute the length (in bytes) of the texture once serialized in its native format,
+    /// i.e. of the whole file that [`Self::write_to_memory`] would produce, to pre-size a buffer for it.
+    ///
+    /// **This is not [`Self::data_size`]**, which is the size of the image data only: the serialized texture also
+    /// contains its header, level index, DFD, metadata (KVData), supercompression global data and padding.  
+    /// The texture is serialized to a stream that only counts bytes (nothing is buffered in memory).
+    #[cfg(feature = "write")]
+    pub fn serialized_len(&self) -> Result<usize, KtxError> {
+        let stream = RustKtxStream::new(Box::new(crate::sinks::LenCounter::default()))
+            .map_err(crate::enums::ktx_error)?;
+        let mut sink = StreamSink::new(stream);
+        self.write_to(&mut sink).map_err(|err| err.code())?;
+        Ok(sink.into_inner().into_inner().len as usize)
+    }
+
+    /// Attempts to write the texture (in its native format, either KTX1 or KTX2) to `writer`, which needs not be seekable
+    /// (e.g. a socket or a compressor).
+    ///
+    /// Since the C library may seek while writing, the whole texture is first serialized to memory
+    /// (see [`Self::write_to_memory`]), then written out to `writer` in one go; this temporarily takes up
+    /// as much extra memory as the serialized texture. Prefer a [`crate::sinks::StreamSink`] for seekable targets.
+    #[cfg(feature = "write")]
+    pub fn write_to_writer<W: std::io::Write + ?Sized>(&self, writer: &mut W) -> Result<(), Error> {
+        let bytes = self.write_to_memory()?;
+        writer.write_all(&bytes).map_err(|err| {
+            log_error!("Texture::write_to_writer: {}", err);
+            Error::new(
+                KtxError::FileWriteError,
+                format!("Texture::write_to_writer: {}", err),
+            )
+        })
+    }
+
+    /// Attempts to detach this texture from the [`TextureSource`] it was created from,
+    /// returning an [`OwnedTexture`] that can be sent across threads.
+    ///
+    /// Since some sources (e.g. [`crate::sources::StreamSource`]) are not [`Send`], this works by writing the
+    /// texture to memory and reading it back from an owned buffer. Image data is loaded in the process.
+    #[cfg(feature = "write")]
+    pub fn into_owned(self) -> Result<OwnedTexture, Error> {
+        // SAFETY: Safe if `self.handle` is sane.
+        if unsafe { (*self.handle).pData.is_null() } {
+            self.load_image_data().context(|| "Texture::into_owned")?;
+        }
+        let bytes = self.write_to_memory()?;
+        let texture = Texture::new(OwnedMemorySource {
+            bytes,
+            create_flags: TextureCreateFlags::LOAD_IMAGE_DATA,
+        })?;
+        Ok(OwnedTexture { texture })
+    }
+
+    /// Attempts to write the texture (in its native format, either KTX1 or KTX2) to the file at `path`.  
+    /// This is a shorthand for writing to a [`crate::sinks::FileSink`].
+    #[cfg(feature = "write")]
+    pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        self.write_to(&mut crate::sinks::FileSink::new(path))
+    }
+
+    /// Attempts to upload this texture to a new Vulkan image on the device described by `device_info`.
+    ///
+    /// `tiling`, `usage_flags` and `final_layout` are the raw `VkImageTiling`, `VkImageUsageFlags`
+    /// and `VkImageLayout` to create the image with.  
+    /// Failures on the Vulkan side are reported as [`KtxError`]s by the underlying C library.
+    ///
+    /// Image data must already be loaded (see [`Self::load_image_data()`]), otherwise this fails with
+    /// [`KtxError::InvalidOperation`]: the C library would load it from the texture's source and then release it.
+    #[cfg(feature = "vulkan")]
+    pub fn vk_upload(
+        &self,
+        device_info: &crate::vulkan::VkDeviceInfo,
+        tiling: u32,
+        usage_flags: u32,
+        final_layout: u32,
+    ) -> Result<crate::vulkan::VkTexture, KtxError> {
+        // SAFETY: Safe if `self.handle` is sane.
+        if unsafe { (*self.handle).pData.is_null() } {
+            return Err(KtxError::InvalidOperation);
+        }
+        // SAFETY: The C library fully initializes the struct on success
+        let mut vk_texture: sys::ktxVulkanTexture = unsafe { std::mem::zeroed() };
+        // SAFETY: Safe if `self.handle` and `device_info.handle` are sane.
+        let err = unsafe {
+            sys::ktxTexture_VkUploadEx(
+                self.handle,
+                device_info.handle,
+                &mut vk_texture,
+                tiling,
+                usage_flags,
+                final_layout,
+            )
+        };
+        ktx_result(err, vk_texture.into())
+    }
+
     /// Returns the pointer to the (C-allocated) underlying [`sys::ktxTexture`].
     ///
     /// **SAFETY**: Pointers are harmless. Dereferencing them is not!
@@ -74,26 +385,65 @@ impl<'a> Texture<'a> {
         self.handle
     }
 
-    /// Returns the total size of image data, in bytes.
+    /// Relinquishes ownership of the underlying [`sys::ktxTexture`], returning its handle without destroying it.  
+    /// The caller becomes responsible for destroying it, for example with [`Self::from_raw`].
+    ///
+    /// Since the handle may still reference the texture's source (e.g. if image data was not loaded),
+    /// the source is leaked instead of being dropped; the handle must not be used past `'a`.
+    pub fn into_raw(self) -> *mut sys::ktxTexture {
+        // Neither the handle nor the source are dropped
+        let this = std::mem::ManuallyDrop::new(self);
+        this.handle
+    }
+
+    /// Returns the total size of image data, in bytes.  
+    /// This does not include the header, metadata etc.; see [`Self::serialized_len`] for the size of the whole file.
     pub fn data_size(&self) -> usize {
         // SAFETY: Safe if `self.handle` is sane.
         unsafe { sys::ktxTexture_GetDataSize(self.handle) as usize }
     }
 
     /// Returns a read-only view on the image data.
+    ///
+    /// This is empty if image data was not loaded (or allocated); also see [`Self::data_checked`].
     pub fn data(&self) -> &[u8] {
-        let data = unsafe { sys::ktxTexture_GetData(self.handle) };
+        self.data_checked().unwrap_or(&[])
+    }
+
+    /// Returns a read-only view on the image data, or `None` if it was not loaded (or allocated),
+    /// e.g. for textures created with [`CreateStorage::NoStorage`] or without [`TextureCreateFlags::LOAD_IMAGE_DATA`].
+    pub fn data_checked(&self) -> Option<&[u8]> {
         // SAFETY: Safe if `self.handle` is sane.
-        unsafe { std::slice::from_raw_parts(data, self.data_size()) }
+        let data = unsafe { sys::ktxTexture_GetData(self.handle) };
+        if data.is_null() {
+            return None;
+        }
+        // SAFETY: Safe if `self.handle` is sane; `data` points to `data_size()` bytes.
+        Some(unsafe { std::slice::from_raw_parts(data, self.data_size()) })
     }
 
     /// Returns a read-write view on the image data.
+    ///
+    /// This is empty if image data was not loaded (or allocated); also see [`Self::data_checked`].
     pub fn data_mut(&mut self) -> &mut [u8] {
-        let data = unsafe { sys::ktxTexture_GetData(self.handle) };
         // SAFETY: Safe if `self.handle` is sane.
+        let data = unsafe { sys::ktxTexture_GetData(self.handle) };
+        if data.is_null() {
+            return &mut [];
+        }
+        // SAFETY: Safe if `self.handle` is sane; `data` points to `data_size()` bytes.
         unsafe { std::slice::from_raw_parts_mut(data, self.data_size()) }
     }
 
+    /// Consumes the texture, returning its image data as an owned buffer.
+    ///
+    /// The buffer is allocated by the C library's allocator, so ownership cannot be transferred to a `Vec`
+    /// directly: this performs exactly one copy of the data, then destroys the texture (freeing its buffer).  
+    /// The result is empty if image data was not loaded (or allocated); also see [`Self::data_checked`].
+    pub fn into_data(self) -> Vec<u8> {
+        self.data().to_vec()
+    }
+
     /// Returns the pitch (in bytes) of an image row at the specified image level.  
     /// This is rounded up to 1 if needed.
     pub fn row_pitch(&self, level: u32) -> usize {
@@ -102,6 +452,23 @@ impl<'a> Texture<'a> {
         unsafe { sys::ktxTexture_GetRowPitch(self.handle, level) as usize }
     }
 
+    /// Returns the pitch (in bytes) of an image row at the specified image level,
+    /// rounded up to a multiple of `alignment` (as in [`Self::row_pitch`] otherwise).  
+    /// This is the pitch graphics APIs expect when copying rows into a staging buffer,
+    /// for example 4 for OpenGL's default unpack alignment or 256 for D3D12.
+    ///
+    /// # Panics
+    /// If `alignment` is not a power of two.
+    pub fn row_pitch_aligned(&self, level: u32, alignment: u32) -> usize {
+        assert!(
+            alignment.is_power_of_two(),
+            "row alignment {} is not a power of two",
+            alignment
+        );
+        let mask = alignment as usize - 1;
+        (self.row_pitch(level) + mask) & !mask
+    }
+
     /// Returns the size (in bytes) of an element of the image.
     pub fn element_size(&self) -> usize {
         // SAFETY: Safe if `self.handle` is sane.
@@ -126,6 +493,28 @@ impl<'a> Texture<'a> {
         unsafe { (*self.handle).isCompressed }
     }
 
+    /// Returns `true` if the texture's data is sRGB-encoded.
+    ///
+    /// For KTX2, this checks the transfer function in the DFD (see [`Ktx2::transfer_function`]);
+    /// for KTX1, the OpenGL internal format (see [`GlInternalFormat::is_srgb`]).
+    pub fn is_srgb(&self) -> bool {
+        // SAFETY: Safe if `self.handle` is sane; the class ID tells which texture struct it really is.
+        unsafe {
+            match (*self.handle).classId {
+                sys::class_id_ktxTexture1_c => GlInternalFormat::from(
+                    (*(self.handle as *mut sys::ktxTexture1)).glInternalformat,
+                )
+                .is_srgb(),
+                sys::class_id_ktxTexture2_c => {
+                    TransferFunction::from(sys::ktxTexture2_GetOETF(
+                        self.handle as *mut sys::ktxTexture2,
+                    )) == TransferFunction::Srgb
+                }
+                _ => false,
+            }
+        }
+    }
+
     /// Returns the width (in texels) of this texture's base level.
     pub fn base_width(&self) -> usize {
         // SAFETY: Safe if `self.handle` is sane.
@@ -144,6 +533,56 @@ impl<'a> Texture<'a> {
         unsafe { (*self.handle).baseDepth as usize }
     }
 
+    /// Returns the width, height and depth (in texels) of this texture's base level.
+    pub fn dimensions(&self) -> Extent3d {
+        Extent3d {
+            width: self.base_width(),
+            height: self.base_height(),
+            depth: self.base_depth(),
+        }
+    }
+
+    /// Returns the width, height and depth (in texels) of the given mip level of this texture,
+    /// i.e. [`Self::dimensions`] halved `level` times (but never less than 1).
+    pub fn extent_at_level(&self, level: u32) -> Extent3d {
+        let base = self.dimensions();
+        Extent3d {
+            width: base.width.checked_shr(level).unwrap_or(0).max(1),
+            height: base.height.checked_shr(level).unwrap_or(0).max(1),
+            depth: base.depth.checked_shr(level).unwrap_or(0).max(1),
+        }
+    }
+
+    /// Returns `true` if all base dimensions of this texture (width, height and depth) are powers of two.
+    pub fn is_power_of_two(&self) -> bool {
+        let base = self.dimensions();
+        base.width.is_power_of_two()
+            && base.height.is_power_of_two()
+            && base.depth.is_power_of_two()
+    }
+
+    /// Returns `true` if the base width and height of this texture are equal.
+    pub fn is_square(&self) -> bool {
+        self.base_width() == self.base_height()
+    }
+
+    /// Checks that this texture can be compressed or transcoded to PVRTC1, which requires square,
+    /// power-of-two 2D textures.  
+    /// Fails with [`KtxError::UnsupportedFeature`] (and a description of the problem) otherwise.
+    pub fn validate_for_pvrtc(&self) -> Result<(), Error> {
+        let base = self.dimensions();
+        if self.num_dimensions() == 3 || !self.is_square() || !self.is_power_of_two() {
+            return Err(Error::new(
+                KtxError::UnsupportedFeature,
+                format!(
+                    "validate_for_pvrtc({}x{}x{}; must be 2D, square and power-of-two)",
+                    base.width, base.height, base.depth
+                ),
+            ));
+        }
+        Ok(())
+    }
+
     /// Returns the number of dimensions in this texture (1, 2 or 3).
     pub fn num_dimensions(&self) -> usize {
         // SAFETY: Safe if `self.handle` is sane.
@@ -167,7 +606,20 @@ impl<'a> Texture<'a> {
     /// Returns the number of faces in this texture. It is 1 for standard images, and 6 for cubemaps.
     pub fn num_faces(&self) -> usize {
         // SAFETY: Safe if `self.handle` is sane.
-        unsafe { (*self.handle).numLayers as usize }
+        unsafe { (*self.handle).numFaces as usize }
+    }
+
+    /// Returns the total number of 2D images in this texture, i.e. the sum over all mip levels
+    /// of [`Self::num_layers`] × [`Self::num_faces`] × the depth of the level (see [`Self::extent_at_level`]).
+    ///
+    /// This is the number of distinct images addressed by [`Self::get_image_offset`], each [`Self::get_image_size`] bytes.  
+    /// Note that [`Self::iterate_levels`] calls back once per level and face instead, passing all layers and
+    /// depth slices of it at once; the images it sees still add up to this count.
+    pub fn total_image_count(&self) -> usize {
+        let images_per_slice = self.num_layers() * self.num_faces();
+        (0..self.num_levels() as u32)
+            .map(|level| images_per_slice * self.extent_at_level(level).depth)
+            .sum()
     }
 
     /// Returns the logical orientation of this texture in all possible directions (X, Y and Z).
@@ -188,6 +640,162 @@ impl<'a> Texture<'a> {
         }
     }
 
+    /// Attempts to set the logical orientation of the texture.
+    ///
+    /// This also writes the `KTXorientation` metadata key, so that the orientation is persisted
+    /// when the texture is written out. Only the first [`Self::num_dimensions`] directions are written,
+    /// in the KTX1 (e.g. `S=r,T=d`) or KTX2 (e.g. `rd`) form depending on the texture's class.
+    pub fn set_orientation(&mut self, orientation: Orientations) -> Result<(), KtxError> {
+        let num_dimensions = self.num_dimensions().clamp(1, 3);
+        let chars: Vec<char> = orientation
+            .to_string()
+            .chars()
+            .take(num_dimensions)
+            .collect();
+        // SAFETY: Safe if `self.handle` is sane.
+        let is_ktx1 = unsafe { (*self.handle).classId == sys::class_id_ktxTexture1_c };
+        let mut value = if is_ktx1 {
+            chars
+                .iter()
+                .zip(["S", "T", "R"].iter())
+                .map(|(ch, axis)| format!("{}={}", axis, ch))
+                .collect::<Vec<_>>()
+                .join(",")
+        } else {
+            chars.into_iter().collect()
+        };
+        value.push('\0');
+        self.set_metadata("KTXorientation", value.as_bytes())?;
+
+        // SAFETY: Safe if `self.handle` is sane.
+        unsafe {
+            let c_orientation = &mut (*self.handle).orientation;
+            c_orientation.x = orientation.x as sys::ktxOrientationX;
+            c_orientation.y = orientation.y as sys::ktxOrientationY;
+            c_orientation.z = orientation.z as sys::ktxOrientationZ;
+        }
+        Ok(())
+    }
+
+    /// Returns a read-only view on the key/value metadata of this texture.
+    ///
+    /// If the texture was created with [`crate::TextureCreateFlags::RAW_KVDATA`], its raw metadata is parsed
+    /// (once) on the first call to this.  
+    /// This is empty if the texture was created with [`crate::TextureCreateFlags::SKIP_KVDATA`].
+    pub fn metadata(&self) -> Metadata<'_> {
+        // SAFETY: Safe if `self.handle` is sane. Both hash lists live as long as `self`.
+        unsafe {
+            let texture = &mut *self.handle;
+            if texture.kvDataHead.is_null() && !texture.kvData.is_null() {
+                if self.raw_kv_list.get().is_null() {
+                    let mut list: sys::ktxHashList = std::ptr::null_mut();
+                    let err = sys::ktxHashList_Deserialize(
+                        &mut list,
+                        texture.kvDataLen,
+                        texture.kvData as *mut std::ffi::c_void,
+                    );
+                    if err != sys::ktx_error_code_e_KTX_SUCCESS {
+                        // Malformed KVData; treat it as no metadata at all
+                        sys::ktxHashList_Destruct(&mut list);
+                        list = std::ptr::null_mut();
+                    }
+                    self.raw_kv_list.set(list);
+                }
+                Metadata::from_head(self.raw_kv_list.as_ptr())
+            } else {
+                Metadata::from_head(&mut texture.kvDataHead)
+            }
+        }
+    }
+
+    /// Returns an iterator over all `(key, value)` pairs in the metadata of this texture, in storage order.  
+    /// Keys are decoded as UTF-8, replacing invalid sequences (see [`String::from_utf8_lossy`]);
+    /// use [`Self::metadata`] for their raw bytes.
+    pub fn metadata_iter(&self) -> impl Iterator<Item = (Cow<'_, str>, &[u8])> {
+        self.metadata()
+            .iter()
+            .map(|(key, value)| (String::from_utf8_lossy(key), value))
+    }
+
+    /// Attempts to set the metadata value associated to `key`.  
+    /// If `key` is already present, its value is overwritten.
+    ///
+    /// Fails with [`KtxError::InvalidValue`] if `key` is empty or contains NULs.
+    pub fn set_metadata(&mut self, key: &str, value: &[u8]) -> Result<(), KtxError> {
+        let c_key = CString::new(key).map_err(|_| KtxError::InvalidValue)?;
+        self.set_metadata_c(&c_key, value)
+    }
+
+    /// Like [`Self::set_metadata`], but with a C string key (that needs not be UTF-8).
+    fn set_metadata_c(&mut self, key: &CStr, value: &[u8]) -> Result<(), KtxError> {
+        // SAFETY: Safe if `self.handle` is sane.
+        unsafe {
+            let head = &mut (*self.handle).kvDataHead;
+            // The C hash list allows duplicate keys; remove the old entry (if any) first
+            delete_kv_entry(head, key);
+            let err = sys::ktxHashList_AddKVPair(
+                head,
+                key.as_ptr(),
+                value.len() as std::os::raw::c_uint,
+                value.as_ptr() as *const std::ffi::c_void,
+            );
+            ktx_result(err, ())
+        }
+    }
+
+    /// Attempts to copy all metadata of `other` to this texture, including `KTXorientation` and `KTXwriter`;
+    /// e.g. to preserve it across a transcode or rewrite.
+    ///
+    /// Keys that are present in both textures are overwritten with the values from `other`,
+    /// while keys that are only present in this texture are kept as they are.  
+    /// Keys are copied byte-for-byte, even if they are not valid UTF-8. The exception is `KTXorientation`,
+    /// which is set via [`Self::set_orientation`] so that it is in this texture's form (KTX1 or KTX2)
+    /// and that [`Self::orientation`] is updated too.
+    pub fn copy_metadata_from(&mut self, other: &Texture) -> Result<(), KtxError> {
+        for (key, value) in other.metadata().iter() {
+            if key == b"KTXorientation" {
+                self.set_orientation(other.orientation())?;
+            } else {
+                let c_key = CString::new(key).map_err(|_| KtxError::InvalidValue)?;
+                self.set_metadata_c(&c_key, value)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Attempts to set the `KTXwriter` metadata key, which identifies the tool that wrote the texture.  
+    /// Valid KTX2 files are expected to carry it; pass [`DEFAULT_WRITER`] to identify this crate.
+    ///
+    /// Fails with [`KtxError::InvalidValue`] if `writer` contains NULs.
+    pub fn set_writer(&mut self, writer: &str) -> Result<(), KtxError> {
+        if writer.contains('\0') {
+            return Err(KtxError::InvalidValue);
+        }
+        let mut value = writer.to_owned();
+        value.push('\0');
+        self.set_metadata("KTXwriter", value.as_bytes())
+    }
+
+    /// Attempts to remove `key` (and its value) from the metadata.
+    ///
+    /// Fails with [`KtxError::NotFound`] if `key` is not present.
+    pub fn delete_metadata(&mut self, key: &str) -> Result<(), KtxError> {
+        let c_key = CString::new(key).map_err(|_| KtxError::InvalidValue)?;
+        // SAFETY: Safe if `self.handle` is sane.
+        let err = unsafe { delete_kv_entry(&mut (*self.handle).kvDataHead, &c_key) };
+        ktx_result(err, ())
+    }
+
+    /// Attempts to sort the metadata by key (in codepoint order), so that it is written out in a deterministic order
+    /// regardless of the order keys were set in.
+    ///
+    /// The C library already does this when writing KTX2s, as their spec requires; KTX1s are written in insertion order.
+    pub fn sort_metadata(&mut self) -> Result<(), KtxError> {
+        // SAFETY: Safe if `self.handle` is sane.
+        let err = unsafe { sys::ktxHashList_Sort(&mut (*self.handle).kvDataHead) };
+        ktx_result(err, ())
+    }
+
     /// Attempts to return the offset (in bytes) into [`Self::data`] for the image
     /// at the given mip level, array layer, and slice.  
     /// `slice` is either a cubemap's face or a 3D texture's depth slice.
@@ -205,7 +813,150 @@ impl<'a> Texture<'a> {
         }
     }
 
-    /// Attempts to return the size (in bytes) of the uncompressed image data.
+    /// Attempts to return the offset (in bytes) into [`Self::data`] for the image
+    /// at the given mip level, array layer, and cubemap face.
+    ///
+    /// Fails with [`KtxError::InvalidValue`] if any of them is out of range
+    /// (`face` must be less than [`Self::num_faces`], i.e. 0 for non-cubemaps).
+    pub fn image_offset_2d(&self, level: u32, layer: u32, face: u32) -> Result<usize, KtxError> {
+        self.check_level_layer(level, layer)?;
+        if face as usize >= self.num_faces() {
+            return Err(KtxError::InvalidValue);
+        }
+        self.get_image_offset(level, layer, face)
+    }
+
+    /// Attempts to return the offset (in bytes) into [`Self::data`] for the depth slice `z`
+    /// at the given mip level and array layer of a 3D texture.
+    ///
+    /// Fails with [`KtxError::InvalidValue`] if any of them is out of range
+    /// (`z` must be less than the depth of `level`, see [`Self::extent_at_level`]).
+    pub fn image_offset_3d(&self, level: u32, layer: u32, z: u32) -> Result<usize, KtxError> {
+        self.check_level_layer(level, layer)?;
+        if self.is_cubemap() || z as usize >= self.extent_at_level(level).depth {
+            return Err(KtxError::InvalidValue);
+        }
+        self.get_image_offset(level, layer, z)
+    }
+
+    fn check_level_layer(&self, level: u32, layer: u32) -> Result<(), KtxError> {
+        if level as usize >= self.num_levels() || layer as usize >= self.num_layers() {
+            Err(KtxError::InvalidValue)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Attempts to return a read-only view on the image data at the given mip level, array layer, and slice.  
+    /// `slice` is either a cubemap's face or a 3D texture's depth slice.
+    ///
+    /// Note that image data should already have been loaded (see [`Self::load_image_data()`]).
+    pub fn get_image_data(&self, level: u32, layer: u32, slice: u32) -> Result<&[u8], KtxError> {
+        let range = self.image_data_range(level, layer, slice)?;
+        self.data().get(range).ok_or(KtxError::InvalidValue)
+    }
+
+    /// Attempts to return a read-write view on the image data at the given mip level, array layer, and slice.  
+    /// `slice` is either a cubemap's face or a 3D texture's depth slice.
+    ///
+    /// Note that image data should already have been loaded (see [`Self::load_image_data()`]).
+    pub fn get_image_data_mut(
+        &mut self,
+        level: u32,
+        layer: u32,
+        slice: u32,
+    ) -> Result<&mut [u8], KtxError> {
+        let range = self.image_data_range(level, layer, slice)?;
+        self.data_mut().get_mut(range).ok_or(KtxError::InvalidValue)
+    }
+
+    /// Returns an iterator over the image data of each face of a cubemap, at the given mip level and array layer,
+    /// in the canonical +X, -X, +Y, -Y, +Z, -Z order; see [`Self::get_image_data`].
+    ///
+    /// For non-cubemaps, this yields a single [`KtxError::InvalidOperation`].  
+    /// Each face is [`KtxError::InvalidValue`] if `level` or `layer` are out of range, or if image data was not loaded.
+    pub fn faces(
+        &self,
+        level: u32,
+        layer: u32,
+    ) -> impl Iterator<Item = Result<&[u8], KtxError>> + '_ {
+        // (Collected eagerly, as the iterator can't capture `self` without also capturing the source's lifetime)
+        let faces: Vec<_> = if self.is_cubemap() {
+            (0..self.num_faces() as u32)
+                .map(|face| self.get_image_data(level, layer, face))
+                .collect()
+        } else {
+            vec![Err(KtxError::InvalidOperation)]
+        };
+        faces.into_iter()
+    }
+
+    /// Attempts to overwrite the image at the given mip level, array layer, and slice with the data in `src`.  
+    /// `slice` is either a cubemap's face or a 3D texture's depth slice.
+    ///
+    /// `src` must be exactly as big as an image at `level` (see [`Self::get_image_size`]),
+    /// otherwise [`KtxError::InvalidValue`] is returned.  
+    /// The texture must have been created with [`crate::CreateStorage::AllocStorage`].
+    #[cfg(feature = "write")]
+    pub fn set_image(
+        &mut self,
+        level: u32,
+        layer: u32,
+        slice: u32,
+        src: &[u8],
+    ) -> Result<(), KtxError> {
+        if src.len() != self.get_image_size(level)? {
+            return Err(KtxError::InvalidValue);
+        }
+
+        // SAFETY: Safe if `self.handle` is sane. `src` is exactly as big as the C library expects.
+        unsafe {
+            let vtbl = (*self.handle).vtbl;
+            if let Some(set_image_fn) = (*vtbl).SetImageFromMemory {
+                let err = (set_image_fn)(
+                    self.handle,
+                    level,
+                    layer,
+                    slice,
+                    src.as_ptr(),
+                    src.len() as sys::ktx_size_t,
+                );
+                ktx_result(err, ())
+            } else {
+                Err(KtxError::InvalidValue)
+            }
+        }
+    }
+
+    pub(crate) fn image_data_range(
+        &self,
+        level: u32,
+        layer: u32,
+        slice: u32,
+    ) -> Result<std::ops::Range<usize>, KtxError> {
+        // SAFETY: Safe if `self.handle` is sane.
+        if unsafe { (*self.handle).pData.is_null() } {
+            // Data was not loaded
+            return Err(KtxError::InvalidValue);
+        }
+        self.check_level_layer(level, layer)?;
+        let num_slices = if self.is_cubemap() {
+            self.num_faces()
+        } else {
+            self.extent_at_level(level).depth
+        };
+        if slice as usize >= num_slices {
+            return Err(KtxError::InvalidValue);
+        }
+
+        let offset = self.get_image_offset(level, layer, slice)?;
+        let size = self.get_image_size(level)?;
+        Ok(offset..offset + size)
+    }
+
+    /// Attempts to return the size (in bytes) of the uncompressed image data.  
+    /// For supercompressed KTX2 textures, this is the size of the data once inflated/transcoded,
+    /// while [`Self::data_size`] is the size of the (supercompressed) data as stored.
     pub fn get_data_size_uncompressed(&self) -> Result<usize, KtxError> {
         // SAFETY: Safe if `self.handle` is sane.
         unsafe {
@@ -218,7 +969,12 @@ impl<'a> Texture<'a> {
         }
     }
 
-    /// Attempts to return the size (in bytes) of a certain mip level.
+    /// Attempts to return the size (in bytes) of a single image (i.e. one layer, face, or depth slice)
+    /// at a certain mip level.
+    ///
+    /// This is computed from the texture's format and extent, so it is always the *decoded* size,
+    /// even for supercompressed KTX2 textures; it is what GPU upload buffers should be sized after.  
+    /// Also see [`Ktx2::image_size_uncompressed`], which additionally checks `level`.
     pub fn get_image_size(&self, level: u32) -> Result<usize, KtxError> {
         // SAFETY: Safe if `self.handle` is sane.
         unsafe {
@@ -236,6 +992,10 @@ impl<'a> Texture<'a> {
     ///
     /// Creating the image with [`enums::TextureCreateFlags::LOAD_IMAGE_DATA`] performs this step automatically on load.
     pub fn load_image_data(&self) -> Result<(), KtxError> {
+        if self.source_consumed {
+            // The source stream was already torn down by `iterate_load_level_faces()`
+            return Err(KtxError::InvalidOperation);
+        }
         // SAFETY: Safe if `self.handle` is sane.
         unsafe {
             let vtbl = (*self.handle).vtbl;
@@ -254,8 +1014,9 @@ impl<'a> Texture<'a> {
     /// callback(miplevel: i32, face: i32, width: i32, height: i32, depth: i32, pixel_data: &[u8]) -> Result<(), KtxError>
     /// ```
     /// for each level/face. The image data passed to the callback is immutable.
+    /// If the callback returns an error, iteration stops and that same error is returned.
     /// Note that image data should already have been loaded (see [`Self::load_image_data()`]).
-    pub fn iterate_levels<F>(&self, mut callback: F) -> Result<(), KtxError>
+    pub fn iterate_levels<F>(&self, callback: F) -> Result<(), KtxError>
     where
         F: FnMut(i32, i32, i32, i32, i32, &[u8]) -> Result<(), KtxError>,
     {
@@ -272,12 +1033,16 @@ impl<'a> Texture<'a> {
         where
             F: FnMut(i32, i32, i32, i32, i32, &[u8]) -> Result<(), KtxError>,
         {
-            let closure = closure_ptr as *mut F;
+            let state = &mut *(closure_ptr as *mut IterationState<F>);
             let pixels_slice =
                 std::slice::from_raw_parts(pixels as *const u8, pixels_size as usize);
-            match (*closure)(mip, face, width, height, depth, pixels_slice) {
+            match (state.callback)(mip, face, width, height, depth, pixels_slice) {
                 Ok(_) => sys::ktx_error_code_e_KTX_SUCCESS,
-                Err(code) => code as u32,
+                Err(code) => {
+                    // Any error code stops the iteration; the actual error is returned out-of-band
+                    state.error = Some(code);
+                    sys::ktx_error_code_e_KTX_INVALID_OPERATION
+                }
             }
         }
 
@@ -290,9 +1055,16 @@ impl<'a> Texture<'a> {
 
             let vtbl = (*self.handle).vtbl;
             if let Some(iterate_levels_fn) = (*vtbl).IterateLevels {
-                let closure_ptr = &mut callback as *mut F as *mut std::ffi::c_void;
-                let err = (iterate_levels_fn)(self.handle, Some(c_iterator_fn::<F>), closure_ptr);
-                ktx_result(err, ())
+                let mut state = IterationState {
+                    callback,
+                    error: None,
+                };
+                let state_ptr = &mut state as *mut IterationState<F> as *mut std::ffi::c_void;
+                let err = (iterate_levels_fn)(self.handle, Some(c_iterator_fn::<F>), state_ptr);
+                match state.error {
+                    Some(error) => Err(error),
+                    None => ktx_result(err, ()),
+                }
             } else {
                 Err(KtxError::InvalidValue)
             }
@@ -305,8 +1077,9 @@ impl<'a> Texture<'a> {
     /// callback(miplevel: i32, face: i32, width: i32, height: i32, depth: i32, pixel_data: &mut [u8]) -> Result<(), KtxError>
     /// ```
     /// for each level/face. The image data passed to the callback is mutable.
+    /// If the callback returns an error, iteration stops and that same error is returned.
     /// Note that image data should already have been loaded (see [`Self::load_image_data()`]).
-    pub fn iterate_levels_mut<F>(&mut self, mut callback: F) -> Result<(), KtxError>
+    pub fn iterate_levels_mut<F>(&mut self, callback: F) -> Result<(), KtxError>
     where
         F: FnMut(i32, i32, i32, i32, i32, &mut [u8]) -> Result<(), KtxError>,
     {
@@ -323,12 +1096,16 @@ impl<'a> Texture<'a> {
         where
             F: FnMut(i32, i32, i32, i32, i32, &mut [u8]) -> Result<(), KtxError>,
         {
-            let closure = closure_ptr as *mut F;
+            let state = &mut *(closure_ptr as *mut IterationState<F>);
             let pixels_slice =
                 std::slice::from_raw_parts_mut(pixels as *mut u8, pixels_size as usize);
-            match (*closure)(mip, face, width, height, depth, pixels_slice) {
+            match (state.callback)(mip, face, width, height, depth, pixels_slice) {
                 Ok(_) => sys::ktx_error_code_e_KTX_SUCCESS,
-                Err(code) => code as u32,
+                Err(code) => {
+                    // Any error code stops the iteration; the actual error is returned out-of-band
+                    state.error = Some(code);
+                    sys::ktx_error_code_e_KTX_INVALID_OPERATION
+                }
             }
         }
 
@@ -341,39 +1118,751 @@ impl<'a> Texture<'a> {
 
             let vtbl = (*self.handle).vtbl;
             if let Some(iterate_levels_fn) = (*vtbl).IterateLevels {
-                let closure_ptr = &mut callback as *mut F as *mut std::ffi::c_void;
-                let err = (iterate_levels_fn)(self.handle, Some(c_iterator_fn::<F>), closure_ptr);
-                ktx_result(err, ())
+                let mut state = IterationState {
+                    callback,
+                    error: None,
+                };
+                let state_ptr = &mut state as *mut IterationState<F> as *mut std::ffi::c_void;
+                let err = (iterate_levels_fn)(self.handle, Some(c_iterator_fn::<F>), state_ptr);
+                match state.error {
+                    Some(error) => Err(error),
+                    None => ktx_result(err, ()),
+                }
             } else {
                 Err(KtxError::InvalidValue)
             }
         }
     }
 
-    /// If this [`Texture`] really is a KTX1, returns KTX1-specific functionalities for it.
-    pub fn ktx1<'b>(&'b mut self) -> Option<Ktx1<'b, 'a>> {
-        // SAFETY: Safe if `self.handle` is sane.
-        if unsafe { &*self.handle }.classId == sys::class_id_ktxTexture1_c {
-            Some(Ktx1 { texture: self })
-        } else {
-            None
+    /// Attempts to iterate all mip levels of the image, and all faces of cubemaps, reading their data
+    /// from the texture's source as it goes instead of from the (unloaded) internal buffer.  
+    /// This calls
+    /// ```rust,ignore
+    /// callback(miplevel: i32, face: i32, width: i32, height: i32, depth: i32, pixel_data: &mut [u8]) -> Result<(), KtxError>
+    /// ```
+    /// for each level/face, starting from the smallest mip level. ZStandard-supercompressed data is inflated first.  
+    /// `pixel_data` is a temporary buffer, only valid for the duration of the callback.
+    /// If the callback returns an error, iteration stops and that same error is returned.
+    ///
+    /// Image data must *not* have been loaded (i.e. create the texture without [`TextureCreateFlags::LOAD_IMAGE_DATA`]),
+    /// otherwise this fails with [`KtxError::InvalidOperation`]. Also see [`Ktx2::load_level`].
+    ///
+    /// This can only be done once per texture: the C library releases the source once iteration ends (even if it
+    /// fails), so any later call to this, [`Ktx2::load_level`] or [`Self::load_image_data`] fails with
+    /// [`KtxError::InvalidOperation`]. Unlike [`Ktx2::load_level`], this also inflates ZStandard-supercompressed levels.
+    pub fn iterate_load_level_faces<F>(&mut self, callback: F) -> Result<(), KtxError>
+    where
+        F: FnMut(i32, i32, i32, i32, i32, &mut [u8]) -> Result<(), KtxError>,
+    {
+        unsafe extern "C" fn c_iterator_fn<F>(
+            mip: i32,
+            face: i32,
+            width: i32,
+            height: i32,
+            depth: i32,
+            pixels_size: u64,
+            pixels: *mut std::ffi::c_void,
+            closure_ptr: *mut std::ffi::c_void,
+        ) -> sys::ktx_error_code_e
+        where
+            F: FnMut(i32, i32, i32, i32, i32, &mut [u8]) -> Result<(), KtxError>,
+        {
+            let state = &mut *(closure_ptr as *mut IterationState<F>);
+            let pixels_slice =
+                std::slice::from_raw_parts_mut(pixels as *mut u8, pixels_size as usize);
+            match (state.callback)(mip, face, width, height, depth, pixels_slice) {
+                Ok(_) => sys::ktx_error_code_e_KTX_SUCCESS,
+                Err(code) => {
+                    // Any error code stops the iteration; the actual error is returned out-of-band
+                    state.error = Some(code);
+                    sys::ktx_error_code_e_KTX_INVALID_OPERATION
+                }
+            }
         }
-    }
 
-    /// If this [`Texture`] really is a KTX2, returns KTX2-specific functionalities for it.
-    pub fn ktx2<'b>(&'b mut self) -> Option<Ktx2<'b, 'a>> {
         // SAFETY: Safe if `self.handle` is sane.
-        if unsafe { &*self.handle }.classId == sys::class_id_ktxTexture2_c {
-            Some(Ktx2 { texture: self })
-        } else {
+        unsafe {
+            if self.source_consumed || !(*self.handle).pData.is_null() {
+                // Data was already loaded, or the source is gone
+                return Err(KtxError::InvalidOperation);
+            }
+
+            let vtbl = (*self.handle).vtbl;
+            if let Some(iterate_load_fn) = (*vtbl).IterateLoadLevelFaces {
+                self.source_consumed = true;
+                let mut state = IterationState {
+                    callback,
+                    error: None,
+                };
+                let state_ptr = &mut state as *mut IterationState<F> as *mut std::ffi::c_void;
+                let err = (iterate_load_fn)(self.handle, Some(c_iterator_fn::<F>), state_ptr);
+                match state.error {
+                    Some(error) => Err(error),
+                    None => ktx_result(err, ()),
+                }
+            } else {
+                Err(KtxError::InvalidValue)
+            }
+        }
+    }
+
+    /// Returns a lazy iterator over all images in the texture; see [`LevelImage`].
+    ///
+    /// Images are yielded in the same order they are stored in: by mip level, then by array layer,
+    /// then by cubemap face.  
+    /// Unlike [`Self::iterate_levels`], this does not go through the C library's callback.
+    /// Note that image data should already have been loaded (see [`Self::load_image_data()`]);
+    /// if it wasn't, the iterator yields nothing.
+    pub fn levels(&self) -> LevelImages<'_, 'a> {
+        LevelImages {
+            texture: self,
+            level: 0,
+            layer: 0,
+            face: 0,
+        }
+    }
+
+    /// Attempts to generate the full mip chain of this texture from its base level, using a box filter.
+    ///
+    /// This replaces the texture's images with a newly-allocated chain of mip levels (down to 1x1x1),
+    /// updating [`Self::num_levels`]; metadata and orientation are preserved.
+    /// Image data is loaded first if needed (see [`Self::load_image_data()`]).  
+    /// Only uncompressed formats with 8-bit unsigned channels are supported;
+    /// [`KtxError::UnsupportedFeature`] is returned for block-compressed (or any other) formats.
+    pub fn generate_mipmaps(&mut self) -> Result<(), KtxError> {
+        if self.is_compressed() || !self.has_unsigned_byte_channels() {
+            return Err(KtxError::UnsupportedFeature);
+        }
+        // SAFETY: Safe if `self.handle` is sane.
+        if unsafe { (*self.handle).pData.is_null() } {
+            self.load_image_data()?;
+        }
+
+        let max_dim = self
+            .base_width()
+            .max(self.base_height())
+            .max(self.base_depth());
+        let mut mipmapped = self.create_similar(usize::BITS - max_dim.leading_zeros(), false)?;
+
+        let texel_size = self.element_size();
+        for layer in 0..self.num_layers() as u32 {
+            for face in 0..self.num_faces() as u32 {
+                let src_range = self.level_image_range(0, layer, face)?;
+                let dst_range = mipmapped.level_image_range(0, layer, face)?;
+                mipmapped.data_mut()[dst_range].copy_from_slice(&self.data()[src_range]);
+
+                for level in 1..mipmapped.num_levels() as u32 {
+                    let src_range = mipmapped.level_image_range(level - 1, layer, face)?;
+                    let dst_range = mipmapped.level_image_range(level, layer, face)?;
+                    let src = mipmapped.data()[src_range].to_vec();
+                    let src_pitch = mipmapped.row_pitch(level - 1);
+                    let dst_pitch = mipmapped.row_pitch(level);
+                    let src_dims = mipmapped.extent_at_level(level - 1);
+                    let dst_dims = mipmapped.extent_at_level(level);
+                    box_filter(
+                        &src,
+                        src_dims,
+                        src_pitch,
+                        &mut mipmapped.data_mut()[dst_range],
+                        dst_dims,
+                        dst_pitch,
+                        texel_size,
+                    );
+                }
+            }
+        }
+
+        // SAFETY: Safe if both handles are sane. Metadata is moved over to the new handle,
+        // then the old handle is destroyed along with `mipmapped`.
+        unsafe {
+            let (old, new) = (&mut *self.handle, &mut *mipmapped.handle);
+            std::mem::swap(&mut old.kvDataHead, &mut new.kvDataHead);
+            std::mem::swap(&mut old.kvDataLen, &mut new.kvDataLen);
+            std::mem::swap(&mut old.kvData, &mut new.kvData);
+            new.orientation = old.orientation;
+        }
+        std::mem::swap(&mut self.handle, &mut mipmapped.handle);
+        Ok(())
+    }
+
+    /// Attempts to flip all images of this texture vertically, i.e. to reverse the order of their rows;
+    /// e.g. to convert between top-left and bottom-left origins.
+    ///
+    /// The Y orientation (see [`Self::orientation`]) is flipped accordingly, and its metadata updated.
+    /// Image data is loaded first if needed (see [`Self::load_image_data()`]). 1D textures are left untouched.  
+    /// Fails with [`KtxError::UnsupportedFeature`] for block-compressed or supercompressed textures.
+    pub fn flip_y(&mut self) -> Result<(), KtxError> {
+        // SAFETY: Safe if `self.handle` is sane; the class ID tells which texture struct it really is.
+        if self.is_compressed()
+            || unsafe {
+                (*self.handle).classId == sys::class_id_ktxTexture2_c
+                    && (*(self.handle as *mut sys::ktxTexture2)).supercompressionScheme
+                        != sys::ktxSupercmpScheme_KTX_SS_NONE
+            }
+        {
+            return Err(KtxError::UnsupportedFeature);
+        }
+        if self.num_dimensions() < 2 {
+            return Ok(());
+        }
+        // SAFETY: Safe if `self.handle` is sane.
+        if unsafe { (*self.handle).pData.is_null() } {
+            self.load_image_data()?;
+        }
+
+        for level in 0..self.num_levels() as u32 {
+            let extent = self.extent_at_level(level);
+            let num_slices = if self.is_cubemap() {
+                self.num_faces()
+            } else {
+                extent.depth
+            };
+            for layer in 0..self.num_layers() as u32 {
+                for slice in 0..num_slices as u32 {
+                    let range = self.image_data_range(level, layer, slice)?;
+                    let image = &mut self.data_mut()[range];
+                    // Images are made of whole rows (padded for KTX1, unpadded for KTX2)
+                    let row_pitch = image.len() / extent.height;
+                    for row in 0..extent.height / 2 {
+                        let (top, bottom) =
+                            image.split_at_mut((extent.height - 1 - row) * row_pitch);
+                        top[row * row_pitch..(row + 1) * row_pitch]
+                            .swap_with_slice(&mut bottom[..row_pitch]);
+                    }
+                }
+            }
+        }
+
+        let mut orientation = self.orientation();
+        orientation.y = match orientation.y {
+            OrientationY::Up => OrientationY::Down,
+            OrientationY::Down => OrientationY::Up,
+        };
+        self.set_orientation(orientation)
+    }
+
+    /// Attempts to remap the channels of all images of this texture in-place; e.g. `[B, G, R, A]` converts
+    /// BGRA to RGBA, and `[R, R, R, One]` broadcasts red to an opaque gray.
+    ///
+    /// Only the first [`Self::num_components`] entries of `mapping` are used.
+    /// Image data is loaded first if needed (see [`Self::load_image_data()`]).  
+    /// Fails with [`KtxError::InvalidValue`] if `mapping` refers to a channel the texture does not have,
+    /// and with [`KtxError::UnsupportedFeature`] for block-compressed, supercompressed or packed formats
+    /// (e.g. `R5G6B5`), whose channels are not made of whole bytes.
+    pub fn swizzle(&mut self, mapping: [Channel; 4]) -> Result<(), KtxError> {
+        // SAFETY: Safe if `self.handle` is sane; the class ID tells which texture struct it really is.
+        if self.is_compressed()
+            || unsafe {
+                (*self.handle).classId == sys::class_id_ktxTexture2_c
+                    && (*(self.handle as *mut sys::ktxTexture2)).supercompressionScheme
+                        != sys::ktxSupercmpScheme_KTX_SS_NONE
+            }
+        {
+            return Err(KtxError::UnsupportedFeature);
+        }
+        let num_components = self.num_components()? as usize;
+        let (component_size, one) = self.component_layout(num_components)?;
+        let mapping = &mapping[..num_components];
+        let sources: Vec<Option<usize>> = mapping
+            .iter()
+            .map(|channel| match channel {
+                Channel::R => Some(0),
+                Channel::G => Some(1),
+                Channel::B => Some(2),
+                Channel::A => Some(3),
+                Channel::Zero | Channel::One => None,
+            })
+            .collect();
+        if sources
+            .iter()
+            .flatten()
+            .any(|&index| index >= num_components)
+        {
+            return Err(KtxError::InvalidValue);
+        }
+
+        // SAFETY: Safe if `self.handle` is sane.
+        if unsafe { (*self.handle).pData.is_null() } {
+            self.load_image_data()?;
+        }
+        let element_size = component_size * num_components;
+        // Cubemap arrays are iterated one whole level at a time (all layers and faces), other textures one face at a time
+        let images_per_callback = if self.is_cubemap() && self.is_array() {
+            self.num_layers() * self.num_faces()
+        } else {
+            self.num_layers()
+        };
+        let mut element_copy = vec![0; element_size];
+        self.iterate_levels_mut(|_mip, _face, width, height, depth, pixel_data| {
+            // Each level/face is made of whole rows (padded for KTX1, unpadded for KTX2)
+            let num_rows = height as usize * depth as usize * images_per_callback;
+            let row_pitch = pixel_data.len() / num_rows;
+            let row_len = width as usize * element_size;
+            for row in pixel_data.chunks_exact_mut(row_pitch) {
+                for element in row[..row_len].chunks_exact_mut(element_size) {
+                    element_copy.copy_from_slice(element);
+                    let components = element.chunks_exact_mut(component_size);
+                    for ((component, channel), source) in components.zip(mapping).zip(&sources) {
+                        match (channel, source) {
+                            (_, Some(index)) => component.copy_from_slice(
+                                &element_copy[index * component_size..][..component_size],
+                            ),
+                            (Channel::One, None) => component.copy_from_slice(&one),
+                            _ => component.iter_mut().for_each(|byte| *byte = 0),
+                        }
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Returns the size in bytes of each of the `num_components` components of an element, and the bytes of a 1.
+    ///
+    /// Fails with [`KtxError::UnsupportedFeature`] if components are not all made of the same whole number of bytes.
+    fn component_layout(&self, num_components: usize) -> Result<(usize, Vec<u8>), KtxError> {
+        let (size, is_float, upper) = match self.class() {
+            TextureClass::Ktx1 => {
+                // SAFETY: Safe if `self.handle` is sane; the class ID tells which texture struct it really is.
+                let gl_type = unsafe { (*(self.handle as *mut sys::ktxTexture1)).glType };
+                // Assuming normalized integers, 1 is their maximum value
+                match GlType::from(gl_type) {
+                    GlType::UnsignedByte => (1, false, u8::MAX as u32),
+                    GlType::Byte => (1, false, i8::MAX as u32),
+                    GlType::UnsignedShort => (2, false, u16::MAX as u32),
+                    GlType::Short => (2, false, i16::MAX as u32),
+                    GlType::UnsignedInt => (4, false, u32::MAX),
+                    GlType::Int => (4, false, i32::MAX as u32),
+                    GlType::HalfFloat => (2, true, 0),
+                    GlType::Float => (4, true, 0),
+                    _ => return Err(KtxError::UnsupportedFeature),
+                }
+            }
+            TextureClass::Ktx2 => {
+                // SAFETY: Safe if `self.handle` is sane; the class ID tells which texture struct it really is.
+                let dfd = unsafe { dfd_slice(self.handle as *mut sys::ktxTexture2) };
+                let header = DfdHeader::parse(dfd).map_err(|_| KtxError::UnsupportedFeature)?;
+                let first = header.samples.first().ok_or(KtxError::UnsupportedFeature)?;
+                let bit_length = first.bit_length;
+                let is_plain = header.samples.len() == num_components
+                    && header.samples.iter().enumerate().all(|(index, sample)| {
+                        sample.bit_length == bit_length
+                            && sample.bit_offset == index as u32 * bit_length
+                            && sample.is_float == first.is_float
+                    });
+                if !is_plain || !matches!(bit_length, 8 | 16 | 32) {
+                    return Err(KtxError::UnsupportedFeature);
+                }
+                // The upper value of a sample is what 1 maps to (for floats, it is always a 32-bit float)
+                ((bit_length / 8) as usize, first.is_float, first.upper)
+            }
+        };
+        let one = match (size, is_float) {
+            (2, true) => 0x3C00u16.to_ne_bytes().to_vec(),
+            (4, true) => 1.0f32.to_ne_bytes().to_vec(),
+            (1, _) => vec![upper as u8],
+            (2, _) => (upper as u16).to_ne_bytes().to_vec(),
+            _ => upper.to_ne_bytes().to_vec(),
+        };
+        Ok((size, one))
+    }
+
+    /// Attempts to create a deep copy of this texture, that owns its own copy of the image data and metadata.
+    ///
+    /// Image data is loaded first if needed (see [`Self::load_image_data()`]).  
+    /// Fails with [`KtxError::UnsupportedFeature`] for supercompressed textures, be it with BasisLZ or ZStandard
+    /// (e.g. after [`Ktx2::deflate_zstd`]), as their data does not fit the uncompressed layout of the clone.
+    pub fn try_clone(&self) -> Result<Texture<'static>, KtxError> {
+        // SAFETY: Safe if `self.handle` is sane.
+        if unsafe { (*self.handle).pData.is_null() } {
+            self.load_image_data()?;
+        }
+        // SAFETY: Safe if `self.handle` is sane; the class ID tells which texture struct it really is.
+        if unsafe {
+            (*self.handle).classId == sys::class_id_ktxTexture2_c
+                && (*(self.handle as *mut sys::ktxTexture2)).supercompressionScheme
+                    != sys::ktxSupercmpScheme_KTX_SS_NONE
+        } {
+            return Err(KtxError::UnsupportedFeature);
+        }
+
+        // SAFETY: Safe if `self.handle` is sane.
+        let generate_mipmaps = unsafe { (*self.handle).generateMipmaps };
+        let mut clone = self.create_similar(self.num_levels() as u32, generate_mipmaps)?;
+        if clone.data_size() != self.data_size() {
+            return Err(KtxError::InvalidOperation);
+        }
+        clone.data_mut().copy_from_slice(self.data());
+
+        // SAFETY: Safe if both handles are sane. The clone's (empty) hash list is replaced with a copy of ours.
+        unsafe {
+            let (orig, new) = (&*self.handle, &mut *clone.handle);
+            sys::ktxHashList_Destruct(&mut new.kvDataHead);
+            sys::ktxHashList_ConstructCopy(&mut new.kvDataHead, orig.kvDataHead);
+            new.orientation = orig.orientation;
+        }
+        Ok(clone)
+    }
+
+    /// Attempts to create a new texture with storage, of the same class, format and dimensions as `self`,
+    /// but with the given number of mip levels.
+    fn create_similar(
+        &self,
+        num_levels: u32,
+        generate_mipmaps: bool,
+    ) -> Result<Texture<'static>, KtxError> {
+        let common = CommonCreateInfo {
+            create_storage: CreateStorage::AllocStorage,
+            base_width: self.base_width() as u32,
+            base_height: self.base_height() as u32,
+            base_depth: self.base_depth() as u32,
+            num_dimensions: self.num_dimensions() as u32,
+            num_levels,
+            num_layers: self.num_layers() as u32,
+            num_faces: self.num_faces() as u32,
+            is_array: self.is_array(),
+            generate_mipmaps,
+        };
+        // SAFETY: Safe if `self.handle` is sane; the class ID tells which texture struct it really is.
+        unsafe {
+            if (*self.handle).classId == sys::class_id_ktxTexture1_c {
+                Texture::new(Ktx1CreateInfo {
+                    gl_internal_format: (*(self.handle as *mut sys::ktxTexture1)).glInternalformat,
+                    common,
+                })
+            } else {
+                let ktx2 = &*(self.handle as *mut sys::ktxTexture2);
+                // The first word of the DFD is its total size in bytes
+                let dfd = if ktx2.pDfd.is_null() {
+                    None
+                } else {
+                    let dfd_len = *ktx2.pDfd as usize / std::mem::size_of::<u32>();
+                    Some(std::slice::from_raw_parts(ktx2.pDfd, dfd_len).to_vec())
+                };
+                Texture::new(Ktx2CreateInfo {
+                    vk_format: ktx2.vkFormat.into(),
+                    dfd,
+                    common,
+                })
+            }
+        }
+        .map_err(|err| err.code())
+    }
+
+    /// Returns `true` if the texture's format is uncompressed and only has 8-bit unsigned channels.
+    fn has_unsigned_byte_channels(&self) -> bool {
+        // SAFETY: Safe if `self.handle` is sane; the class ID tells which texture struct it really is.
+        unsafe {
+            match (*self.handle).classId {
+                sys::class_id_ktxTexture1_c => {
+                    GlType::from((*(self.handle as *mut sys::ktxTexture1)).glType)
+                        == GlType::UnsignedByte
+                }
+                sys::class_id_ktxTexture2_c => matches!(
+                    VkFormat::from((*(self.handle as *mut sys::ktxTexture2)).vkFormat),
+                    VkFormat::R8Unorm
+                        | VkFormat::R8Uint
+                        | VkFormat::R8Srgb
+                        | VkFormat::R8G8Unorm
+                        | VkFormat::R8G8Uint
+                        | VkFormat::R8G8Srgb
+                        | VkFormat::R8G8B8Unorm
+                        | VkFormat::R8G8B8Uint
+                        | VkFormat::R8G8B8Srgb
+                        | VkFormat::B8G8R8Unorm
+                        | VkFormat::B8G8R8Srgb
+                        | VkFormat::R8G8B8A8Unorm
+                        | VkFormat::R8G8B8A8Uint
+                        | VkFormat::R8G8B8A8Srgb
+                        | VkFormat::B8G8R8A8Unorm
+                        | VkFormat::B8G8R8A8Srgb
+                ),
+                _ => false,
+            }
+        }
+    }
+
+    /// Returns the range into [`Self::data`] of the image at the given mip level, array layer and face,
+    /// including all of its depth slices.
+    fn level_image_range(
+        &self,
+        level: u32,
+        layer: u32,
+        face: u32,
+    ) -> Result<std::ops::Range<usize>, KtxError> {
+        let offset = self.get_image_offset(level, layer, face)?;
+        let size = self.get_image_size(level)? * self.extent_at_level(level).depth;
+        Ok(offset..offset + size)
+    }
+
+    /// Returns a summary of this texture's properties, which can be printed (like `ktxinfo` does).
+    pub fn info(&self) -> TextureInfo {
+        // SAFETY: Safe if `self.handle` is sane; the class ID tells which texture struct it really is.
+        let (class, format, supercompression_scheme) = unsafe {
+            match (*self.handle).classId {
+                sys::class_id_ktxTexture1_c => {
+                    let ktx1 = &*(self.handle as *mut sys::ktxTexture1);
+                    let format = GlInternalFormat::from(ktx1.glInternalformat);
+                    (
+                        "KTX1",
+                        format!("{:?}", format),
+                        SuperCompressionScheme::None,
+                    )
+                }
+                sys::class_id_ktxTexture2_c => {
+                    let ktx2 = &*(self.handle as *mut sys::ktxTexture2);
+                    let format = VkFormat::from(ktx2.vkFormat);
+                    (
+                        "KTX2",
+                        format!("{:?}", format),
+                        ktx2.supercompressionScheme.into(),
+                    )
+                }
+                _ => ("unknown", String::new(), SuperCompressionScheme::None),
+            }
+        };
+        TextureInfo {
+            class,
+            dimensions: self.dimensions(),
+            num_dimensions: self.num_dimensions(),
+            format,
+            num_levels: self.num_levels(),
+            num_layers: self.num_layers(),
+            num_faces: self.num_faces(),
+            is_array: self.is_array(),
+            is_cubemap: self.is_cubemap(),
+            is_compressed: self.is_compressed(),
+            supercompression_scheme,
+            data_size: self.data_size(),
+        }
+    }
+
+    /// Returns the class (i.e. KTX version) of this texture.  
+    /// Unlike [`Self::ktx1`] and [`Self::ktx2`], this does not require a mutable borrow.
+    pub fn class(&self) -> TextureClass {
+        // SAFETY: Safe if `self.handle` is sane.
+        let class_id = unsafe { (*self.handle).classId };
+        TextureClass::try_from(class_id).expect("a ktxTexture1 or ktxTexture2")
+    }
+
+    /// Returns the number of components (channels) of this texture, regardless of its class.
+    ///
+    /// - For KTX2: see [`Ktx2::num_components`].
+    /// - For KTX1: derived from the OpenGL base internal format (which is also set for compressed textures).
+    ///
+    /// Returns [`KtxError::UnsupportedFeature`] for KTX1 formats that cannot be classified.
+    pub fn num_components(&self) -> Result<u32, KtxError> {
+        match self.class() {
+            TextureClass::Ktx1 => {
+                // SAFETY: Safe if `self.handle` is sane; the class ID tells which texture struct it really is.
+                let base_format =
+                    unsafe { (*(self.handle as *mut sys::ktxTexture1)).glBaseInternalformat };
+                GlFormat::from(base_format)
+                    .num_components()
+                    .ok_or(KtxError::UnsupportedFeature)
+            }
+            // SAFETY: Safe if `self.handle` is sane; the class ID tells which texture struct it really is.
+            TextureClass::Ktx2 => Ok(unsafe {
+                sys::ktxTexture2_GetNumComponents(self.handle as *mut sys::ktxTexture2)
+            }),
+        }
+    }
+
+    /// If this [`Texture`] really is a KTX1, returns KTX1-specific functionalities for it.
+    pub fn ktx1<'b>(&'b mut self) -> Option<Ktx1<'b, 'a>> {
+        if self.class() == TextureClass::Ktx1 {
+            Some(Ktx1 { texture: self })
+        } else {
             None
         }
     }
+
+    /// If this [`Texture`] really is a KTX2, returns KTX2-specific functionalities for it.
+    pub fn ktx2<'b>(&'b mut self) -> Option<Ktx2<'b, 'a>> {
+        if self.class() == TextureClass::Ktx2 {
+            Some(Ktx2 { texture: self })
+        } else {
+            None
+        }
+    }
+}
+
+/// The state passed to the C callbacks of [`Texture::iterate_levels`] and [`Texture::iterate_levels_mut`].
+struct IterationState<F> {
+    callback: F,
+    /// The error returned by `callback`, if any.
+    error: Option<KtxError>,
+}
+
+/// Downsamples the `src_dims` image in `src` to the `dst_dims` image in `dst` with a box filter.  
+/// Rows are `src_pitch` and `dst_pitch` bytes long respectively;
+/// each byte of a `texel_size`-byte texel is filtered as a separate channel.
+fn box_filter(
+    src: &[u8],
+    src_dims: Extent3d,
+    src_pitch: usize,
+    dst: &mut [u8],
+    dst_dims: Extent3d,
+    dst_pitch: usize,
+    texel_size: usize,
+) {
+    let src_slice_size = src_pitch * src_dims.height;
+    let dst_slice_size = dst_pitch * dst_dims.height;
+    for z in 0..dst_dims.depth {
+        for y in 0..dst_dims.height {
+            for x in 0..dst_dims.width {
+                for c in 0..texel_size {
+                    let (mut sum, mut count) = (0u32, 0u32);
+                    for sz in (z * 2)..(z * 2 + 2).min(src_dims.depth) {
+                        for sy in (y * 2)..(y * 2 + 2).min(src_dims.height) {
+                            for sx in (x * 2)..(x * 2 + 2).min(src_dims.width) {
+                                let i = sz * src_slice_size + sy * src_pitch + sx * texel_size + c;
+                                sum += src[i] as u32;
+                                count += 1;
+                            }
+                        }
+                    }
+                    let i = z * dst_slice_size + y * dst_pitch + x * texel_size + c;
+                    dst[i] = ((sum + count / 2) / count) as u8;
+                }
+            }
+        }
+    }
+}
+
+/// Parses a swizzle string (e.g. `"rgb1"`) to a Rust swizzle.
+fn parse_swizzle(swizzle: &str) -> Result<[char; 4], KtxError> {
+    let mut chars = ['\0'; 4];
+    let mut count = 0;
+    for ch in swizzle.chars() {
+        if count >= chars.len() || !"rgba01".contains(ch) {
+            return Err(KtxError::InvalidValue);
+        }
+        chars[count] = ch;
+        count += 1;
+    }
+    if count == chars.len() {
+        Ok(chars)
+    } else {
+        Err(KtxError::InvalidValue)
+    }
+}
+
+/// Converts a Rust swizzle (e.g. `['r', 'g', 'b', '1']`) to a C one.
+fn c_swizzle(swizzle: &[char; 4]) -> [std::os::raw::c_char; 4] {
+    let mut c_swizzle: [std::os::raw::c_char; 4] = [0, 0, 0, 0];
+    for (ch, c_ch) in swizzle.iter().zip(c_swizzle.iter_mut()) {
+        *c_ch = *ch as _;
+    }
+    c_swizzle
+}
+
+/// Reads the (not supercompressed) data of `level` from the KTX2 file behind `stream`, via its level index.
+/// The position of `stream` is restored afterwards, so that libKTX can keep using it.
+///
+/// **SAFETY**: `stream` must point to the active source stream of a KTX2 texture.
+unsafe fn read_ktx2_level(stream: *mut sys::ktxStream, level: u32) -> Result<Vec<u8>, KtxError> {
+    let (getpos_fn, setpos_fn) = match ((*stream).getpos, (*stream).setpos) {
+        (Some(getpos_fn), Some(setpos_fn)) => (getpos_fn, setpos_fn),
+        _ => return Err(KtxError::InvalidOperation),
+    };
+    let mut old_pos: sys::ktx_off_t = 0;
+    ktx_result((getpos_fn)(stream, &mut old_pos), ())?;
+    let result = read_ktx2_level_at(stream, level);
+    ktx_result((setpos_fn)(stream, old_pos), ())?;
+    result
+}
+
+/// See [`read_ktx2_level`]; this leaves `stream` wherever the reads left it.
+unsafe fn read_ktx2_level_at(stream: *mut sys::ktxStream, level: u32) -> Result<Vec<u8>, KtxError> {
+    // The level index follows the 12-byte identifier, the 36-byte header and the 32-byte index;
+    // each entry is `{ byteOffset, byteLength, uncompressedByteLength }`, all `u64`s
+    const LEVEL_INDEX_OFFSET: usize = 80;
+    const LEVEL_INDEX_ENTRY_SIZE: usize = 3 * std::mem::size_of::<u64>();
+
+    let (read_fn, setpos_fn) = match ((*stream).read, (*stream).setpos) {
+        (Some(read_fn), Some(setpos_fn)) => (read_fn, setpos_fn),
+        _ => return Err(KtxError::InvalidOperation),
+    };
+    let seek = |offset: u64| {
+        let offset = sys::ktx_off_t::try_from(offset).map_err(|_| KtxError::FileOverflow)?;
+        ktx_result((setpos_fn)(stream, offset), ())
+    };
+
+    let mut entry = [0u8; LEVEL_INDEX_ENTRY_SIZE];
+    seek((LEVEL_INDEX_OFFSET + level as usize * LEVEL_INDEX_ENTRY_SIZE) as u64)?;
+    ktx_result(
+        (read_fn)(
+            stream,
+            entry.as_mut_ptr() as *mut std::ffi::c_void,
+            entry.len(),
+        ),
+        (),
+    )?;
+    let field = |index: usize| {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&entry[index * 8..(index + 1) * 8]);
+        u64::from_le_bytes(bytes)
+    };
+    let (byte_offset, byte_length, uncompressed_byte_length) = (field(0), field(1), field(2));
+    if byte_length != uncompressed_byte_length {
+        return Err(KtxError::FileDataError);
+    }
+
+    let mut data = vec![0u8; usize::try_from(byte_length).map_err(|_| KtxError::FileOverflow)?];
+    seek(byte_offset)?;
+    ktx_result(
+        (read_fn)(
+            stream,
+            data.as_mut_ptr() as *mut std::ffi::c_void,
+            data.len(),
+        ),
+        (),
+    )?;
+    Ok(data)
+}
+
+/// Returns a view on the DFD of the KTX2 at `handle` (whose first word is its total size in bytes),
+/// or an empty slice if it has none.
+///
+/// **SAFETY**: `handle` must point to a valid [`sys::ktxTexture2`] that outlives `'a`.
+unsafe fn dfd_slice<'a>(handle: *mut sys::ktxTexture2) -> &'a [u32] {
+    let dfd = (*handle).pDfd;
+    if dfd.is_null() {
+        return &[];
+    }
+    std::slice::from_raw_parts(dfd, *dfd as usize / std::mem::size_of::<u32>())
+}
+
+/// Like [`dfd_slice`], but mutable.
+///
+/// **SAFETY**: `handle` must point to a valid [`sys::ktxTexture2`] that outlives `'a`.
+unsafe fn dfd_slice_mut<'a>(handle: *mut sys::ktxTexture2) -> &'a mut [u32] {
+    let dfd = (*handle).pDfd;
+    if dfd.is_null() {
+        return &mut [];
+    }
+    std::slice::from_raw_parts_mut(dfd, *dfd as usize / std::mem::size_of::<u32>())
+}
+
+/// Finds the entry for `key` in the hash list at `head`, and deletes (+ frees) it.
+///
+/// **SAFETY**: `head` must point to a valid [`sys::ktxHashList`].
+unsafe fn delete_kv_entry(head: *mut sys::ktxHashList, key: &CStr) -> sys::ktx_error_code_e {
+    let mut entry: *mut sys::ktxHashListEntry = std::ptr::null_mut();
+    let err = sys::ktxHashList_FindEntry(head, key.as_ptr(), &mut entry);
+    if err != sys::ktx_error_code_e_KTX_SUCCESS {
+        return err;
+    }
+    sys::ktxHashList_DeleteEntry(head, entry)
 }
 
 impl<'a> Drop for Texture<'a> {
     fn drop(&mut self) {
         unsafe {
+            if !self.raw_kv_list.get().is_null() {
+                sys::ktxHashList_Destruct(self.raw_kv_list.as_ptr());
+            }
             let vtbl = (*self.handle).vtbl;
             if let Some(destroy_fn) = (*vtbl).Destroy {
                 (destroy_fn)(self.handle as *mut sys::ktxTexture);
@@ -382,6 +1871,260 @@ impl<'a> Drop for Texture<'a> {
     }
 }
 
+impl<'a> fmt::Debug for Texture<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // SAFETY: Safe if `self.handle` is sane.
+        let class = match unsafe { (*self.handle).classId } {
+            sys::class_id_ktxTexture1_c => "KTX1",
+            sys::class_id_ktxTexture2_c => "KTX2",
+            _ => "unknown",
+        };
+        f.debug_struct("Texture")
+            .field("class", &class)
+            .field("dimensions", &self.dimensions())
+            .field("num_dimensions", &self.num_dimensions())
+            .field("num_levels", &self.num_levels())
+            .field("num_layers", &self.num_layers())
+            .field("num_faces", &self.num_faces())
+            .field("is_array", &self.is_array())
+            .field("is_cubemap", &self.is_cubemap())
+            .field("is_compressed", &self.is_compressed())
+            .field("data_size", &self.data_size())
+            .finish()
+    }
+}
+
+/// The width, height and depth (in texels) of a [`Texture`] or of one of its levels.
+///
+/// See [`Texture::dimensions`] and [`Texture::extent_at_level`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Extent3d {
+    pub width: usize,
+    pub height: usize,
+    pub depth: usize,
+}
+
+/// Size information about the format of a KTX2 texture, as returned by [`Ktx2::format_info`].
+///
+/// Uncompressed formats have 1x1-texel blocks, so `bytes_per_block` is their size per texel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FormatInfo {
+    /// The size (in bytes) of a block of texels.
+    pub bytes_per_block: usize,
+    /// The width (in texels) of a block.
+    pub block_width: u32,
+    /// The height (in texels) of a block.
+    pub block_height: u32,
+    /// Whether the format is block-compressed.
+    pub is_compressed: bool,
+}
+
+/// A summary of a [`Texture`]'s properties, as returned by [`Texture::info`].
+///
+/// This [`Display`](fmt::Display)s as a table, with one property per line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextureInfo {
+    /// Either `"KTX1"` or `"KTX2"`.
+    pub class: &'static str,
+    pub dimensions: Extent3d,
+    pub num_dimensions: usize,
+    /// The name of the texture's [`GlInternalFormat`] (for KTX1) or [`VkFormat`] (for KTX2).
+    pub format: String,
+    pub num_levels: usize,
+    pub num_layers: usize,
+    pub num_faces: usize,
+    pub is_array: bool,
+    pub is_cubemap: bool,
+    pub is_compressed: bool,
+    /// Always [`SuperCompressionScheme::None`] for KTX1.
+    pub supercompression_scheme: SuperCompressionScheme,
+    pub data_size: usize,
+}
+
+impl fmt::Display for TextureInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Extent3d {
+            width,
+            height,
+            depth,
+        } = self.dimensions;
+        writeln!(f, "{:<18}{}", "class:", self.class)?;
+        writeln!(
+            f,
+            "{:<18}{}x{}x{} ({}D)",
+            "dimensions:", width, height, depth, self.num_dimensions
+        )?;
+        writeln!(f, "{:<18}{}", "format:", self.format)?;
+        writeln!(f, "{:<18}{}", "levels:", self.num_levels)?;
+        writeln!(f, "{:<18}{}", "layers:", self.num_layers)?;
+        writeln!(f, "{:<18}{}", "faces:", self.num_faces)?;
+        writeln!(f, "{:<18}{}", "array:", self.is_array)?;
+        writeln!(f, "{:<18}{}", "cubemap:", self.is_cubemap)?;
+        writeln!(f, "{:<18}{}", "compressed:", self.is_compressed)?;
+        writeln!(
+            f,
+            "{:<18}{}",
+            "supercompression:", self.supercompression_scheme
+        )?;
+        write!(f, "{:<18}{} bytes", "data size:", self.data_size)
+    }
+}
+
+/// An image in a [`Texture`], as yielded by [`Texture::levels`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LevelImage<'a> {
+    /// The mip level of the image.
+    pub level: u32,
+    /// The array layer of the image.
+    pub layer: u32,
+    /// The cubemap face of the image (always 0 for non-cubemaps).
+    pub face: u32,
+    /// The width of the image, in pixels.
+    pub width: usize,
+    /// The height of the image, in pixels.
+    pub height: usize,
+    /// The depth of the image, in pixels.
+    pub depth: usize,
+    /// The image data. For 3D textures, this contains all `depth` slices.
+    pub data: &'a [u8],
+}
+
+/// An iterator over the [`LevelImage`]s of a [`Texture`].
+///
+/// See [`Texture::levels`].
+pub struct LevelImages<'a, 'b: 'a> {
+    texture: &'a Texture<'b>,
+    level: u32,
+    layer: u32,
+    face: u32,
+}
+
+impl<'a, 'b: 'a> Iterator for LevelImages<'a, 'b> {
+    type Item = LevelImage<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let texture = self.texture;
+        // SAFETY: Safe if `texture.handle` is sane.
+        if unsafe { (*texture.handle).pData.is_null() }
+            || self.level as usize >= texture.num_levels()
+        {
+            return None;
+        }
+        let (level, layer, face) = (self.level, self.layer, self.face);
+
+        self.face += 1;
+        if self.face as usize >= texture.num_faces() {
+            self.face = 0;
+            self.layer += 1;
+            if self.layer as usize >= texture.num_layers() {
+                self.layer = 0;
+                self.level += 1;
+            }
+        }
+
+        let Extent3d {
+            width,
+            height,
+            depth,
+        } = texture.extent_at_level(level);
+        let range = texture.level_image_range(level, layer, face).ok()?;
+        let data = texture.data().get(range)?;
+        Some(LevelImage {
+            level,
+            layer,
+            face,
+            width,
+            height,
+            depth,
+            data,
+        })
+    }
+}
+
+/// The result of uploading a [`Texture`] to OpenGL.
+///
+/// See [`Ktx1::gl_upload`].
+#[cfg(feature = "gl")]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct GlUploadResult {
+    /// The name of the OpenGL texture object the image was uploaded to.
+    pub texture: u32,
+    /// The OpenGL texture target the texture object was bound to (e.g. `GL_TEXTURE_2D`).
+    pub target: u32,
+    /// The OpenGL error (if any) raised while uploading the texture.
+    pub gl_error: u32,
+}
+
+/// A [`Texture`] that does not depend on any external [`TextureSource`], and can hence be sent across threads.
+///
+/// See [`Texture::into_owned`]. This dereferences to the wrapped [`Texture`] for reading;
+/// mutable access only goes through the forwarding methods below, as a `&mut Texture` would allow
+/// replacing the wrapped texture (and its source) with one that is not [`Send`].
+#[derive(Debug)]
+pub struct OwnedTexture {
+    texture: Texture<'static>,
+}
+
+// SAFETY: The wrapped `ktxTexture` is only ever accessed through `self`, and its image data is owned by it.
+// The texture's source is an `OwnedMemorySource`, whose buffer moves (and is dropped) along with `self`;
+// no `&mut Texture` is ever handed out, so the texture (and its source) can't be replaced.
+unsafe impl Send for OwnedTexture {}
+
+impl OwnedTexture {
+    /// Destroys `self`, giving back the wrapped [`Texture`] (which is not [`Send`]).
+    pub fn into_inner(self) -> Texture<'static> {
+        self.texture
+    }
+
+    /// See [`Texture::data_mut`].
+    pub fn data_mut(&mut self) -> &mut [u8] {
+        self.texture.data_mut()
+    }
+
+    /// See [`Texture::get_image_data_mut`].
+    pub fn get_image_data_mut(
+        &mut self,
+        level: u32,
+        layer: u32,
+        slice: u32,
+    ) -> Result<&mut [u8], KtxError> {
+        self.texture.get_image_data_mut(level, layer, slice)
+    }
+
+    /// See [`Texture::set_orientation`].
+    pub fn set_orientation(&mut self, orientation: Orientations) -> Result<(), KtxError> {
+        self.texture.set_orientation(orientation)
+    }
+
+    /// See [`Texture::set_metadata`].
+    pub fn set_metadata(&mut self, key: &str, value: &[u8]) -> Result<(), KtxError> {
+        self.texture.set_metadata(key, value)
+    }
+
+    /// See [`Texture::delete_metadata`].
+    pub fn delete_metadata(&mut self, key: &str) -> Result<(), KtxError> {
+        self.texture.delete_metadata(key)
+    }
+
+    /// See [`Texture::ktx1`].
+    pub fn ktx1(&mut self) -> Option<Ktx1<'_, 'static>> {
+        self.texture.ktx1()
+    }
+
+    /// See [`Texture::ktx2`].
+    pub fn ktx2(&mut self) -> Option<Ktx2<'_, 'static>> {
+        self.texture.ktx2()
+    }
+}
+
+impl Deref for OwnedTexture {
+    type Target = Texture<'static>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.texture
+    }
+}
+
 /// KTX1-specific [`Texture`] functionality.
 pub struct Ktx1<'a, 'b: 'a> {
     texture: &'a mut Texture<'b>,
@@ -395,35 +2138,57 @@ impl<'a, 'b: 'a> Ktx1<'a, 'b> {
         self.texture.handle as *mut sys::ktxTexture1
     }
 
-    /// Returns the OpenGL format of the texture's data (e.g. `GL_RGBA`).
+    /// Returns the OpenGL format of the texture's data (e.g. [`GlFormat::Rgba`]).
     ///
-    /// Also see [`Self::gl_internal_format`], [`Self::gl_base_internal_format`].
-    pub fn gl_format(&self) -> u32 {
+    /// Also see [`Self::gl_internal_format`], [`Self::gl_base_internal_format`], [`Self::gl_format_raw`].
+    pub fn gl_format(&self) -> GlFormat {
+        self.gl_format_raw().into()
+    }
+
+    /// Returns the raw OpenGL format of the texture's data (e.g. `GL_RGBA`).
+    pub fn gl_format_raw(&self) -> u32 {
         let handle = self.handle();
         // SAFETY: Safe if `self.texture.handle` is sane + actually a KTX1
         unsafe { (*handle).glFormat }
     }
 
-    /// Returns the OpenGL format of the texture's data (e.g. `GL_RGBA`).
+    /// Returns the OpenGL internal format of the texture's data (e.g. [`GlInternalFormat::Rgba8`]).
     ///
-    /// Also see [`Self::gl_format`], [`Self::gl_base_internal_format`].
-    pub fn gl_internal_format(&self) -> u32 {
+    /// Also see [`Self::gl_format`], [`Self::gl_base_internal_format`], [`Self::gl_internal_format_raw`].
+    pub fn gl_internal_format(&self) -> GlInternalFormat {
+        self.gl_internal_format_raw().into()
+    }
+
+    /// Returns the raw OpenGL internal format of the texture's data (e.g. `GL_RGBA8`).
+    pub fn gl_internal_format_raw(&self) -> u32 {
         let handle = self.handle();
         // SAFETY: Safe if `self.texture.handle` is sane + actually a KTX1
-        unsafe { (*handle).glFormat }
+        unsafe { (*handle).glInternalformat }
     }
 
-    /// Returns the OpenGL base internal format of the texture's data (e.g. `GL_RGBA`).
+    /// Returns the OpenGL base internal format of the texture's data (e.g. [`GlInternalFormat::Rgba`]).
     ///
-    /// Also see [`Self::gl_format`], [`Self::gl_internal_format`].
-    pub fn gl_base_internal_format(&self) -> u32 {
+    /// Also see [`Self::gl_format`], [`Self::gl_internal_format`], [`Self::gl_base_internal_format_raw`].
+    pub fn gl_base_internal_format(&self) -> GlInternalFormat {
+        self.gl_base_internal_format_raw().into()
+    }
+
+    /// Returns the raw OpenGL base internal format of the texture's data (e.g. `GL_RGBA`).
+    pub fn gl_base_internal_format_raw(&self) -> u32 {
         let handle = self.handle();
         // SAFETY: Safe if `self.texture.handle` is sane + actually a KTX1
         unsafe { (*handle).glBaseInternalformat }
     }
 
-    /// Returns the OpenGL datatype of the texture's data (e.g. `GL_UNSIGNED_BYTE`).
-    pub fn gl_type(&self) -> u32 {
+    /// Returns the OpenGL datatype of the texture's data (e.g. [`GlType::UnsignedByte`]).
+    ///
+    /// Also see [`Self::gl_type_raw`].
+    pub fn gl_type(&self) -> GlType {
+        self.gl_type_raw().into()
+    }
+
+    /// Returns the raw OpenGL datatype of the texture's data (e.g. `GL_UNSIGNED_BYTE`).
+    pub fn gl_type_raw(&self) -> u32 {
         let handle = self.handle();
         // SAFETY: Safe if `self.texture.handle` is sane + actually a KTX1
         unsafe { (*handle).glType }
@@ -435,13 +2200,56 @@ impl<'a, 'b: 'a> Ktx1<'a, 'b> {
         unsafe { sys::ktxTexture1_NeedsTranscoding(self.handle()) }
     }
 
-    // TODO: WriteKTX2ToStream with a Rust stream (and to a memory slice?)
-    //       Probably needs a TextureSink trait
+    /// Attempts to upload this texture's image data to a new OpenGL texture object.
+    ///
+    /// A valid OpenGL context must be current on the calling thread,
+    /// and image data must already have been loaded (see [`Texture::load_image_data()`]).  
+    /// Returns [`KtxError::GlError`] if the upload fails on the OpenGL side.
+    #[cfg(feature = "gl")]
+    pub fn gl_upload(&self) -> Result<GlUploadResult, KtxError> {
+        // SAFETY: Safe if `self.texture.handle` is sane.
+        if unsafe { (*self.texture.handle).pData.is_null() } {
+            return Err(KtxError::InvalidOperation);
+        }
+
+        let mut result = GlUploadResult {
+            texture: 0,
+            target: 0,
+            gl_error: 0,
+        };
+        // SAFETY: Safe if `self.texture.handle` is sane.
+        let err = unsafe {
+            sys::ktxTexture_GLUpload(
+                self.texture.handle,
+                &mut result.texture,
+                &mut result.target,
+                &mut result.gl_error,
+            )
+        };
+        ktx_result(err, result)
+    }
+
+    /// Attempts to write this KTX1 to `sink`, upconverting it to a KTX2 in the process.
+    #[cfg(feature = "write")]
+    pub fn write_ktx2_to_stream<'c, T, C>(
+        &self,
+        sink: &mut StreamSink<'c, T, C>,
+    ) -> Result<(), KtxError>
+    where
+        T: RWSeekable + ?Sized + 'c,
+        C: StreamContainer<'c, Inner = T>,
+    {
+        // SAFETY: Safe if `self.texture.handle` is sane + actually a KTX1
+        let err = sink.stream.with_ktx_stream(|ktx_stream| unsafe {
+            sys::ktxTexture1_WriteKTX2ToStream(self.handle(), ktx_stream)
+        })?;
+        ktx_result(err, ())
+    }
 }
 
 /// KTX2-specific [`Texture`] functionality.
 pub struct Ktx2<'a, 'b: 'a> {
-    texture: &'a mut Texture<'b>,
+    pub(crate) texture: &'a mut Texture<'b>,
 }
 
 impl<'a, 'b: 'a> Ktx2<'a, 'b> {
@@ -452,13 +2260,44 @@ impl<'a, 'b: 'a> Ktx2<'a, 'b> {
         self.texture.handle as *mut sys::ktxTexture2
     }
 
-    /// Returns the Vulkan format of the texture's data (e.g. `VK_R8G8B8A8_UNORM`).
-    pub fn vk_format(&self) -> u32 {
+    /// Returns the Vulkan format of the texture's data (e.g. [`VkFormat::R8G8B8A8Unorm`]).
+    ///
+    /// Also see [`Self::vk_format_raw`].
+    pub fn vk_format(&self) -> VkFormat {
+        self.vk_format_raw().into()
+    }
+
+    /// Returns the raw Vulkan format of the texture's data (e.g. `VK_FORMAT_R8G8B8A8_UNORM`).
+    pub fn vk_format_raw(&self) -> u32 {
         let handle = self.handle();
-        // SAFETY: Safe if `self.texture.handle` is sane + actually a KTX1
+        // SAFETY: Safe if `self.texture.handle` is sane + actually a KTX2
         unsafe { (*handle).vkFormat }
     }
 
+    /// Returns a view on the texture's Data Format Descriptor, including its first word (its total size in bytes).  
+    /// This is empty if the texture has no DFD; see [`crate::DfdHeader::parse`] to interpret it.
+    pub fn dfd(&self) -> &[u32] {
+        // SAFETY: Safe if `self.texture.handle` is sane + actually a KTX2
+        unsafe { dfd_slice(self.handle()) }
+    }
+
+    /// Returns size information about the texture's format; see [`FormatInfo`].
+    ///
+    /// The block footprint is read from the texture's DFD (that libKTX derives from the `vkFormat` on creation),
+    /// and defaults to 1x1 texels if the texture has no valid DFD (see [`DfdHeader::parse`]).
+    pub fn format_info(&self) -> FormatInfo {
+        let (block_width, block_height) = DfdHeader::parse(self.dfd()).map_or((1, 1), |header| {
+            let [width, height, ..] = header.texel_block_dimensions;
+            (width, height)
+        });
+        FormatInfo {
+            bytes_per_block: self.texture.element_size(),
+            block_width,
+            block_height,
+            is_compressed: self.texture.is_compressed(),
+        }
+    }
+
     /// Returns the supercompression scheme in use for this texture's data.
     pub fn supercompression_scheme(&self) -> SuperCompressionScheme {
         let handle = self.handle();
@@ -466,6 +2305,50 @@ impl<'a, 'b: 'a> Ktx2<'a, 'b> {
         unsafe { (*handle).supercompressionScheme.into() }
     }
 
+    /// Returns the size (in bytes) of a single decoded image (i.e. one layer, face, or depth slice) at the given mip level.  
+    /// This does not depend on [`Self::supercompression_scheme`]: it is the same before and after [`Self::deflate_zstd`],
+    /// and always matches the images' size after loading the texture, which inflates their data.
+    ///
+    /// Fails with [`KtxError::InvalidValue`] if `level` is out of range.
+    pub fn image_size_uncompressed(&self, level: u32) -> Result<usize, KtxError> {
+        if level as usize >= self.texture.num_levels() {
+            return Err(KtxError::InvalidValue);
+        }
+        self.texture.get_image_size(level)
+    }
+
+    /// Attempts to read the data of a single mip level from the texture's source, without loading the whole texture;
+    /// e.g. to stream in the smallest mip levels first, and larger ones later.
+    ///
+    /// The level is looked up in the file's level index, then read on its own: this can be called any number of times,
+    /// for levels in any order. The result holds the whole level as it is stored (as in [`Texture::data`]),
+    /// i.e. each array layer in turn, each holding all cubemap faces, each holding all depth slices.
+    ///
+    /// Fails with [`KtxError::InvalidValue`] if `level` is out of range, with [`KtxError::UnsupportedFeature`] if the
+    /// texture is supercompressed (see [`Texture::iterate_load_level_faces`] to inflate ZStandard data in one pass),
+    /// or with [`KtxError::InvalidOperation`] if image data was already loaded (i.e. the texture was created with
+    /// [`TextureCreateFlags::LOAD_IMAGE_DATA`]) or the texture's source was consumed by [`Texture::iterate_load_level_faces`].
+    pub fn load_level(&mut self, level: u32) -> Result<Vec<u8>, Error> {
+        let context = || format!("load_level({})", level);
+        if level as usize >= self.texture.num_levels() {
+            return Err(Error::new(KtxError::InvalidValue, context()));
+        }
+        if self.supercompression_scheme() != SuperCompressionScheme::None {
+            return Err(Error::new(KtxError::UnsupportedFeature, context()));
+        }
+        // SAFETY: Safe if `self.texture.handle` is sane.
+        if self.texture.source_consumed || unsafe { !(*self.texture.handle).pData.is_null() } {
+            return Err(Error::new(KtxError::InvalidOperation, context()));
+        }
+
+        // SAFETY: Safe if `self.texture.handle` is sane + actually a KTX2. Its source stream is still there,
+        //         as image data was neither loaded nor iterated over.
+        unsafe {
+            let stream = &mut (*(*self.texture.handle)._protected)._stream as *mut sys::ktxStream;
+            read_ktx2_level(stream, level).context(context)
+        }
+    }
+
     /// Is this a video texture?
     pub fn is_video(&self) -> bool {
         let handle = self.handle();
@@ -500,53 +2383,172 @@ impl<'a, 'b: 'a> Ktx2<'a, 'b> {
         unsafe { sys::ktxTexture2_NeedsTranscoding(self.handle()) }
     }
 
+    /// Returns which Basis Universal encoding this KTX2 is in, or `None` if it is not Basis-compressed.
+    ///
+    /// This is derived from the supercompression scheme (BasisLZ implies ETC1S) and from the color model in the DFD.
+    pub fn basis_encoding(&self) -> Option<BasisEncoding> {
+        if self.supercompression_scheme() == SuperCompressionScheme::BasisLZ {
+            return Some(BasisEncoding::Etc1s);
+        }
+        match DfdHeader::parse(self.dfd()).ok()?.color_model {
+            ColorModel::Etc1s => Some(BasisEncoding::Etc1s),
+            ColorModel::Uastc => Some(BasisEncoding::Uastc),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if this KTX2 is Basis Universal-compressed, either as ETC1S/BasisLZ or as UASTC;
+    /// see [`Self::basis_encoding`].
+    pub fn is_basis_compressed(&self) -> bool {
+        self.basis_encoding().is_some()
+    }
+
+    /// Returns `true` if this KTX2 is ZStandard-supercompressed; see [`Self::supercompression_scheme`].
+    pub fn is_zstd(&self) -> bool {
+        self.supercompression_scheme() == SuperCompressionScheme::ZStd
+    }
+
+    /// Fails with [`KtxError::InvalidOperation`] if this KTX2 is already block-compressed or supercompressed,
+    /// as its data can't be (re)compressed.
+    fn ensure_uncompressed(&self) -> Result<(), KtxError> {
+        if self.texture.is_compressed()
+            || self.supercompression_scheme() != SuperCompressionScheme::None
+        {
+            return Err(KtxError::InvalidOperation);
+        }
+        Ok(())
+    }
+
     /// Compresses a uncompressed KTX2 texture with Basis Universal.  
-    /// `quality` is 1-255; 0 -> the default quality, 128. **Lower `quality` means better (but slower) compression**.
-    pub fn compress_basis(&mut self, quality: u32) -> Result<(), KtxError> {
+    /// `quality` is 1-255; 0 -> the default quality, 128. **Lower `quality` means better (but slower) compression**.  
+    /// Fails with [`KtxError::InvalidValue`] if `quality` is out of range,
+    /// or with [`KtxError::InvalidOperation`] if the texture is already compressed.
+    pub fn compress_basis(&mut self, quality: u32) -> Result<(), Error> {
+        let context = || format!("compress_basis({})", quality);
+        if quality > 255 {
+            return Err(Error::new(KtxError::InvalidValue, context()));
+        }
+        self.ensure_uncompressed()
+            .context(|| format!("{}: texture is already compressed", context()))?;
         // SAFETY: Safe if `self.texture.handle` is sane + actually a KTX2
         let errcode = unsafe { sys::ktxTexture2_CompressBasis(self.handle(), quality as u32) };
-        ktx_result(errcode, ())
+        ktx_result(errcode, ()).context(context)
+    }
+
+    /// Compresses a uncompressed KTX2 texture with Basis Universal, with the given parameters.  
+    /// Depending on [`BasisParams::uastc`], this encodes to either UASTC or ETC1S/BasisLZ.  
+    /// Fails with [`KtxError::InvalidOperation`] if the texture is already compressed.
+    pub fn compress_basis_ex(&mut self, params: BasisParams) -> Result<(), Error> {
+        self.ensure_uncompressed()
+            .context(|| "compress_basis_ex: texture is already compressed")?;
+        let mut c_params = sys::ktxBasisParams {
+            structSize: std::mem::size_of::<sys::ktxBasisParams>() as u32,
+            uastc: params.uastc,
+            verbose: params.verbose,
+            noSSE: params.no_sse,
+            threadCount: resolve_thread_count(params.thread_count),
+            compressionLevel: params.compression_level,
+            qualityLevel: params.quality_level,
+            maxEndpoints: params.max_endpoints,
+            endpointRDOThreshold: params.endpoint_rdo_threshold,
+            maxSelectors: params.max_selectors,
+            selectorRDOThreshold: params.selector_rdo_threshold,
+            inputSwizzle: c_swizzle(&params.input_swizzle),
+            normalMap: params.normal_map,
+            separateRGToRGB_A: params.separate_rg_to_rgb_a,
+            preSwizzle: params.pre_swizzle,
+            noEndpointRDO: params.no_endpoint_rdo,
+            noSelectorRDO: params.no_selector_rdo,
+            uastcFlags: params.uastc_flags.bits(),
+            uastcRDO: params.uastc_rdo,
+            uastcRDOQualityScalar: params.rdo_quality,
+            uastcRDODictSize: params.uastc_rdo_dict_size,
+            uastcRDOMaxSmoothBlockErrorScale: params.uastc_rdo_max_smooth_block_error_scale,
+            uastcRDOMaxSmoothBlockStdDev: params.uastc_rdo_max_smooth_block_std_dev,
+            uastcRDODontFavorSimplerModes: params.uastc_rdo_dont_favor_simpler_modes,
+            uastcRDONoMultithreading: params.uastc_rdo_no_multithreading,
+        };
+
+        // SAFETY: Safe if `self.texture.handle` is sane + actually a KTX2
+        let errcode = unsafe { sys::ktxTexture2_CompressBasisEx(self.handle(), &mut c_params) };
+        ktx_result(errcode, ()).context(|| "compress_basis_ex")
     }
 
     /// Compresses the KTX2 texture's data with ZStandard compression.  
     /// `level` is 1-22; lower is faster (hence, worse compression).  
-    /// Values over 20 may consume significant memory.
-    pub fn deflate_zstd(&mut self, level: u32) -> Result<(), KtxError> {
+    /// Values over 20 may consume significant memory.  
+    /// Fails with [`KtxError::InvalidValue`] if `level` is out of range,
+    /// or with [`KtxError::InvalidOperation`] if the data is already supercompressed.
+    pub fn deflate_zstd(&mut self, level: u32) -> Result<(), Error> {
+        let context = || format!("deflate_zstd({})", level);
+        if !(1..=22).contains(&level) {
+            return Err(Error::new(KtxError::InvalidValue, context()));
+        }
+        if self.supercompression_scheme() != SuperCompressionScheme::None {
+            return Err(Error::new(
+                KtxError::InvalidOperation,
+                format!("{}: texture is already supercompressed", context()),
+            ));
+        }
         // SAFETY: Safe if `self.texture.handle` is sane + actually a KTX2
         let errcode = unsafe { sys::ktxTexture2_DeflateZstd(self.handle(), level as u32) };
-        ktx_result(errcode, ())
+        ktx_result(errcode, ()).context(context)
+    }
+
+    /// Decompresses the KTX2 texture's ZStandard-supercompressed data, undoing [`Self::deflate_zstd`].  
+    /// On success, [`Self::supercompression_scheme`] is back to [`SuperCompressionScheme::None`].
+    ///
+    /// Fails with [`KtxError::InvalidOperation`] if the data is not ZStandard-supercompressed.  
+    /// The C library only inflates data while loading it, so this works by writing the texture
+    /// to memory and reading it back (metadata and orientation are preserved).
+    #[cfg(feature = "write")]
+    pub fn inflate_zstd(&mut self) -> Result<(), Error> {
+        if self.supercompression_scheme() != SuperCompressionScheme::ZStd {
+            return Err(Error::new(KtxError::InvalidOperation, "inflate_zstd"));
+        }
+        let bytes = self.texture.write_to_memory()?;
+        let mut inflated = Texture::new(OwnedMemorySource {
+            bytes,
+            create_flags: TextureCreateFlags::LOAD_IMAGE_DATA,
+        })?;
+        // The inflated texture's data does not reference its source (it was loaded),
+        // so it can take this one's place; the old handle is destroyed along with `inflated`.
+        std::mem::swap(&mut self.texture.handle, &mut inflated.handle);
+        Ok(())
     }
 
     /// Compresses the KTX2's image data with ASTC.  
-    /// This is a simplified version of [`Ktx2::compress_astc_ex`].
-    pub fn compress_astc(&mut self, quality: u32) -> Result<(), KtxError> {
+    /// This is a simplified version of [`Ktx2::compress_astc_ex`].  
+    /// Fails with [`KtxError::InvalidOperation`] if the texture is already compressed.
+    pub fn compress_astc(&mut self, quality: u32) -> Result<(), Error> {
+        self.ensure_uncompressed()
+            .context(|| format!("compress_astc({}): texture is already compressed", quality))?;
         // SAFETY: Safe if `self.texture.handle` is sane + actually a KTX2
         let errcode = unsafe { sys::ktxTexture2_CompressAstc(self.handle(), quality) };
-        ktx_result(errcode, ())
+        ktx_result(errcode, ()).context(|| format!("compress_astc({})", quality))
     }
 
     /// Compresses the KTX2's image data with ASTC.   
-    /// This is an extended version of [`Ktx2::compress_astc`].
-    pub fn compress_astc_ex(&mut self, params: AstcParams) -> Result<(), KtxError> {
-        let mut c_input_swizzle: [std::os::raw::c_char; 4] = [0, 0, 0, 0];
-        for (ch, c_ch) in params.input_swizzle.iter().zip(c_input_swizzle.iter_mut()) {
-            *c_ch = *ch as _;
-        }
+    /// This is an extended version of [`Ktx2::compress_astc`].  
+    /// Fails with [`KtxError::InvalidOperation`] if the texture is already compressed.
+    pub fn compress_astc_ex(&mut self, params: AstcParams) -> Result<(), Error> {
+        self.ensure_uncompressed()
+            .context(|| "compress_astc_ex: texture is already compressed")?;
         let mut c_params = sys::ktxAstcParams {
             structSize: std::mem::size_of::<sys::ktxAstcParams>() as u32,
             verbose: params.verbose,
-            threadCount: params.thread_count,
+            threadCount: resolve_thread_count(params.thread_count),
             blockDimension: params.block_dimension as u32,
             function: params.function as u32,
             mode: params.mode as u32,
             qualityLevel: params.quality_level as u32,
             normalMap: params.normal_map,
-            inputSwizzle: c_input_swizzle,
+            inputSwizzle: c_swizzle(&params.input_swizzle),
         };
 
         // SAFETY: Safe if `self.texture.handle` is sane + actually a KTX2
         let errcode = unsafe { sys::ktxTexture2_CompressAstcEx(self.handle(), &mut c_params) };
-        ktx_result(errcode, ())
+        ktx_result(errcode, ()).context(|| "compress_astc_ex")
     }
 
     /// Returns the number of components of the KTX2 and the size in bytes of each components.
@@ -584,6 +2586,63 @@ impl<'a, 'b: 'a> Ktx2<'a, 'b> {
         unsafe { sys::ktxTexture2_GetOETF(self.handle()) }
     }
 
+    /// Returns the transfer function for this KTX2; see [`Self::oetf`].
+    pub fn transfer_function(&self) -> TransferFunction {
+        self.oetf().into()
+    }
+
+    /// Re-tags this KTX2's data with the given transfer function, without re-encoding it; see [`Self::oetf`].
+    ///
+    /// This updates the DFD and, for formats that come in both UNORM and SRGB variants (e.g. [`VkFormat::R8G8B8A8Unorm`]),
+    /// also switches [`Self::vk_format`] to the matching one.  
+    /// Fails with [`KtxError::InvalidOperation`] if the texture has no valid DFD, or if its format can't carry `oetf`
+    /// (e.g. sRGB for a float format); or with [`KtxError::InvalidValue`] if `oetf` does not fit in the DFD.
+    pub fn set_oetf(&mut self, oetf: TransferFunction) -> Result<(), KtxError> {
+        if DfdHeader::parse(self.dfd()).is_err() {
+            return Err(KtxError::InvalidOperation);
+        }
+        let vk_format = self.vk_format();
+        let new_vk_format = match (vk_format, vk_format.srgb_pair(), oetf) {
+            // The format is entirely described by the DFD
+            (VkFormat::Undefined, _, _) => vk_format,
+            (_, Some((_, srgb)), TransferFunction::Srgb) => srgb,
+            (_, Some((unorm, _)), TransferFunction::Linear) => unorm,
+            // Other formats imply their transfer function, so it can't be changed
+            (_, None, _) if oetf == self.transfer_function() => vk_format,
+            _ => return Err(KtxError::InvalidOperation),
+        };
+
+        // SAFETY: Safe if `self.texture.handle` is sane + actually a KTX2
+        let dfd_words = unsafe { dfd_slice_mut(self.handle()) };
+        dfd::set_transfer_function(dfd_words, oetf)?;
+        // SAFETY: Safe if `self.texture.handle` is sane + actually a KTX2
+        unsafe {
+            (*self.handle()).vkFormat = new_vk_format.into();
+        }
+        Ok(())
+    }
+
+    /// Returns the color primaries of this KTX2, as stored in its DFD;
+    /// [`ColorPrimaries::Unspecified`] if the texture has no valid DFD.
+    pub fn primaries(&self) -> ColorPrimaries {
+        DfdHeader::parse(self.dfd()).map_or(ColorPrimaries::Unspecified, |header| {
+            header.color_primaries.into()
+        })
+    }
+
+    /// Re-tags this KTX2's data with the given color primaries, without re-encoding it; see [`Self::primaries`].
+    ///
+    /// Fails with [`KtxError::InvalidOperation`] if the texture has no valid DFD,
+    /// or with [`KtxError::InvalidValue`] if `primaries` does not fit in the DFD.
+    pub fn set_primaries(&mut self, primaries: ColorPrimaries) -> Result<(), KtxError> {
+        if DfdHeader::parse(self.dfd()).is_err() {
+            return Err(KtxError::InvalidOperation);
+        }
+        // SAFETY: Safe if `self.texture.handle` is sane + actually a KTX2
+        let dfd_words = unsafe { dfd_slice_mut(self.handle()) };
+        dfd::set_color_primaries(dfd_words, primaries)
+    }
+
     /// Does this KTX2 have premultiplied alpha?
     pub fn premultiplied_alpha(&self) -> bool {
         // SAFETY: Safe if `self.texture.handle` is sane + actually a KTX2
@@ -600,10 +2659,158 @@ impl<'a, 'b: 'a> Ktx2<'a, 'b> {
         &mut self,
         format: TranscodeFormat,
         flags: TranscodeFlags,
-    ) -> Result<(), KtxError> {
+    ) -> Result<(), Error> {
         // SAFETY: Safe if `self.texture.handle` is sane + actually a KTX2
         let errcode =
             unsafe { sys::ktxTexture2_TranscodeBasis(self.handle(), format as u32, flags.bits()) };
-        ktx_result(errcode, ())
+        ktx_result(errcode, ()).context(|| format!("transcode_basis({:?})", format))
+    }
+
+    /// Like [`Self::transcode_basis`], but returns the concrete format that the texture was transcoded to.
+    ///
+    /// This is `format` itself, unless it is an automatic selection ([`TranscodeFormat::Etc`] or
+    /// [`TranscodeFormat::Bc1or3`]); the actual choice is then read back from the texture's new Vulkan format.  
+    /// Fails with [`KtxError::UnsupportedFeature`] if the new Vulkan format does not match any [`TranscodeFormat`].
+    pub fn transcode_basis_resolved(
+        &mut self,
+        format: TranscodeFormat,
+        flags: TranscodeFlags,
+    ) -> Result<TranscodeFormat, Error> {
+        self.transcode_basis(format, flags)?;
+        if !matches!(format, TranscodeFormat::Etc | TranscodeFormat::Bc1or3) {
+            return Ok(format);
+        }
+        match self.vk_format() {
+            VkFormat::Etc2R8G8B8UnormBlock | VkFormat::Etc2R8G8B8SrgbBlock => {
+                Ok(TranscodeFormat::Etc1Rgb)
+            }
+            VkFormat::Etc2R8G8B8A8UnormBlock | VkFormat::Etc2R8G8B8A8SrgbBlock => {
+                Ok(TranscodeFormat::Etc2Rgba)
+            }
+            VkFormat::Bc1RgbUnormBlock | VkFormat::Bc1RgbSrgbBlock => Ok(TranscodeFormat::Bc1Rgb),
+            VkFormat::Bc3UnormBlock | VkFormat::Bc3SrgbBlock => Ok(TranscodeFormat::Bc3Rgba),
+            other => Err(Error::new(
+                KtxError::UnsupportedFeature,
+                format!(
+                    "transcode_basis_resolved({:?}): resolved to {:?}",
+                    format, other
+                ),
+            )),
+        }
+    }
+
+    /// Chooses the best format to [transcode](Self::transcode_basis) this KTX2 to, on a GPU with the given `features`.
+    ///
+    /// This follows the Basis Universal recommendations: UASTC is preferably transcoded to ASTC, then BC7,
+    /// while ETC1S (i.e. BasisLZ) is preferably transcoded to ETC, then BC7. PVRTC1 is only chosen for textures
+    /// that pass [`Texture::validate_for_pvrtc`]. If no supported format fits, this falls back to uncompressed [`TranscodeFormat::Rgba32`].
+    ///
+    /// Within a family, the format is chosen by [`TranscodeFormat::preferred_for_components`] (e.g. BC4 for
+    /// single-channel textures); UASTC textures without alpha still get BC7 rather than BC1, as they transcode to it almost losslessly.  
+    /// Two-component textures are taken as luminance + alpha if they are ETC1S, and as RG (e.g. normal maps, for BC5) otherwise.
+    pub fn select_transcode_format(&self, features: GpuFeatures) -> TranscodeFormat {
+        let num_components = self.num_components();
+        let is_etc1s = self.supercompression_scheme() == SuperCompressionScheme::BasisLZ;
+        // Two-component ETC1S textures hold luminance + alpha, while any other two-component texture is RG
+        let has_alpha = num_components == 4 || (num_components == 2 && is_etc1s);
+        let pvrtc_compatible = self.texture.validate_for_pvrtc().is_ok();
+
+        let families = if is_etc1s {
+            [GpuFeatures::ETC2, GpuFeatures::BC7, GpuFeatures::ASTC]
+        } else {
+            [GpuFeatures::ASTC, GpuFeatures::BC7, GpuFeatures::ETC2]
+        };
+        families
+            .iter()
+            .copied()
+            .find(|&family| features.contains(family))
+            .or_else(|| {
+                if features.contains(GpuFeatures::PVRTC) && pvrtc_compatible {
+                    Some(GpuFeatures::PVRTC)
+                } else {
+                    None
+                }
+            })
+            .map(|family| {
+                match TranscodeFormat::preferred_for_components(num_components, has_alpha, family) {
+                    TranscodeFormat::Bc1Rgb if !is_etc1s => TranscodeFormat::Bc7Rgba,
+                    format => format,
+                }
+            })
+            .unwrap_or(TranscodeFormat::Rgba32)
+    }
+
+    /// Transcodes this KTX2 to the format chosen by [`Self::select_transcode_format`] for `features`;
+    /// see [`Self::transcode_basis`]. Returns the chosen format.
+    pub fn transcode_for(
+        &mut self,
+        features: GpuFeatures,
+        flags: TranscodeFlags,
+    ) -> Result<TranscodeFormat, Error> {
+        let format = self.select_transcode_format(features);
+        self.transcode_basis(format, flags)?;
+        Ok(format)
+    }
+
+    /// Like [`Self::transcode_basis`], but calls `progress(levels_done, total_levels)` as transcoding proceeds.
+    ///
+    /// The C library transcodes all levels in one go, so `progress` is only called twice:
+    /// with `levels_done = 0` before starting, and with `levels_done = total_levels` once all levels are done.  
+    /// If transcoding fails, the second call does not happen.
+    pub fn transcode_basis_with_progress<F>(
+        &mut self,
+        format: TranscodeFormat,
+        flags: TranscodeFlags,
+        mut progress: F,
+    ) -> Result<(), Error>
+    where
+        F: FnMut(u32, u32),
+    {
+        let total_levels = self.texture.num_levels() as u32;
+        progress(0, total_levels);
+        self.transcode_basis(format, flags)?;
+        progress(total_levels, total_levels);
+        Ok(())
+    }
+
+    /// Returns the size (in bytes) that an image at the given mip level would have
+    /// after transcoding this KTX2 to `format` with [`Self::transcode_basis`].
+    ///
+    /// Partial blocks at the edges of the image are rounded up to whole blocks (PVRTC1 images
+    /// are also at least 2x2 blocks in size). This does not check that the texture can actually be transcoded.
+    ///
+    /// Fails with [`KtxError::InvalidValue`] if `level` is out of range, or if `format`'s size
+    /// is only known after an automatic selection (like [`TranscodeFormat::Etc`]).
+    pub fn transcoded_image_size(
+        &self,
+        level: u32,
+        format: TranscodeFormat,
+    ) -> Result<usize, KtxError> {
+        if level as usize >= self.texture.num_levels() {
+            return Err(KtxError::InvalidValue);
+        }
+        let Extent3d { width, height, .. } = self.texture.extent_at_level(level);
+        if format.is_uncompressed() {
+            let bytes_per_pixel = match format {
+                TranscodeFormat::Rgba32 => 4,
+                _ => 2,
+            };
+            return Ok(width * height * bytes_per_pixel);
+        }
+
+        let (block_width, block_height) =
+            format.block_dimensions().ok_or(KtxError::InvalidValue)?;
+        let bytes_per_block = format.bytes_per_block().ok_or(KtxError::InvalidValue)?;
+        let (block_width, block_height) = (block_width as usize, block_height as usize);
+        let mut blocks_x = width.div_ceil(block_width);
+        let mut blocks_y = height.div_ceil(block_height);
+        if matches!(
+            format,
+            TranscodeFormat::Pvrtc14Rgb | TranscodeFormat::Pvrtc14Rgba
+        ) {
+            blocks_x = blocks_x.max(2);
+            blocks_y = blocks_y.max(2);
+        }
+        Ok(blocks_x * blocks_y * bytes_per_block as usize)
     }
 }
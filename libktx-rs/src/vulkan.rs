@@ -0,0 +1,131 @@
+// Copyright (C) 2021 Paolo Jovon <paolo.jovon@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+#![cfg(feature = "vulkan")]
+
+//! Uploading [`crate::Texture`]s to Vulkan.
+//!
+//! Vulkan handles are passed around as raw pointers (for dispatchable handles) or `u64`s (for non-dispatchable ones),
+//! so that this module does not depend on any specific Vulkan binding crate.
+
+use crate::{sys, KtxError};
+use std::os::raw::c_void;
+
+/// Information about the Vulkan device to upload [`crate::Texture`]s to.
+///
+/// See [`sys::ktxVulkanDeviceInfo`].
+#[derive(Debug)]
+pub struct VkDeviceInfo {
+    pub(crate) handle: *mut sys::ktxVulkanDeviceInfo,
+}
+
+impl VkDeviceInfo {
+    /// Attempts to create a new device info from the given Vulkan handles.
+    ///
+    /// `physical_device`, `device` and `queue` are the raw `VkPhysicalDevice`, `VkDevice` and `VkQueue` handles,
+    /// while `command_pool` is the raw `VkCommandPool` handle. The queue must support graphics operations,
+    /// and command buffers for uploading textures will be allocated from `command_pool`.
+    ///
+    /// # Safety
+    /// All handles must be valid, and they must outlive the returned device info.
+    pub unsafe fn new(
+        physical_device: *mut c_void,
+        device: *mut c_void,
+        queue: *mut c_void,
+        command_pool: u64,
+    ) -> Result<Self, KtxError> {
+        let handle = sys::ktxVulkanDeviceInfo_Create(
+            physical_device as sys::VkPhysicalDevice,
+            device as sys::VkDevice,
+            queue as sys::VkQueue,
+            command_pool as sys::VkCommandPool,
+            std::ptr::null(),
+        );
+        if handle.is_null() {
+            Err(KtxError::InvalidOperation)
+        } else {
+            Ok(VkDeviceInfo { handle })
+        }
+    }
+
+    /// Returns a pointer to the underlying (C-allocated) [`sys::ktxVulkanDeviceInfo`].
+    ///
+    /// **SAFETY**: Pointers are harmless. Dereferencing them is not!
+    pub fn handle(&self) -> *mut sys::ktxVulkanDeviceInfo {
+        self.handle
+    }
+}
+
+impl Drop for VkDeviceInfo {
+    fn drop(&mut self) {
+        // SAFETY: Safe if `self.handle` is sane.
+        unsafe { sys::ktxVulkanDeviceInfo_Destroy(self.handle) }
+    }
+}
+
+/// A Vulkan image that a [`crate::Texture`] was uploaded to.
+///
+/// See [`sys::ktxVulkanTexture`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct VkTexture {
+    /// The raw `VkImage` handle of the created image.
+    pub image: u64,
+    /// The `VkFormat` of the image's data.
+    pub image_format: u32,
+    /// The `VkImageLayout` the image was left in.
+    pub image_layout: u32,
+    /// The raw `VkDeviceMemory` handle of the memory bound to the image.
+    pub device_memory: u64,
+    /// The `VkImageViewType` that corresponds to the image.
+    pub view_type: u32,
+    pub width: u32,
+    pub height: u32,
+    pub depth: u32,
+    pub level_count: u32,
+    pub layer_count: u32,
+}
+
+impl VkTexture {
+    /// Destroys the image and frees its device memory.
+    ///
+    /// # Safety
+    /// `device` must be the raw `VkDevice` handle the image was created on,
+    /// and the image must not be in use anymore.
+    pub unsafe fn destroy(self, device: *mut c_void) {
+        let mut vk_texture: sys::ktxVulkanTexture = self.into();
+        sys::ktxVulkanTexture_Destruct(&mut vk_texture, device as sys::VkDevice, std::ptr::null());
+    }
+}
+
+impl From<sys::ktxVulkanTexture> for VkTexture {
+    fn from(vk_texture: sys::ktxVulkanTexture) -> Self {
+        VkTexture {
+            image: vk_texture.image as u64,
+            image_format: vk_texture.imageFormat,
+            image_layout: vk_texture.imageLayout,
+            device_memory: vk_texture.deviceMemory as u64,
+            view_type: vk_texture.viewType,
+            width: vk_texture.width,
+            height: vk_texture.height,
+            depth: vk_texture.depth,
+            level_count: vk_texture.levelCount,
+            layer_count: vk_texture.layerCount,
+        }
+    }
+}
+
+impl From<VkTexture> for sys::ktxVulkanTexture {
+    fn from(vk_texture: VkTexture) -> Self {
+        sys::ktxVulkanTexture {
+            image: vk_texture.image as sys::VkImage,
+            imageFormat: vk_texture.image_format,
+            imageLayout: vk_texture.image_layout,
+            deviceMemory: vk_texture.device_memory as sys::VkDeviceMemory,
+            viewType: vk_texture.view_type,
+            width: vk_texture.width,
+            height: vk_texture.height,
+            depth: vk_texture.depth,
+            levelCount: vk_texture.level_count,
+            layerCount: vk_texture.layer_count,
+        }
+    }
+}
@@ -2,13 +2,51 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use libktx_rs::{
-    sources::{Ktx1CreateInfo, Ktx2CreateInfo},
-    Texture,
+    dfd::DfdSampleType,
+    sources::{CommonCreateInfo, Ktx1CreateInfo, Ktx2CreateInfo},
+    texture::{AstcParams, BasisParams, Extent3d, FormatInfo},
+    BasisEncoding, Channel, ColorModel, ColorPrimaries, CreateStorage, DfdBuilder, DfdHeader,
+    GlFormat, GlInternalFormat, GlType, GpuFeatures, KtxError, OrientationX, OrientationY,
+    OrientationZ, Orientations, PackAstcBlockDimension, PackAstcEncoderFunction,
+    PackAstcEncoderMode, PackAstcQualityLevel, PackUastcFlags, PackUastcLevel,
+    SuperCompressionScheme, Texture, TextureClass, TextureCreateFlags, TranscodeFlags,
+    TranscodeFormat, TransferFunction, VkFormat,
 };
 
+#[test]
+fn libktx_version() {
+    let version = libktx_rs::version();
+    assert!(!version.is_empty());
+    let mut parts = version.trim_start_matches('v').splitn(3, '.');
+    for _ in 0..3 {
+        let part = parts.next().expect("a major.minor.patch version");
+        let digits: String = part.chars().take_while(char::is_ascii_digit).collect();
+        assert!(
+            digits.parse::<u32>().is_ok(),
+            "{:?} is not semver-ish",
+            version
+        );
+    }
+}
+
+#[test]
+fn prelude() {
+    use libktx_rs::prelude::*;
+
+    let mut texture: Texture =
+        Texture::new(Ktx2CreateInfo::default()).expect("a default KTX2 texture");
+    let mut ktx2: Ktx2 = texture.ktx2().expect("a KTX2 texture");
+    // Not Basis-compressed, hence not transcodable
+    let result: Result<(), Error> =
+        ktx2.transcode_basis(TranscodeFormat::Rgba32, TranscodeFlags::empty());
+    assert!(result.is_err());
+    let flags: TextureCreateFlags = TextureCreateFlags::LOAD_IMAGE_DATA;
+    assert!(!flags.is_empty());
+}
+
 #[test]
 fn create_default_ktx1() {
-    let texture = Texture::new(Ktx1CreateInfo::default()).expect("a default KTX1 texture");
+    let mut texture = Texture::new(Ktx1CreateInfo::default()).expect("a default KTX1 texture");
 
     // 1x1 RGBA8 texel
     assert_eq!(texture.element_size(), 4);
@@ -21,11 +59,242 @@ fn create_default_ktx1() {
             Ok(())
         })
         .expect("mip/face iteration to succeed");
+
+    let ktx1 = texture.ktx1().expect("a KTX1 texture");
+    assert_eq!(ktx1.gl_format(), GlFormat::Rgba);
+    assert_eq!(ktx1.gl_internal_format(), GlInternalFormat::Rgba8);
+    assert_eq!(ktx1.gl_base_internal_format(), GlInternalFormat::Rgba);
+    assert_eq!(ktx1.gl_type(), GlType::UnsignedByte);
+    assert_eq!(ktx1.gl_type_raw(), 0x1401);
 }
 
 #[test]
-fn create_default_ktx2() {
+fn create_ktx1_with_internal_format() {
+    let mut texture = Texture::new(Ktx1CreateInfo {
+        gl_internal_format: 0x8C43, // GL_SRGB8_ALPHA8
+        ..Default::default()
+    })
+    .expect("a sRGB KTX1 texture");
+
+    let ktx1 = texture.ktx1().expect("a KTX1 texture");
+    assert_eq!(ktx1.gl_internal_format_raw(), 0x8C43);
+    assert_eq!(ktx1.gl_internal_format(), GlInternalFormat::Srgb8Alpha8);
+    assert_ne!(ktx1.gl_internal_format_raw(), ktx1.gl_format_raw());
+}
+
+#[test]
+fn raw_handle_roundtrip() {
+    let info = Ktx2CreateInfo::builder()
+        .dimensions(4, 2, 1)
+        .build()
+        .expect("a valid KTX2 create info");
+    let texture = Texture::new(info).expect("a 4x2 KTX2 texture");
+    let handle = texture.into_raw();
+    assert!(!handle.is_null());
+
+    // SAFETY: The handle comes from a created texture (referencing no source), whose ownership was just relinquished.
+    let mut texture = unsafe { Texture::from_raw(handle) };
+    assert_eq!(texture.handle(), handle);
+    assert_eq!(texture.class(), TextureClass::Ktx2);
+    assert_eq!(texture.data_size(), 4 * 2 * 4);
+    let ktx2 = texture.ktx2().expect("a KTX2 texture");
+    assert_eq!(ktx2.vk_format(), VkFormat::R8G8B8A8Unorm);
+}
+
+#[test]
+fn create_from_dfd() {
+    let info = Ktx2CreateInfo::builder()
+        .dimensions(2, 2, 1)
+        .build()
+        .expect("a valid KTX2 create info");
+    let srgb_dfd = DfdBuilder::new(4).transfer_function(TransferFunction::Srgb);
+    let mut texture = Texture::new(info.clone().with_dfd(&srgb_dfd).expect("a valid DFD"))
+        .expect("a 2x2 KTX2 texture from a DFD");
+    assert_eq!(texture.element_size(), 4);
+    assert!(texture.is_srgb());
+
+    // Should match the DFD that the C library generates for the equivalent Vulkan format
+    let mut reference = Texture::new(Ktx2CreateInfo {
+        vk_format: VkFormat::R8G8B8A8Srgb,
+        ..info.clone()
+    })
+    .expect("a 2x2 sRGB KTX2 texture");
+    let reference_ktx2 = reference.ktx2().expect("a KTX2 texture");
+    assert_eq!(srgb_dfd.build().as_deref(), Ok(reference_ktx2.dfd()));
+    let ktx2 = texture.ktx2().expect("a KTX2 texture");
+    assert_eq!(ktx2.vk_format(), VkFormat::Undefined);
+
+    let float_dfd = DfdBuilder::new(2).sample_type(DfdSampleType::Float);
+    let texture = Texture::new(info.clone().with_dfd(&float_dfd).expect("a valid DFD"))
+        .expect("a 2x2 RG32F KTX2 texture from a DFD");
+    assert_eq!(texture.element_size(), 8);
+
+    for invalid in [
+        DfdBuilder::new(0),
+        DfdBuilder::new(5),
+        DfdBuilder::new(4)
+            .sample_type(DfdSampleType::Half)
+            .transfer_function(TransferFunction::Srgb),
+    ]
+    .iter()
+    {
+        assert_eq!(invalid.build(), Err(KtxError::InvalidValue));
+    }
+}
+
+#[test]
+fn set_oetf_and_primaries() {
+    let mut texture = Texture::new(
+        Ktx2CreateInfo::builder()
+            .vk_format(VkFormat::R8G8B8A8Unorm)
+            .dimensions(4, 4, 1)
+            .build()
+            .expect("valid creation info"),
+    )
+    .expect("a KTX2 texture");
+    let data = texture.data().to_vec();
+    let mut ktx2 = texture.ktx2().expect("a KTX2 texture");
+    assert_eq!(ktx2.transfer_function(), TransferFunction::Linear);
+
+    ktx2.set_oetf(TransferFunction::Srgb)
+        .expect("re-tagging the texture as sRGB");
+    assert_eq!(ktx2.transfer_function(), TransferFunction::Srgb);
+    assert_eq!(ktx2.vk_format(), VkFormat::R8G8B8A8Srgb);
+    let header = DfdHeader::parse(ktx2.dfd()).expect("a valid DFD");
+    assert!(header.samples[3].is_linear);
+    assert!(!header.samples[0].is_linear);
+
+    ktx2.set_primaries(ColorPrimaries::DisplayP3)
+        .expect("re-tagging the texture as Display P3");
+    assert_eq!(ktx2.primaries(), ColorPrimaries::DisplayP3);
+    assert_eq!(
+        ktx2.set_primaries(ColorPrimaries::Unknown(0x100)),
+        Err(KtxError::InvalidValue)
+    );
+
+    ktx2.set_oetf(TransferFunction::Linear)
+        .expect("re-tagging the texture as linear");
+    assert_eq!(ktx2.vk_format(), VkFormat::R8G8B8A8Unorm);
+    assert_eq!(ktx2.primaries(), ColorPrimaries::DisplayP3);
+    assert!(!texture.is_srgb());
+    assert_eq!(texture.data(), &data[..]);
+
+    let mut texture = Texture::new(
+        Ktx2CreateInfo::builder()
+            .vk_format(VkFormat::R32G32B32A32Sfloat)
+            .dimensions(4, 4, 1)
+            .build()
+            .expect("valid creation info"),
+    )
+    .expect("a float KTX2 texture");
+    let mut ktx2 = texture.ktx2().expect("a KTX2 texture");
+    assert_eq!(
+        ktx2.set_oetf(TransferFunction::Srgb),
+        Err(KtxError::InvalidOperation)
+    );
+    assert_eq!(ktx2.set_oetf(TransferFunction::Linear), Ok(()));
+}
+
+#[test]
+fn dfd_header() {
+    let mut texture = Texture::new(Ktx2CreateInfo {
+        vk_format: VkFormat::R8G8B8A8Srgb,
+        ..Default::default()
+    })
+    .expect("a sRGB KTX2 texture");
+    let ktx2 = texture.ktx2().expect("a KTX2 texture");
+    let dfd = ktx2.dfd();
+    assert_eq!(dfd[0] as usize, dfd.len() * 4);
+
+    let header = DfdHeader::parse(dfd).expect("a valid DFD");
+    assert_eq!(header.color_model, ColorModel::Rgbsda);
+    assert_eq!(header.transfer_function, TransferFunction::Srgb);
+    assert_eq!(header.transfer_function, ktx2.transfer_function());
+    assert!(!header.premultiplied_alpha);
+    assert_eq!(header.texel_block_dimensions, [1, 1, 1, 1]);
+    assert_eq!(header.bytes_planes[0], 4);
+    let channels: Vec<_> = header
+        .samples
+        .iter()
+        .map(|sample| sample.channel_id)
+        .collect();
+    assert_eq!(channels, [0, 1, 2, 15]);
+    for sample in header.samples.iter() {
+        assert_eq!(sample.bit_length, 8);
+        assert!(!sample.is_float);
+    }
+    assert!(header.samples[3].is_linear);
+
+    assert_eq!(DfdHeader::parse(&[]), Err(KtxError::InvalidValue));
+    assert_eq!(
+        DfdHeader::parse(&dfd[..dfd.len() - 1]),
+        Err(KtxError::InvalidValue)
+    );
+}
+
+#[test]
+fn copy_metadata() {
+    let mut source = Texture::new(Ktx2CreateInfo::default()).expect("a default KTX2 texture");
+    source
+        .set_metadata("Shared", b"from source")
+        .expect("setting a metadata key");
+    source
+        .set_metadata("SourceOnly", b"source")
+        .expect("setting a metadata key");
+    source
+        .set_orientation(Orientations {
+            x: OrientationX::Right,
+            y: OrientationY::Up,
+            z: OrientationZ::Out,
+        })
+        .expect("setting the orientation");
+
+    let mut target = Texture::new(Ktx2CreateInfo::default()).expect("a default KTX2 texture");
+    target
+        .set_metadata("Shared", b"from target")
+        .expect("setting a metadata key");
+    target
+        .set_metadata("TargetOnly", b"target")
+        .expect("setting a metadata key");
+    target
+        .copy_metadata_from(&source)
+        .expect("copying metadata");
+
+    let metadata = target.metadata();
+    assert_eq!(metadata.get("Shared"), Some(&b"from source"[..]));
+    assert_eq!(metadata.get("SourceOnly"), Some(&b"source"[..]));
+    assert_eq!(metadata.get("TargetOnly"), Some(&b"target"[..]));
+    assert_eq!(
+        metadata.iter().filter(|(key, _)| *key == b"Shared").count(),
+        1
+    );
+    assert_eq!(target.orientation(), source.orientation());
+}
+
+#[test]
+fn row_pitch_aligned() {
+    let info = Ktx2CreateInfo::builder()
+        .dimensions(3, 1, 1)
+        .build()
+        .expect("a valid KTX2 create info");
+    let texture = Texture::new(info).expect("a 3x1 KTX2 texture");
+    assert_eq!(texture.row_pitch(0), 12);
+    assert_eq!(texture.row_pitch_aligned(0, 1), 12);
+    assert_eq!(texture.row_pitch_aligned(0, 4), 12);
+    assert_eq!(texture.row_pitch_aligned(0, 8), 16);
+    assert_eq!(texture.row_pitch_aligned(0, 256), 256);
+}
+
+#[test]
+#[should_panic]
+fn row_pitch_aligned_non_power_of_two() {
     let texture = Texture::new(Ktx2CreateInfo::default()).expect("a default KTX2 texture");
+    texture.row_pitch_aligned(0, 3);
+}
+
+#[test]
+fn create_default_ktx2() {
+    let mut texture = Texture::new(Ktx2CreateInfo::default()).expect("a default KTX2 texture");
 
     // 1x1 RGBA8 texel
     assert_eq!(texture.element_size(), 4);
@@ -38,4 +307,1330 @@ fn create_default_ktx2() {
             Ok(())
         })
         .expect("mip/face iteration to succeed");
+
+    let ktx2 = texture.ktx2().expect("a KTX2 texture");
+    assert_eq!(ktx2.vk_format(), VkFormat::R8G8B8A8Unorm);
+    assert_eq!(ktx2.vk_format_raw(), 37);
+}
+
+#[test]
+fn create_from_builders() {
+    let ktx1_info = Ktx1CreateInfo::builder()
+        .gl_internal_format(GlInternalFormat::Rgba8)
+        .dimensions(4, 4, 1)
+        .levels(3)
+        .build()
+        .expect("a valid KTX1 create info");
+    let texture = Texture::new(ktx1_info).expect("a 4x4 KTX1 texture");
+    assert_eq!(texture.num_dimensions(), 2);
+    assert_eq!(texture.num_levels(), 3);
+
+    let ktx2_info = Ktx2CreateInfo::builder()
+        .vk_format(VkFormat::R8G8B8A8Srgb)
+        .dimensions(8, 8, 1)
+        .faces(6)
+        .build()
+        .expect("a valid KTX2 create info");
+    let texture = Texture::new(ktx2_info).expect("a 8x8 KTX2 cubemap");
+    assert_eq!(texture.base_width(), 8);
+    assert_eq!(texture.num_faces(), 6);
+    assert!(texture.is_cubemap());
+
+    assert_eq!(
+        Ktx2CreateInfo::builder().faces(2).build(),
+        Err(KtxError::InvalidValue)
+    );
+    assert_eq!(
+        Ktx1CreateInfo::builder().num_dimensions(4).build(),
+        Err(KtxError::InvalidValue)
+    );
+    assert_eq!(
+        Ktx2CreateInfo::builder().levels(0).build(),
+        Err(KtxError::InvalidValue)
+    );
+}
+
+#[test]
+fn invalid_create_info() {
+    let invalid_commons = [
+        // Non-square cubemap
+        CommonCreateInfo {
+            base_width: 8,
+            base_height: 4,
+            num_dimensions: 2,
+            num_faces: 6,
+            ..Default::default()
+        },
+        // 3D cubemap
+        CommonCreateInfo {
+            base_width: 4,
+            base_height: 4,
+            base_depth: 4,
+            num_dimensions: 3,
+            num_faces: 6,
+            ..Default::default()
+        },
+        // Array with no layers
+        CommonCreateInfo {
+            num_layers: 0,
+            is_array: true,
+            ..Default::default()
+        },
+        // More levels than a 4x4 texture can have
+        CommonCreateInfo {
+            base_width: 4,
+            base_height: 4,
+            num_dimensions: 2,
+            num_levels: 4,
+            ..Default::default()
+        },
+    ];
+    for common in invalid_commons.iter() {
+        let err = Texture::new(Ktx1CreateInfo {
+            common: common.clone(),
+            ..Default::default()
+        })
+        .expect_err("an invalid KTX1 create info");
+        assert_eq!(err.code(), KtxError::InvalidValue);
+        let err = Texture::new(Ktx2CreateInfo {
+            common: common.clone(),
+            ..Default::default()
+        })
+        .expect_err("an invalid KTX2 create info");
+        assert_eq!(err.code(), KtxError::InvalidValue);
+    }
+
+    assert_eq!(
+        Ktx2CreateInfo::builder()
+            .dimensions(8, 4, 1)
+            .faces(6)
+            .build(),
+        Err(KtxError::InvalidValue)
+    );
+}
+
+#[test]
+fn compress_uastc() {
+    let info = Ktx2CreateInfo::builder()
+        .dimensions(4, 4, 1)
+        .build()
+        .expect("a valid KTX2 create info");
+    let mut texture = Texture::new(info).expect("a 4x4 KTX2 texture");
+    let mut ktx2 = texture.ktx2().expect("a KTX2 texture");
+    assert_eq!(ktx2.basis_encoding(), None);
+    assert!(!ktx2.is_basis_compressed());
+    ktx2.compress_basis_ex(BasisParams {
+        uastc: true,
+        uastc_flags: PackUastcFlags::LEVEL_FASTEST,
+        thread_count: 1,
+        ..Default::default()
+    })
+    .expect("UASTC compression to succeed");
+    assert!(ktx2.needs_transcoding());
+    assert_eq!(ktx2.basis_encoding(), Some(BasisEncoding::Uastc));
+    assert!(ktx2.is_basis_compressed());
+    assert!(!ktx2.is_zstd());
+}
+
+#[test]
+fn compress_etc1s() {
+    let info = Ktx2CreateInfo::builder()
+        .dimensions(4, 4, 1)
+        .build()
+        .expect("a valid KTX2 create info");
+    let mut texture = Texture::new(info).expect("a 4x4 KTX2 texture");
+    let mut ktx2 = texture.ktx2().expect("a KTX2 texture");
+    ktx2.compress_basis_ex(BasisParams {
+        thread_count: 1,
+        ..Default::default()
+    })
+    .expect("ETC1S compression to succeed");
+    assert_eq!(ktx2.basis_encoding(), Some(BasisEncoding::Etc1s));
+    assert!(ktx2.is_basis_compressed());
+}
+
+#[test]
+fn compress_auto_thread_count() {
+    let info = Ktx2CreateInfo::builder()
+        .dimensions(4, 4, 1)
+        .build()
+        .expect("a valid KTX2 create info");
+    let mut texture = Texture::new(info).expect("a 4x4 KTX2 texture");
+    let mut ktx2 = texture.ktx2().expect("a KTX2 texture");
+    assert_eq!(AstcParams::default().thread_count, 0);
+    ktx2.compress_basis_ex(BasisParams {
+        uastc: true,
+        uastc_flags: PackUastcFlags::LEVEL_FASTEST,
+        thread_count: 0,
+        ..Default::default()
+    })
+    .expect("UASTC compression with an automatic thread count to succeed");
+    assert!(ktx2.is_basis_compressed());
+}
+
+#[test]
+fn transcode_basis_resolved() {
+    let etc1s_texture = || {
+        let info = Ktx2CreateInfo::builder()
+            .dimensions(4, 4, 1)
+            .build()
+            .expect("a valid KTX2 create info");
+        let mut texture = Texture::new(info).expect("a 4x4 RGBA KTX2 texture");
+        texture
+            .ktx2()
+            .expect("a KTX2 texture")
+            .compress_basis_ex(BasisParams {
+                thread_count: 1,
+                ..Default::default()
+            })
+            .expect("ETC1S compression to succeed");
+        texture
+    };
+
+    for &(format, expected) in [
+        (TranscodeFormat::Etc, TranscodeFormat::Etc2Rgba),
+        (TranscodeFormat::Bc1or3, TranscodeFormat::Bc3Rgba),
+        (TranscodeFormat::Bc7Rgba, TranscodeFormat::Bc7Rgba),
+    ]
+    .iter()
+    {
+        let mut texture = etc1s_texture();
+        let mut ktx2 = texture.ktx2().expect("a KTX2 texture");
+        let resolved = ktx2
+            .transcode_basis_resolved(format, TranscodeFlags::empty())
+            .expect("transcoding to succeed");
+        assert_eq!(resolved, expected);
+    }
+}
+
+#[test]
+fn compress_already_compressed() {
+    let info = Ktx2CreateInfo::builder()
+        .dimensions(4, 4, 1)
+        .build()
+        .expect("a valid KTX2 create info");
+    let mut texture = Texture::new(info).expect("a 4x4 KTX2 texture");
+    let mut ktx2 = texture.ktx2().expect("a KTX2 texture");
+    ktx2.compress_basis_ex(BasisParams {
+        thread_count: 1,
+        ..Default::default()
+    })
+    .expect("ETC1S compression to succeed");
+
+    let err = ktx2.compress_basis(0).expect_err("compressing twice");
+    assert_eq!(err.code(), KtxError::InvalidOperation);
+    let err = ktx2
+        .compress_astc_ex(Default::default())
+        .expect_err("compressing twice");
+    assert_eq!(err.code(), KtxError::InvalidOperation);
+    let err = ktx2.deflate_zstd(5).expect_err("supercompressing twice");
+    assert_eq!(err.code(), KtxError::InvalidOperation);
+}
+
+#[test]
+fn zstd_image_sizes() {
+    let info = Ktx2CreateInfo::builder()
+        .vk_format(VkFormat::R8G8B8A8Unorm)
+        .dimensions(16, 16, 1)
+        .levels(2)
+        .build()
+        .expect("a valid KTX2 create info");
+    let mut texture = Texture::new(info).expect("a 16x16 KTX2 texture");
+    let mut ktx2 = texture.ktx2().expect("a KTX2 texture");
+    ktx2.deflate_zstd(5)
+        .expect("ZStandard supercompression to succeed");
+    assert_eq!(ktx2.supercompression_scheme(), SuperCompressionScheme::ZStd);
+    assert_eq!(ktx2.image_size_uncompressed(0), Ok(16 * 16 * 4));
+    assert_eq!(ktx2.image_size_uncompressed(1), Ok(8 * 8 * 4));
+    assert_eq!(ktx2.image_size_uncompressed(2), Err(KtxError::InvalidValue));
+
+    assert_eq!(texture.get_image_size(0), Ok(16 * 16 * 4));
+    let uncompressed = texture
+        .get_data_size_uncompressed()
+        .expect("the uncompressed data size");
+    assert!(uncompressed >= (16 * 16 + 8 * 8) * 4);
+    // All-zero data deflates well
+    assert!(texture.data_size() < uncompressed);
+}
+
+#[test]
+fn compression_params_out_of_range() {
+    let mut texture = Texture::new(Ktx2CreateInfo::default()).expect("a default KTX2 texture");
+    let mut ktx2 = texture.ktx2().expect("a KTX2 texture");
+    for &level in [0, 23].iter() {
+        let err = ktx2.deflate_zstd(level).expect_err("an invalid level");
+        assert_eq!(err.code(), KtxError::InvalidValue);
+    }
+    let err = ktx2.compress_basis(256).expect_err("an invalid quality");
+    assert_eq!(err.code(), KtxError::InvalidValue);
+    assert!(!ktx2.needs_transcoding());
+}
+
+#[test]
+fn astc_params_swizzle() {
+    let params = AstcParams::default()
+        .with_swizzle("rrr1")
+        .expect("a valid swizzle");
+    assert_eq!(params.input_swizzle, ['r', 'r', 'r', '1']);
+    assert_eq!(params.quality_level, PackAstcQualityLevel::Medium);
+    assert_eq!(params.block_dimension, PackAstcBlockDimension::Dim4x4);
+    assert_eq!(
+        params,
+        AstcParams {
+            input_swizzle: ['r', 'r', 'r', '1'],
+            ..Default::default()
+        }
+    );
+    assert_ne!(params, AstcParams::default());
+    assert_eq!(params.clone(), params);
+    assert_ne!(
+        BasisParams {
+            uastc: true,
+            ..Default::default()
+        },
+        BasisParams::default()
+    );
+
+    for &swizzle in ["rgb", "rgbaa", "rgbx", ""].iter() {
+        assert_eq!(
+            AstcParams::default().with_swizzle(swizzle).err(),
+            Some(KtxError::InvalidValue)
+        );
+    }
+}
+
+#[test]
+fn astc_enums_roundtrip() {
+    use std::convert::TryFrom;
+    assert_eq!(PackAstcQualityLevel::ALL.len(), 5);
+    for &value in PackAstcQualityLevel::ALL.iter() {
+        assert_eq!(PackAstcQualityLevel::try_from(value as u32), Ok(value));
+    }
+    assert_eq!(PackAstcBlockDimension::ALL.len(), 24);
+    for &value in PackAstcBlockDimension::ALL.iter() {
+        assert_eq!(PackAstcBlockDimension::try_from(value as u32), Ok(value));
+    }
+    assert_eq!(PackAstcEncoderFunction::ALL.len(), 3);
+    for &value in PackAstcEncoderFunction::ALL.iter() {
+        assert_eq!(PackAstcEncoderFunction::try_from(value as u32), Ok(value));
+    }
+    assert_eq!(PackAstcEncoderMode::ALL.len(), 3);
+    for &value in PackAstcEncoderMode::ALL.iter() {
+        assert_eq!(PackAstcEncoderMode::try_from(value as u32), Ok(value));
+    }
+    assert!(PackAstcQualityLevel::try_from(42).is_err());
+}
+
+#[test]
+fn try_from_roundtrip() {
+    use std::convert::TryFrom;
+    assert_eq!(KtxError::ALL.len(), 18);
+    for &value in KtxError::ALL.iter() {
+        assert_eq!(KtxError::try_from(u32::from(value)), Ok(value));
+    }
+    assert_eq!(KtxError::try_from(0), Err("KTX_SUCCESS is not an error"));
+    assert_eq!(TranscodeFormat::ALL.len(), 21);
+    for &value in TranscodeFormat::ALL.iter() {
+        assert_eq!(TranscodeFormat::try_from(value as u32), Ok(value));
+    }
+    assert_eq!(
+        TranscodeFormat::try_from(1000),
+        Err("Not a ktx_transcode_fmt_e variant")
+    );
+}
+
+#[test]
+fn hashable_enums() {
+    use std::collections::{HashMap, HashSet};
+    let mut histogram: HashMap<KtxError, usize> = HashMap::new();
+    for &error in [
+        KtxError::NotFound,
+        KtxError::InvalidValue,
+        KtxError::NotFound,
+        KtxError::Unknown(1000),
+    ]
+    .iter()
+    {
+        *histogram.entry(error).or_default() += 1;
+    }
+    assert_eq!(histogram[&KtxError::NotFound], 2);
+    assert_eq!(histogram[&KtxError::Unknown(1000)], 1);
+
+    let formats: HashSet<_> = TranscodeFormat::ALL.iter().copied().collect();
+    assert_eq!(formats.len(), TranscodeFormat::ALL.len());
+    let schemes: HashSet<_> = [SuperCompressionScheme::None, SuperCompressionScheme::ZStd]
+        .iter()
+        .copied()
+        .collect();
+    assert!(schemes.contains(&SuperCompressionScheme::ZStd));
+    assert!(HashSet::from([CreateStorage::AllocStorage]).contains(&CreateStorage::AllocStorage));
+}
+
+#[test]
+fn unknown_error_code() {
+    use std::convert::TryFrom;
+    let error = KtxError::try_from(1000).expect("an unknown, but valid, error code");
+    assert_eq!(error, KtxError::Unknown(1000));
+    assert_eq!(u32::from(error), 1000);
+    assert_eq!(error.to_string(), "unknown KTX error (1000)");
+    assert_eq!(
+        KtxError::try_from(u32::from(KtxError::NotFound)),
+        Ok(KtxError::NotFound)
+    );
+}
+
+#[test]
+fn uastc_levels() {
+    assert_eq!(PackUastcFlags::default(), PackUastcFlags::LEVEL_DEFAULT);
+    assert_eq!(
+        PackUastcFlags::default().level(),
+        Some(PackUastcLevel::Default)
+    );
+
+    let flags = (PackUastcFlags::LEVEL_SLOWER | PackUastcFlags::FAVOR_BC7_ERROR)
+        .with_level(PackUastcLevel::Faster);
+    assert_eq!(
+        flags,
+        PackUastcFlags::LEVEL_FASTER | PackUastcFlags::FAVOR_BC7_ERROR
+    );
+    assert_eq!(flags.level(), Some(PackUastcLevel::Faster));
+    assert_eq!(PackUastcFlags::LEVEL_MASK.level(), None);
+}
+
+#[test]
+fn pvrtc_validation() {
+    let texture = Texture::new(
+        Ktx2CreateInfo::builder()
+            .dimensions(8, 8, 1)
+            .build()
+            .expect("valid creation info"),
+    )
+    .expect("a 8x8 KTX2 texture");
+    assert!(texture.is_power_of_two());
+    assert!(texture.is_square());
+    assert_eq!(texture.validate_for_pvrtc(), Ok(()));
+
+    let texture = Texture::new(
+        Ktx2CreateInfo::builder()
+            .dimensions(8, 6, 1)
+            .build()
+            .expect("valid creation info"),
+    )
+    .expect("a 8x6 KTX2 texture");
+    assert!(!texture.is_power_of_two());
+    assert!(!texture.is_square());
+    let err = texture
+        .validate_for_pvrtc()
+        .expect_err("a non-square, non-power-of-two texture");
+    assert_eq!(err.code(), KtxError::UnsupportedFeature);
+    assert!(err.to_string().contains("8x6x1"));
+}
+
+#[test]
+fn select_transcode_format() {
+    let mut texture = Texture::new(
+        Ktx2CreateInfo::builder()
+            .vk_format(VkFormat::R8G8B8A8Unorm)
+            .dimensions(8, 8, 1)
+            .build()
+            .expect("valid creation info"),
+    )
+    .expect("a KTX2 texture");
+    let ktx2 = texture.ktx2().expect("a KTX2 texture");
+    assert_eq!(
+        ktx2.select_transcode_format(GpuFeatures::all()),
+        TranscodeFormat::Astc4x4Rgba
+    );
+    assert_eq!(
+        ktx2.select_transcode_format(GpuFeatures::BC7 | GpuFeatures::ETC2),
+        TranscodeFormat::Bc7Rgba
+    );
+    assert_eq!(
+        ktx2.select_transcode_format(GpuFeatures::ETC2),
+        TranscodeFormat::Etc2Rgba
+    );
+    assert_eq!(
+        ktx2.select_transcode_format(GpuFeatures::PVRTC),
+        TranscodeFormat::Pvrtc14Rgba
+    );
+    assert_eq!(
+        ktx2.select_transcode_format(GpuFeatures::empty()),
+        TranscodeFormat::Rgba32
+    );
+}
+
+#[test]
+fn select_transcode_format_single_channel() {
+    let mut texture = Texture::new(
+        Ktx2CreateInfo::builder()
+            .vk_format(VkFormat::R8Unorm)
+            .dimensions(8, 8, 1)
+            .build()
+            .expect("valid creation info"),
+    )
+    .expect("a KTX2 texture");
+    let ktx2 = texture.ktx2().expect("a KTX2 texture");
+    assert_eq!(
+        ktx2.select_transcode_format(GpuFeatures::BC7 | GpuFeatures::ETC2),
+        TranscodeFormat::Bc4R
+    );
+    assert_eq!(
+        ktx2.select_transcode_format(GpuFeatures::ETC2),
+        TranscodeFormat::Etc2EacR11
+    );
+
+    // Two components are RG (not luminance + alpha) unless the texture is ETC1S
+    let mut texture = Texture::new(
+        Ktx2CreateInfo::builder()
+            .vk_format(VkFormat::R8G8Unorm)
+            .dimensions(8, 8, 1)
+            .build()
+            .expect("valid creation info"),
+    )
+    .expect("a KTX2 texture");
+    let ktx2 = texture.ktx2().expect("a KTX2 texture");
+    assert_eq!(
+        ktx2.select_transcode_format(GpuFeatures::BC7 | GpuFeatures::ETC2),
+        TranscodeFormat::Bc5Rg
+    );
+    assert_eq!(
+        ktx2.select_transcode_format(GpuFeatures::ETC2),
+        TranscodeFormat::Etc2EacRg11
+    );
+}
+
+#[test]
+fn preferred_for_components() {
+    use TranscodeFormat::*;
+    let table = [
+        (1, false, Bc4R, Etc2EacR11, Pvrtc14Rgb),
+        (2, false, Bc5Rg, Etc2EacRg11, Pvrtc14Rgb),
+        (2, true, Bc7Rgba, Etc2Rgba, Pvrtc14Rgba),
+        (3, false, Bc1Rgb, Etc1Rgb, Pvrtc14Rgb),
+        (4, true, Bc7Rgba, Etc2Rgba, Pvrtc14Rgba),
+    ];
+    for &(num_components, has_alpha, bc, etc, pvrtc) in table.iter() {
+        let preferred = |features| {
+            TranscodeFormat::preferred_for_components(num_components, has_alpha, features)
+        };
+        assert_eq!(preferred(GpuFeatures::BC7), bc);
+        assert_eq!(preferred(GpuFeatures::all()), bc);
+        assert_eq!(preferred(GpuFeatures::ETC2), etc);
+        assert_eq!(preferred(GpuFeatures::ASTC), Astc4x4Rgba);
+        assert_eq!(preferred(GpuFeatures::PVRTC), pvrtc);
+        assert_eq!(preferred(GpuFeatures::empty()), Rgba32);
+    }
+}
+
+#[test]
+fn transcode_progress_on_failure() {
+    let mut texture = Texture::new(Ktx2CreateInfo::default()).expect("a default KTX2 texture");
+    let mut calls = Vec::new();
+    let result = texture
+        .ktx2()
+        .expect("a KTX2 texture")
+        .transcode_basis_with_progress(
+            TranscodeFormat::Rgba32,
+            TranscodeFlags::empty(),
+            |done, total| calls.push((done, total)),
+        );
+    // Not a Basis Universal texture
+    assert!(result.is_err());
+    assert_eq!(calls, [(0, 1)]);
+}
+
+#[test]
+fn transcoded_image_size() {
+    let mut texture = Texture::new(
+        Ktx2CreateInfo::builder()
+            .dimensions(10, 6, 1)
+            .levels(4)
+            .build()
+            .expect("valid creation info"),
+    )
+    .expect("a mipmapped KTX2 texture");
+    let ktx2 = texture.ktx2().expect("a KTX2 texture");
+    assert_eq!(
+        ktx2.transcoded_image_size(0, TranscodeFormat::Rgba32),
+        Ok(240)
+    );
+    assert_eq!(
+        ktx2.transcoded_image_size(0, TranscodeFormat::Rgb565),
+        Ok(120)
+    );
+    // 10x6 -> 3x2 4x4 blocks
+    assert_eq!(
+        ktx2.transcoded_image_size(0, TranscodeFormat::Bc1Rgb),
+        Ok(48)
+    );
+    assert_eq!(
+        ktx2.transcoded_image_size(0, TranscodeFormat::Bc7Rgba),
+        Ok(96)
+    );
+    // 1x1 -> a single block, or the minimum 2x2 blocks for PVRTC1
+    assert_eq!(
+        ktx2.transcoded_image_size(3, TranscodeFormat::Bc7Rgba),
+        Ok(16)
+    );
+    assert_eq!(
+        ktx2.transcoded_image_size(3, TranscodeFormat::Pvrtc14Rgba),
+        Ok(32)
+    );
+    assert_eq!(
+        ktx2.transcoded_image_size(4, TranscodeFormat::Rgba32),
+        Err(KtxError::InvalidValue)
+    );
+    assert_eq!(
+        ktx2.transcoded_image_size(0, TranscodeFormat::Etc),
+        Err(KtxError::InvalidValue)
+    );
+}
+
+#[test]
+fn get_image_data() {
+    let mut texture = Texture::new(Ktx2CreateInfo::default()).expect("a default KTX2 texture");
+
+    texture
+        .get_image_data_mut(0, 0, 0)
+        .expect("the only image in the texture")
+        .copy_from_slice(&[1, 2, 3, 4]);
+    assert_eq!(texture.get_image_data(0, 0, 0), Ok(&[1u8, 2, 3, 4][..]));
+    assert_eq!(texture.data(), &[1, 2, 3, 4]);
+
+    assert_eq!(texture.get_image_data(1, 0, 0), Err(KtxError::InvalidValue));
+    assert_eq!(texture.get_image_data(0, 1, 0), Err(KtxError::InvalidValue));
+    assert_eq!(texture.get_image_data(0, 0, 1), Err(KtxError::InvalidValue));
+}
+
+#[test]
+fn iterate_levels_error() {
+    let mut texture = Texture::new(
+        Ktx2CreateInfo::builder()
+            .dimensions(4, 4, 1)
+            .levels(3)
+            .build()
+            .expect("valid creation info"),
+    )
+    .expect("a mipmapped KTX2 texture");
+
+    let mut calls = 0;
+    let result = texture.iterate_levels(|_, _, _, _, _, _| {
+        calls += 1;
+        Err(KtxError::OutOfMemory)
+    });
+    assert_eq!(result, Err(KtxError::OutOfMemory));
+    assert_eq!(calls, 1);
+
+    let result = texture.iterate_levels_mut(|mip, _, _, _, _, _| {
+        if mip == 1 {
+            Err(KtxError::NotFound)
+        } else {
+            Ok(())
+        }
+    });
+    assert_eq!(result, Err(KtxError::NotFound));
+}
+
+#[test]
+fn data_not_allocated() {
+    let mut texture = Texture::new(
+        Ktx2CreateInfo::builder()
+            .storage(CreateStorage::NoStorage)
+            .dimensions(4, 4, 1)
+            .build()
+            .expect("valid creation info"),
+    )
+    .expect("a KTX2 texture without storage");
+    assert_eq!(texture.data_checked(), None);
+    assert!(texture.data().is_empty());
+    assert!(texture.data_mut().is_empty());
+
+    let texture = Texture::new(Ktx2CreateInfo::default()).expect("a default KTX2 texture");
+    assert_eq!(texture.data_checked().map(|data| data.len()), Some(4));
+}
+
+#[test]
+fn into_data() {
+    let mut texture = Texture::new(Ktx2CreateInfo::default()).expect("a default KTX2 texture");
+    texture.data_mut().copy_from_slice(&[1, 2, 3, 4]);
+    assert_eq!(texture.into_data(), vec![1, 2, 3, 4]);
+}
+
+#[test]
+fn levels() {
+    let texture = Texture::new(Ktx2CreateInfo {
+        common: CommonCreateInfo {
+            base_width: 4,
+            base_height: 2,
+            num_levels: 3,
+            num_layers: 2,
+            is_array: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .expect("a mipmapped KTX2 texture array");
+
+    let images: Vec<_> = texture.levels().collect();
+    assert_eq!(images.len(), 6);
+    let dims: Vec<_> = images
+        .iter()
+        .map(|image| (image.level, image.layer, image.width, image.height))
+        .collect();
+    assert_eq!(
+        dims,
+        [
+            (0, 0, 4, 2),
+            (0, 1, 4, 2),
+            (1, 0, 2, 1),
+            (1, 1, 2, 1),
+            (2, 0, 1, 1),
+            (2, 1, 1, 1)
+        ]
+    );
+    for image in &images {
+        assert_eq!(image.data.len(), image.width * image.height * 4);
+    }
+}
+
+#[test]
+fn debug_format() {
+    let texture = Texture::new(Ktx1CreateInfo::default()).expect("a default KTX1 texture");
+    let debug = format!("{:?}", texture);
+    assert!(debug.starts_with("Texture {"));
+    assert!(debug.contains("class: \"KTX1\""));
+    assert!(debug.contains("data_size: 4"));
+}
+
+#[test]
+fn texture_class() {
+    let ktx1 = Texture::new(Ktx1CreateInfo::default()).expect("a default KTX1 texture");
+    assert_eq!(ktx1.class(), TextureClass::Ktx1);
+    let ktx2 = Texture::new(Ktx2CreateInfo::default()).expect("a default KTX2 texture");
+    assert_eq!(ktx2.class(), TextureClass::Ktx2);
+}
+
+#[test]
+fn format_info() {
+    let mut texture = Texture::new(
+        Ktx2CreateInfo::builder()
+            .vk_format(VkFormat::R16G16B16A16Sfloat)
+            .build()
+            .expect("valid creation info"),
+    )
+    .expect("a KTX2 texture");
+    assert_eq!(
+        texture.ktx2().expect("a KTX2 texture").format_info(),
+        FormatInfo {
+            bytes_per_block: 8,
+            block_width: 1,
+            block_height: 1,
+            is_compressed: false,
+        }
+    );
+
+    let mut texture = Texture::new(
+        Ktx2CreateInfo::builder()
+            .vk_format(VkFormat::Bc7UnormBlock)
+            .dimensions(8, 8, 1)
+            .build()
+            .expect("valid creation info"),
+    )
+    .expect("a BC7 KTX2 texture");
+    assert_eq!(
+        texture.ktx2().expect("a KTX2 texture").format_info(),
+        FormatInfo {
+            bytes_per_block: 16,
+            block_width: 4,
+            block_height: 4,
+            is_compressed: true,
+        }
+    );
+}
+
+#[test]
+fn flip_y() {
+    let mut texture = Texture::new(
+        Ktx2CreateInfo::builder()
+            .vk_format(VkFormat::R8Unorm)
+            .dimensions(4, 3, 1)
+            .build()
+            .expect("valid creation info"),
+    )
+    .expect("a KTX2 texture");
+    texture
+        .data_mut()
+        .copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]);
+    let orientation_y = texture.orientation().y;
+    texture.flip_y().expect("flipping an uncompressed texture");
+    assert_eq!(texture.data(), &[9, 10, 11, 12, 5, 6, 7, 8, 1, 2, 3, 4]);
+    assert_ne!(texture.orientation().y, orientation_y);
+    texture.flip_y().expect("flipping the texture back");
+    assert_eq!(texture.orientation().y, orientation_y);
+
+    let mut texture = Texture::new(
+        Ktx2CreateInfo::builder()
+            .vk_format(VkFormat::Bc7UnormBlock)
+            .dimensions(8, 8, 1)
+            .build()
+            .expect("valid creation info"),
+    )
+    .expect("a BC7 KTX2 texture");
+    assert_eq!(texture.flip_y(), Err(KtxError::UnsupportedFeature));
+}
+
+#[test]
+fn swizzle() {
+    let mut texture = Texture::new(
+        Ktx2CreateInfo::builder()
+            .dimensions(2, 1, 1)
+            .build()
+            .expect("valid creation info"),
+    )
+    .expect("a RGBA8 KTX2 texture");
+    texture
+        .data_mut()
+        .copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+    texture
+        .swizzle([Channel::B, Channel::G, Channel::R, Channel::A])
+        .expect("swizzling BGRA to RGBA");
+    assert_eq!(texture.data(), &[3, 2, 1, 4, 7, 6, 5, 8]);
+    texture
+        .swizzle([Channel::R, Channel::R, Channel::Zero, Channel::One])
+        .expect("broadcasting red");
+    assert_eq!(texture.data(), &[3, 3, 0, 255, 7, 7, 0, 255]);
+
+    // KTX1 rows are padded to 4 bytes, which must be skipped
+    let mut texture = Texture::new(Ktx1CreateInfo {
+        gl_internal_format: 0x8051, // GL_RGB8
+        common: CommonCreateInfo {
+            base_width: 1,
+            base_height: 2,
+            num_dimensions: 2,
+            ..Default::default()
+        },
+    })
+    .expect("a RGB8 KTX1 texture");
+    texture
+        .data_mut()
+        .copy_from_slice(&[1, 2, 3, 0, 4, 5, 6, 0]);
+    texture
+        .swizzle([Channel::B, Channel::G, Channel::R, Channel::A])
+        .expect("swizzling BGR to RGB");
+    assert_eq!(texture.data(), &[3, 2, 1, 0, 6, 5, 4, 0]);
+    assert_eq!(
+        texture.swizzle([Channel::A, Channel::G, Channel::B, Channel::R]),
+        Err(KtxError::InvalidValue)
+    );
+
+    // Every face of every layer of a cubemap array must be swizzled
+    let mut texture = Texture::new(
+        Ktx2CreateInfo::builder()
+            .dimensions(2, 2, 1)
+            .layers(2)
+            .array(true)
+            .faces(6)
+            .build()
+            .expect("valid creation info"),
+    )
+    .expect("a RGBA8 KTX2 cubemap array");
+    texture
+        .data_mut()
+        .chunks_exact_mut(4)
+        .for_each(|element| element.copy_from_slice(&[1, 2, 3, 4]));
+    texture
+        .swizzle([Channel::B, Channel::G, Channel::R, Channel::A])
+        .expect("swizzling a cubemap array");
+    assert_eq!(
+        texture.data(),
+        [3, 2, 1, 4].repeat(2 * 2 * 2 * 6).as_slice()
+    );
+
+    let mut texture = Texture::new(
+        Ktx2CreateInfo::builder()
+            .vk_format(VkFormat::Bc7UnormBlock)
+            .dimensions(8, 8, 1)
+            .build()
+            .expect("valid creation info"),
+    )
+    .expect("a BC7 KTX2 texture");
+    assert_eq!(
+        texture.swizzle([Channel::R, Channel::G, Channel::B, Channel::A]),
+        Err(KtxError::UnsupportedFeature)
+    );
+}
+
+#[test]
+fn num_components() {
+    let ktx1 = Texture::new(Ktx1CreateInfo::default()).expect("a default KTX1 texture");
+    assert_eq!(ktx1.num_components(), Ok(4));
+    let ktx2 = Texture::new(
+        Ktx2CreateInfo::builder()
+            .vk_format(VkFormat::R8G8Unorm)
+            .build()
+            .expect("valid creation info"),
+    )
+    .expect("a KTX2 texture");
+    assert_eq!(ktx2.num_components(), Ok(2));
+    assert_eq!(GlFormat::Rgb.num_components(), Some(3));
+    assert_eq!(GlFormat::Other(0).num_components(), None);
+}
+
+#[test]
+fn texture_info() {
+    let texture = Texture::new(
+        Ktx2CreateInfo::builder()
+            .vk_format(VkFormat::R8G8B8A8Srgb)
+            .dimensions(8, 4, 1)
+            .levels(4)
+            .build()
+            .expect("valid creation info"),
+    )
+    .expect("a KTX2 texture");
+    let info = texture.info();
+    assert_eq!(info.class, "KTX2");
+    assert_eq!(info.num_dimensions, 2);
+    assert_eq!(info.format, "R8G8B8A8Srgb");
+    assert_eq!(info.num_levels, 4);
+    assert!(!info.is_compressed);
+    assert_eq!(info.supercompression_scheme, SuperCompressionScheme::None);
+    assert_eq!(info.data_size, texture.data_size());
+
+    let table = info.to_string();
+    assert!(table.contains("class:            KTX2\n"));
+    assert!(table.contains("dimensions:       8x4x1 (2D)\n"));
+
+    let texture = Texture::new(Ktx1CreateInfo::default()).expect("a default KTX1 texture");
+    assert_eq!(texture.info().class, "KTX1");
+}
+
+#[test]
+fn extents() {
+    let texture = Texture::new(Ktx2CreateInfo {
+        common: CommonCreateInfo {
+            base_width: 8,
+            base_height: 4,
+            base_depth: 2,
+            num_dimensions: 3,
+            num_levels: 4,
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .expect("a mipmapped 3D KTX2 texture");
+
+    let extent = |width, height, depth| Extent3d {
+        width,
+        height,
+        depth,
+    };
+    assert_eq!(texture.dimensions(), extent(8, 4, 2));
+    assert_eq!(texture.extent_at_level(0), texture.dimensions());
+    assert_eq!(texture.extent_at_level(1), extent(4, 2, 1));
+    assert_eq!(texture.extent_at_level(2), extent(2, 1, 1));
+    assert_eq!(texture.extent_at_level(3), extent(1, 1, 1));
+    assert_eq!(texture.extent_at_level(100), extent(1, 1, 1));
+}
+
+#[test]
+fn total_image_count() {
+    let texture = Texture::new(Ktx2CreateInfo {
+        common: CommonCreateInfo {
+            base_width: 8,
+            base_height: 4,
+            base_depth: 4,
+            num_dimensions: 3,
+            num_levels: 4,
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .expect("a mipmapped 3D KTX2 texture");
+    assert_eq!(texture.total_image_count(), 4 + 2 + 1 + 1);
+    let mut num_callbacks = 0;
+    texture
+        .iterate_levels(|_mip, _face, _width, _height, _depth, _pixel_data| {
+            num_callbacks += 1;
+            Ok(())
+        })
+        .expect("mip/face read-only iteration to succeed");
+    assert_eq!(num_callbacks, 4);
+
+    let texture = Texture::new(
+        Ktx2CreateInfo::builder()
+            .dimensions(4, 4, 1)
+            .levels(2)
+            .layers(3)
+            .array(true)
+            .faces(6)
+            .build()
+            .expect("valid creation info"),
+    )
+    .expect("a mipmapped KTX2 cubemap array");
+    assert_eq!(texture.total_image_count(), 2 * 3 * 6);
+}
+
+#[test]
+fn generate_mipmaps() {
+    let mut texture = Texture::new(
+        Ktx2CreateInfo::builder()
+            .vk_format(VkFormat::R8G8B8A8Unorm)
+            .dimensions(4, 2, 1)
+            .build()
+            .expect("valid creation info"),
+    )
+    .expect("a KTX2 texture");
+    texture
+        .set_metadata("MyKey", b"MyValue")
+        .expect("setting a metadata key");
+    for (i, byte) in texture.data_mut().iter_mut().enumerate() {
+        *byte = (i % 4) as u8 * 10 + (i / 16) as u8;
+    }
+
+    texture.generate_mipmaps().expect("generating mipmaps");
+    assert_eq!(texture.num_levels(), 3);
+    assert_eq!(texture.metadata().get("MyKey"), Some(&b"MyValue"[..]));
+    assert_eq!(
+        texture.get_image_data(1, 0, 0).map(|data| data.len()),
+        Ok(8)
+    );
+    // Each texel of level 2 averages a 2x1 block of level 1, which in turn averages 2x2 blocks of level 0
+    assert_eq!(texture.get_image_data(2, 0, 0), Ok(&[1u8, 11, 21, 31][..]));
+}
+
+#[test]
+fn generate_mipmaps_compressed() {
+    let mut texture = Texture::new(Ktx2CreateInfo {
+        vk_format: VkFormat::Bc1RgbUnormBlock,
+        common: CommonCreateInfo {
+            base_width: 4,
+            base_height: 4,
+            num_dimensions: 2,
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .expect("a BC1 KTX2 texture");
+    assert_eq!(
+        texture.generate_mipmaps(),
+        Err(KtxError::UnsupportedFeature)
+    );
+}
+
+#[test]
+fn try_clone() {
+    let mut texture = Texture::new(Ktx2CreateInfo::default()).expect("a default KTX2 texture");
+    texture.data_mut().copy_from_slice(&[1, 2, 3, 4]);
+    texture
+        .set_metadata("MyKey", b"MyValue")
+        .expect("setting a metadata key");
+
+    let mut clone = texture.try_clone().expect("a clone of the texture");
+    assert_eq!(clone.data(), &[1, 2, 3, 4]);
+    assert_eq!(clone.metadata().get("MyKey"), Some(&b"MyValue"[..]));
+
+    clone.data_mut().copy_from_slice(&[5, 6, 7, 8]);
+    clone
+        .set_metadata("MyKey", b"Changed")
+        .expect("overwriting a metadata key");
+    assert_eq!(texture.data(), &[1, 2, 3, 4]);
+    assert_eq!(texture.metadata().get("MyKey"), Some(&b"MyValue"[..]));
+
+    texture
+        .ktx2()
+        .expect("a KTX2 texture")
+        .deflate_zstd(5)
+        .expect("deflating the texture");
+    assert_eq!(
+        texture.try_clone().map(|_| ()),
+        Err(KtxError::UnsupportedFeature)
+    );
+}
+
+#[test]
+fn orientations_from_str() {
+    let orientations: Orientations = "rdi".parse().expect("a KTX2 KTXorientation");
+    assert_eq!(orientations.to_string(), "rdi");
+    assert_eq!(
+        orientations,
+        Orientations {
+            x: OrientationX::Right,
+            y: OrientationY::Down,
+            z: OrientationZ::In,
+        }
+    );
+    assert_eq!(
+        "S=l,T=u".parse::<Orientations>().map(|o| o.to_string()),
+        Ok("luo".to_string())
+    );
+    assert!("rdx".parse::<Orientations>().is_err());
+    assert!("".parse::<Orientations>().is_err());
+}
+
+#[test]
+fn set_orientation() {
+    let mut texture = Texture::new(
+        Ktx2CreateInfo::builder()
+            .dimensions(2, 2, 1)
+            .build()
+            .expect("valid creation info"),
+    )
+    .expect("a 2D KTX2 texture");
+    let orientation = "ru".parse().expect("a KTXorientation");
+    texture
+        .set_orientation(orientation)
+        .expect("setting the orientation");
+    assert_eq!(texture.orientation(), orientation);
+    assert_eq!(texture.metadata().get("KTXorientation"), Some(&b"ru\0"[..]));
+}
+
+#[test]
+fn is_srgb() {
+    let mut texture = Texture::new(Ktx2CreateInfo::default()).expect("a default KTX2 texture");
+    assert!(!texture.is_srgb());
+    let ktx2 = texture.ktx2().expect("a KTX2 texture");
+    assert_eq!(ktx2.transfer_function(), TransferFunction::Linear);
+
+    let mut texture = Texture::new(Ktx2CreateInfo {
+        vk_format: VkFormat::R8G8B8A8Srgb,
+        ..Default::default()
+    })
+    .expect("a sRGB KTX2 texture");
+    assert!(texture.is_srgb());
+    let ktx2 = texture.ktx2().expect("a KTX2 texture");
+    assert_eq!(ktx2.transfer_function(), TransferFunction::Srgb);
+
+    let texture = Texture::new(
+        Ktx1CreateInfo::builder()
+            .gl_internal_format(GlInternalFormat::Srgb8Alpha8)
+            .build()
+            .expect("valid creation info"),
+    )
+    .expect("a sRGB KTX1 texture");
+    assert!(texture.is_srgb());
+}
+
+#[test]
+fn cubemap_faces() {
+    let mut texture = Texture::new(
+        Ktx2CreateInfo::builder()
+            .vk_format(VkFormat::R8G8B8A8Unorm)
+            .dimensions(2, 2, 1)
+            .levels(2)
+            .faces(6)
+            .build()
+            .expect("valid creation info"),
+    )
+    .expect("a KTX2 cubemap");
+    for face in 0..6 {
+        texture
+            .get_image_data_mut(0, 0, face)
+            .expect("the face's image data")
+            .fill(face as u8);
+    }
+
+    let faces: Vec<_> = texture
+        .faces(0, 0)
+        .collect::<Result<_, _>>()
+        .expect("the faces' image data");
+    assert_eq!(faces.len(), 6);
+    for (face, data) in faces.iter().enumerate() {
+        assert_eq!(*data, &[face as u8; 2 * 2 * 4][..]);
+    }
+    assert_eq!(texture.faces(1, 0).count(), 6);
+    assert!(texture
+        .faces(2, 0)
+        .all(|face| face == Err(KtxError::InvalidValue)));
+
+    let texture = Texture::new(Ktx2CreateInfo::default()).expect("a default KTX2 texture");
+    let faces: Vec<_> = texture.faces(0, 0).collect();
+    assert_eq!(faces, [Err(KtxError::InvalidOperation)]);
+}
+
+#[test]
+fn image_offsets() {
+    let texture = Texture::new(
+        Ktx2CreateInfo::builder()
+            .dimensions(2, 2, 1)
+            .faces(6)
+            .build()
+            .expect("valid creation info"),
+    )
+    .expect("a KTX2 cubemap");
+    assert_eq!(texture.image_offset_2d(0, 0, 0), Ok(0));
+    assert_eq!(texture.image_offset_2d(0, 0, 5), Ok(5 * 16));
+    assert_eq!(
+        texture.image_offset_2d(0, 0, 6),
+        Err(KtxError::InvalidValue)
+    );
+    assert_eq!(
+        texture.image_offset_2d(1, 0, 0),
+        Err(KtxError::InvalidValue)
+    );
+    assert_eq!(
+        texture.image_offset_3d(0, 0, 1),
+        Err(KtxError::InvalidValue)
+    );
+
+    let texture = Texture::new(
+        Ktx2CreateInfo::builder()
+            .dimensions(2, 2, 4)
+            .build()
+            .expect("valid creation info"),
+    )
+    .expect("a 3D KTX2 texture");
+    assert_eq!(texture.image_offset_3d(0, 0, 3), Ok(3 * 16));
+    assert_eq!(
+        texture.image_offset_3d(0, 0, 4),
+        Err(KtxError::InvalidValue)
+    );
+    assert_eq!(
+        texture.image_offset_2d(0, 0, 1),
+        Err(KtxError::InvalidValue)
+    );
+}
+
+#[test]
+fn transcode_format_blocks() {
+    use TranscodeFormat::*;
+    let table = [
+        (Etc1Rgb, Some((4, 4)), Some(8)),
+        (Etc2Rgba, Some((4, 4)), Some(16)),
+        (Bc1Rgb, Some((4, 4)), Some(8)),
+        (Bc3Rgba, Some((4, 4)), Some(16)),
+        (Bc4R, Some((4, 4)), Some(8)),
+        (Bc5Rg, Some((4, 4)), Some(16)),
+        (Bc7Rgba, Some((4, 4)), Some(16)),
+        (Pvrtc14Rgb, Some((4, 4)), Some(8)),
+        (Pvrtc14Rgba, Some((4, 4)), Some(8)),
+        (Astc4x4Rgba, Some((4, 4)), Some(16)),
+        (Pvrtc24Rgb, Some((4, 4)), Some(8)),
+        (Pvrtc24Rgba, Some((4, 4)), Some(8)),
+        (Etc2EacR11, Some((4, 4)), Some(8)),
+        (Etc2EacRg11, Some((4, 4)), Some(16)),
+        (Rgba32, None, None),
+        (Rgb565, None, None),
+        (Bgr565, None, None),
+        (Rgba4444, None, None),
+        (Etc, Some((4, 4)), None),
+        (Bc1or3, Some((4, 4)), None),
+        (NoSelection, None, None),
+    ];
+    for &(format, block_dimensions, bytes_per_block) in table.iter() {
+        assert_eq!(format.block_dimensions(), block_dimensions, "{:?}", format);
+        assert_eq!(format.bytes_per_block(), bytes_per_block, "{:?}", format);
+        assert_eq!(
+            format.is_uncompressed(),
+            matches!(format, Rgba32 | Rgb565 | Bgr565 | Rgba4444),
+            "{:?}",
+            format
+        );
+    }
+}
+
+#[test]
+fn transcode_format_roundtrip() {
+    use std::convert::TryFrom;
+    use TranscodeFormat::*;
+    let formats = [
+        Etc1Rgb,
+        Etc2Rgba,
+        Bc1Rgb,
+        Bc3Rgba,
+        Bc4R,
+        Bc5Rg,
+        Bc7Rgba,
+        Pvrtc14Rgb,
+        Pvrtc14Rgba,
+        Astc4x4Rgba,
+        Pvrtc24Rgb,
+        Pvrtc24Rgba,
+        Etc2EacR11,
+        Etc2EacRg11,
+        Rgba32,
+        Rgb565,
+        Bgr565,
+        Rgba4444,
+        Etc,
+        Bc1or3,
+        NoSelection,
+    ];
+    for &format in formats.iter() {
+        assert_eq!(TranscodeFormat::try_from(format as u32), Ok(format));
+    }
+    assert_eq!(
+        TranscodeFormat::try_from(libktx_rs::sys::ktx_transcode_fmt_e_KTX_TTF_BC4_R),
+        Ok(Bc4R)
+    );
+    assert!(TranscodeFormat::try_from(7).is_err());
+}
+
+#[test]
+fn open_missing_file() {
+    let result = Texture::from_file("this/file/does/not/exist.ktx2", TextureCreateFlags::empty());
+    let err = result.expect_err("opening a missing file to fail");
+    assert_eq!(err.code(), KtxError::FileOpenFailed);
+    assert_eq!(
+        err.context(),
+        Some("FileSource::create_texture(this/file/does/not/exist.ktx2)")
+    );
+    assert!(err
+        .to_string()
+        .starts_with("FileSource::create_texture(this/file/does/not/exist.ktx2) failed: "));
+}
+
+#[cfg(feature = "gl")]
+#[test]
+fn gl_upload_without_data() {
+    let mut texture = Texture::new(Ktx1CreateInfo {
+        common: CommonCreateInfo {
+            create_storage: CreateStorage::NoStorage,
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .expect("a KTX1 texture without storage");
+    let ktx1 = texture.ktx1().expect("a KTX1 texture");
+    assert_eq!(ktx1.gl_upload().err(), Some(KtxError::InvalidOperation));
+}
+
+#[cfg(feature = "vulkan")]
+#[test]
+fn vk_upload_without_data() {
+    let texture = Texture::new(Ktx1CreateInfo {
+        common: CommonCreateInfo {
+            create_storage: CreateStorage::NoStorage,
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .expect("a KTX1 texture without storage");
+    // The texture is rejected before the device info is ever used, so a dummy one will do.
+    // SAFETY: A device info is just a (here, null) pointer; it is never dropped, which would destroy it.
+    let device_info = std::mem::ManuallyDrop::new(unsafe {
+        std::mem::zeroed::<libktx_rs::vulkan::VkDeviceInfo>()
+    });
+    assert_eq!(
+        texture.vk_upload(&device_info, 0, 0, 0).err(),
+        Some(KtxError::InvalidOperation)
+    );
+}
+
+#[test]
+fn ktx_error_into_io_error() {
+    let io_error: std::io::Error = KtxError::FileUnexpectedEof.into();
+    assert_eq!(io_error.kind(), std::io::ErrorKind::UnexpectedEof);
+    assert_eq!(
+        io_error.to_string(),
+        KtxError::FileUnexpectedEof.to_string()
+    );
+
+    let io_error: std::io::Error = KtxError::TranscodeFailed.into();
+    assert_eq!(io_error.kind(), std::io::ErrorKind::Other);
+    assert_eq!(
+        io_error
+            .into_inner()
+            .and_then(|inner| inner.downcast::<KtxError>().ok()),
+        Some(Box::new(KtxError::TranscodeFailed))
+    );
 }
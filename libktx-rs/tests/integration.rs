@@ -0,0 +1,180 @@
+// Copyright (C) 2021 Paolo Jovon <paolo.jovon@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+#[cfg(all(feature = "image", feature = "write"))]
+mod image {
+    use image::RgbaImage;
+    use libktx_rs::{sources::Ktx2CreateInfo, KtxError, Texture, VkFormat};
+
+    #[test]
+    fn rgba8_image_roundtrip() {
+        let img = RgbaImage::from_fn(4, 2, |x, y| image::Rgba([x as u8, y as u8, 10, 255]));
+        let mut texture = Texture::from_rgba8_image(&img, true).expect("a KTX2 from an image");
+        assert_eq!(texture.data(), img.as_raw().as_slice());
+
+        let ktx2 = texture.ktx2().expect("a KTX2 texture");
+        assert_eq!(ktx2.vk_format(), VkFormat::R8G8B8A8Srgb);
+        let roundtrip = ktx2
+            .to_rgba8_image(0, 0, 0)
+            .expect("an image from the KTX2");
+        assert_eq!(roundtrip, img);
+    }
+
+    #[test]
+    fn to_rgba8_image_unsupported_format() {
+        let mut texture = Texture::new(
+            Ktx2CreateInfo::builder()
+                .vk_format(VkFormat::R8G8B8Unorm)
+                .build()
+                .expect("valid creation info"),
+        )
+        .expect("a KTX2 texture");
+        let ktx2 = texture.ktx2().expect("a KTX2 texture");
+        assert_eq!(
+            ktx2.to_rgba8_image(0, 0, 0),
+            Err(KtxError::UnsupportedFeature)
+        );
+    }
+}
+
+#[cfg(feature = "wgpu")]
+mod wgpu {
+    use libktx_rs::{
+        integration::wgpu::wgpu_texture_format,
+        sources::{CommonCreateInfo, Ktx2CreateInfo},
+        Texture, VkFormat,
+    };
+
+    #[test]
+    fn texture_formats() {
+        assert_eq!(
+            wgpu_texture_format(VkFormat::R8G8B8A8Srgb),
+            Some(wgpu::TextureFormat::Rgba8UnormSrgb)
+        );
+        assert_eq!(
+            wgpu_texture_format(VkFormat::Bc7UnormBlock),
+            Some(wgpu::TextureFormat::Bc7RgbaUnorm)
+        );
+        assert_eq!(wgpu_texture_format(VkFormat::R8G8B8Unorm), None);
+        assert_eq!(wgpu_texture_format(VkFormat::Pvrtc1Bpp4UnormBlockImg), None);
+    }
+
+    #[test]
+    fn cubemap_array_descriptor() {
+        let mut texture = Texture::new(Ktx2CreateInfo {
+            vk_format: VkFormat::R8G8B8A8Unorm,
+            common: CommonCreateInfo {
+                base_width: 8,
+                base_height: 8,
+                num_dimensions: 2,
+                num_levels: 4,
+                num_layers: 2,
+                num_faces: 6,
+                is_array: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .expect("a KTX2 cubemap array");
+        let descriptor = texture
+            .ktx2()
+            .expect("a KTX2 texture")
+            .wgpu_texture_descriptor()
+            .expect("a wgpu descriptor");
+        assert_eq!(descriptor.dimension, wgpu::TextureDimension::D2);
+        assert_eq!(descriptor.format, wgpu::TextureFormat::Rgba8Unorm);
+        assert_eq!(descriptor.mip_level_count, 4);
+        assert_eq!(
+            descriptor.size,
+            wgpu::Extent3d {
+                width: 8,
+                height: 8,
+                depth_or_array_layers: 12
+            }
+        );
+    }
+
+    #[test]
+    fn array_1d_descriptor() {
+        let mut texture = Texture::new(Ktx2CreateInfo {
+            vk_format: VkFormat::R8G8B8A8Unorm,
+            common: CommonCreateInfo {
+                base_width: 8,
+                num_dimensions: 1,
+                num_layers: 3,
+                is_array: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .expect("a KTX2 1D array texture");
+        // wgpu has no 1D arrays; the layers must not be silently dropped
+        assert!(texture
+            .ktx2()
+            .expect("a KTX2 texture")
+            .wgpu_texture_descriptor()
+            .is_none());
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde {
+    use libktx_rs::{
+        sources::{CommonCreateInfo, Ktx2CreateInfo},
+        texture::AstcParams,
+        CreateStorage, PackAstcBlockDimension, TextureCreateFlags, TranscodeFormat, VkFormat,
+    };
+
+    #[test]
+    fn create_info_roundtrip() {
+        let info = Ktx2CreateInfo {
+            vk_format: VkFormat::Bc7SrgbBlock,
+            dfd: Some(vec![1, 2, 3]),
+            common: CommonCreateInfo {
+                create_storage: CreateStorage::NoStorage,
+                base_width: 16,
+                base_height: 16,
+                num_dimensions: 2,
+                ..Default::default()
+            },
+        };
+        let json = serde_json::to_string(&info).expect("serializing a Ktx2CreateInfo");
+        let roundtrip: Ktx2CreateInfo =
+            serde_json::from_str(&json).expect("deserializing a Ktx2CreateInfo");
+        assert_eq!(roundtrip, info);
+    }
+
+    #[test]
+    fn partial_config() {
+        let info: Ktx2CreateInfo = serde_json::from_str(
+            r#"{ "vk_format": "R8G8B8A8Srgb", "common": { "base_width": 4, "base_height": 4 } }"#,
+        )
+        .expect("deserializing a partial Ktx2CreateInfo");
+        assert_eq!(info.vk_format, VkFormat::R8G8B8A8Srgb);
+        assert_eq!(info.dfd, None);
+        assert_eq!(info.common.base_width, 4);
+        assert_eq!(
+            info.common.num_levels,
+            CommonCreateInfo::default().num_levels
+        );
+
+        let params: AstcParams = serde_json::from_str(r#"{ "block_dimension": "Dim6x6" }"#)
+            .expect("deserializing partial AstcParams");
+        assert_eq!(params.block_dimension, PackAstcBlockDimension::Dim6x6);
+        assert_eq!(params.input_swizzle, ['r', 'g', 'b', 'a']);
+    }
+
+    #[test]
+    fn enums_and_flags() {
+        let format: TranscodeFormat =
+            serde_json::from_str(r#""Bc7Rgba""#).expect("deserializing a TranscodeFormat");
+        assert_eq!(format, TranscodeFormat::Bc7Rgba);
+
+        let flags = TextureCreateFlags::LOAD_IMAGE_DATA | TextureCreateFlags::SKIP_KVDATA;
+        let json = serde_json::to_string(&flags).expect("serializing TextureCreateFlags");
+        assert_eq!(
+            serde_json::from_str::<TextureCreateFlags>(&json).expect("deserializing flags"),
+            flags
+        );
+    }
+}
@@ -4,8 +4,9 @@
 #[cfg(feature = "test-images")]
 mod test_images {
     use libktx_rs::{
-        enums::TranscodeFormat, sources::StreamSource, RustKtxStream, Texture, TextureCreateFlags,
-        TranscodeFlags,
+        enums::{TextureClass, TranscodeFormat},
+        sources::StreamSource,
+        RustKtxStream, Texture, TextureCreateFlags, TranscodeFlags,
     };
     use libktx_rs_macros::file_tests;
     use std::{
@@ -14,7 +15,7 @@ mod test_images {
         sync::{Arc, Mutex},
     };
 
-    fn from_stream(_path: PathBuf, file: File) {
+    fn from_stream(path: PathBuf, file: File) {
         let stream = RustKtxStream::new(Box::new(file)).expect("the Rust ktxStream");
         let source = StreamSource::new(
             Arc::new(Mutex::new(stream)),
@@ -30,6 +31,23 @@ mod test_images {
             panic!("The loaded texture should be either KTX1 or KTX2!");
         }
 
+        let metadata = stream_texture.metadata();
+        if stream_texture.class() == TextureClass::Ktx2 {
+            // All KTX2 test images were written by a tool that identifies itself
+            let writer = metadata.get("KTXwriter").expect("a KTXwriter entry");
+            assert!(!writer.is_empty());
+            // KTX2 orientations are e.g. `rd`, one letter per dimension
+            if let Some(orientation) = metadata.get("KTXorientation") {
+                assert!(matches!(orientation.first(), Some(b'r') | Some(b'l')));
+            }
+        }
+        // Raw KVData must be parsed to the very same entries
+        let entries: Vec<_> = metadata.iter().collect();
+        let raw_texture = Texture::from_file(&path, TextureCreateFlags::RAW_KVDATA)
+            .expect("the KTX with raw KVData");
+        let raw_entries: Vec<_> = raw_texture.metadata().iter().collect();
+        assert_eq!(entries, raw_entries);
+
         dbg!(
             stream_texture.data_size(),
             stream_texture.element_size(),
@@ -39,8 +57,15 @@ mod test_images {
         if let Some(mut ktx2) = stream_texture.ktx2() {
             if ktx2.needs_transcoding() {
                 println!("This KTX2 needs transcoding");
-                ktx2.transcode_basis(TranscodeFormat::Rgba32, TranscodeFlags::empty())
-                    .expect("transcoding to work");
+                let mut calls = Vec::new();
+                ktx2.transcode_basis_with_progress(
+                    TranscodeFormat::Rgba32,
+                    TranscodeFlags::empty(),
+                    |done, total| calls.push((done, total)),
+                )
+                .expect("transcoding to work");
+                let num_levels = stream_texture.num_levels() as u32;
+                assert_eq!(calls, [(0, num_levels), (num_levels, num_levels)]);
             }
         }
 
@@ -59,10 +84,76 @@ mod test_images {
             .expect("mip/face read-write iteration to succeed");
     }
 
-    // FIXME: These glob patterns assume that `cargo build` is invoked from the root of the workspace!
-    file_tests! {from_stream =>
-        "libktx-rs-sys/build/KTX-Software/tests/testimages/*.ktx*",
+    fn from_file(path: PathBuf, _file: File) {
+        let texture =
+            Texture::from_file(&path, TextureCreateFlags::LOAD_IMAGE_DATA).expect("the loaded KTX");
+        assert_eq!(texture.data_size(), texture.data().len());
+    }
+
+    fn metadata_entries(path: PathBuf, _file: File) {
+        let texture =
+            Texture::from_file(&path, TextureCreateFlags::empty()).expect("the loaded KTX");
+        let metadata = texture.metadata();
+        let entries: Vec<_> = texture.metadata_iter().collect();
+        assert_eq!(entries.len(), metadata.iter().count());
+        for (key, value) in entries {
+            dbg!(&key, value.len());
+            assert!(!key.is_empty());
+            assert_eq!(metadata.get(&key), Some(value));
+        }
+    }
+
+    fn total_image_count(path: PathBuf, _file: File) {
+        let mut texture =
+            Texture::from_file(&path, TextureCreateFlags::LOAD_IMAGE_DATA).expect("the loaded KTX");
+        if let Some(mut ktx2) = texture.ktx2() {
+            if ktx2.needs_transcoding() {
+                ktx2.transcode_basis(TranscodeFormat::Rgba32, TranscodeFlags::empty())
+                    .expect("transcoding to work");
+            }
+        }
+
+        let mut num_images = 0;
+        texture
+            .iterate_levels(|mip, _face, _width, _height, _depth, pixel_data| {
+                let image_size = texture.get_image_size(mip as u32)?;
+                assert_eq!(pixel_data.len() % image_size, 0);
+                num_images += pixel_data.len() / image_size;
+                Ok(())
+            })
+            .expect("mip/face read-only iteration to succeed");
+        assert_eq!(num_images, texture.total_image_count());
+    }
+
+    fn cubemap_faces(path: PathBuf, _file: File) {
+        let texture =
+            Texture::from_file(&path, TextureCreateFlags::empty()).expect("the loaded KTX");
+        assert!(texture.is_cubemap());
+        assert_eq!(texture.num_faces(), 6);
+    }
+
+    file_tests! {from_stream => relative_to: "$CARGO_MANIFEST_DIR",
+        "../libktx-rs-sys/build/KTX-Software/tests/testimages/*.ktx*",
         // This one has a unsupported image type, skip
-        !"libktx-rs-sys/build/KTX-Software/tests/testimages/luminance-reference-metadata.ktx",
+        ignore: "../libktx-rs-sys/build/KTX-Software/tests/testimages/luminance-reference-metadata.ktx",
+    }
+
+    file_tests! {from_file => relative_to: "$CARGO_MANIFEST_DIR",
+        "../libktx-rs-sys/build/KTX-Software/tests/testimages/*.ktx*",
+        // ...but it must still be rejected cleanly
+        should_panic("the loaded KTX"): "../libktx-rs-sys/build/KTX-Software/tests/testimages/luminance-reference-metadata.ktx",
+    }
+
+    file_tests! {metadata_entries => relative_to: "$CARGO_MANIFEST_DIR",
+        "../libktx-rs-sys/build/KTX-Software/tests/testimages/*.ktx2",
+    }
+
+    file_tests! {total_image_count => relative_to: "$CARGO_MANIFEST_DIR",
+        "../libktx-rs-sys/build/KTX-Software/tests/testimages/*.ktx*",
+        !"../libktx-rs-sys/build/KTX-Software/tests/testimages/luminance-reference-metadata.ktx",
+    }
+
+    file_tests! {cubemap_faces => relative_to: "$CARGO_MANIFEST_DIR",
+        "../libktx-rs-sys/build/KTX-Software/tests/testimages/cubemap*.ktx*",
     }
 }
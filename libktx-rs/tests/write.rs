@@ -5,15 +5,121 @@
 mod write {
     use libktx_rs::{
         sinks::StreamSink,
-        sources::{Ktx1CreateInfo, Ktx2CreateInfo, StreamSource},
-        RustKtxStream, Texture, TextureCreateFlags,
+        sources::{
+            BufferedSource, Ktx1CreateInfo, Ktx2CreateInfo, MemorySource, RawDataSource,
+            StreamSource,
+        },
+        texture::{BasisParams, DEFAULT_WRITER},
+        KtxError, RustKtxStream, SuperCompressionScheme, Texture, TextureClass, TextureCreateFlags,
+        TranscodeFlags, TranscodeFormat, VkFormat,
     };
     use std::{
-        io::{Cursor, Seek, SeekFrom},
-        sync::{Arc, Mutex},
+        cell::RefCell,
+        convert::TryFrom,
+        io::{Cursor, ErrorKind, Read, Seek, SeekFrom, Write},
+        rc::Rc,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc, Mutex,
+        },
     };
 
-    fn write_and_check(texture: &Texture) {
+    /// A [`Cursor`] that counts how many times it is dropped.
+    struct DropCounter {
+        cursor: Cursor<Vec<u8>>,
+        drops: Arc<AtomicUsize>,
+    }
+
+    impl DropCounter {
+        fn new(bytes: Vec<u8>) -> (Self, Arc<AtomicUsize>) {
+            let drops = Arc::new(AtomicUsize::new(0));
+            let counter = DropCounter {
+                cursor: Cursor::new(bytes),
+                drops: drops.clone(),
+            };
+            (counter, drops)
+        }
+    }
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            self.drops.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    impl Read for DropCounter {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.cursor.read(buf)
+        }
+    }
+
+    impl Write for DropCounter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.cursor.write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            self.cursor.flush()
+        }
+    }
+
+    impl Seek for DropCounter {
+        fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+            self.cursor.seek(pos)
+        }
+    }
+
+    /// A stream that refuses to be read from.
+    struct DeniedReader;
+
+    impl Read for DeniedReader {
+        fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+            Err(ErrorKind::PermissionDenied.into())
+        }
+    }
+
+    impl Write for DeniedReader {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Seek for DeniedReader {
+        fn seek(&mut self, _pos: SeekFrom) -> std::io::Result<u64> {
+            Ok(0)
+        }
+    }
+
+    /// A stream that can not seek, like a pipe.
+    struct UnseekableReader;
+
+    impl Read for UnseekableReader {
+        fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+            Ok(0)
+        }
+    }
+
+    impl Write for UnseekableReader {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Seek for UnseekableReader {
+        fn seek(&mut self, _pos: SeekFrom) -> std::io::Result<u64> {
+            Err(ErrorKind::Unsupported.into())
+        }
+    }
+
+    fn write_and_check(texture: &Texture) -> Texture<'static> {
         let cursor: Cursor<Vec<u8>> = Cursor::new(Vec::new());
         let stream = RustKtxStream::new(Box::new(cursor)).expect("a ktxStream over a io::Cursor");
         let arc_stream = Arc::new(Mutex::new(stream));
@@ -28,15 +134,17 @@ mod write {
         // Rewind the stream
         {
             let mut stream_lock = arc_stream.lock().expect("Poisoned stream lock");
-            stream_lock
-                .inner_mut()
-                .seek(SeekFrom::Start(0))
-                .expect("rewinding the io::Cursor");
+            let position = stream_lock.position().expect("the io::Cursor's position");
+            let written_len = stream_lock.stream_len().expect("the io::Cursor's length");
+            assert!(written_len > 0);
+            // `stream_len()` must not move the stream
+            assert_eq!(stream_lock.position().ok(), Some(position));
+            stream_lock.rewind().expect("rewinding the io::Cursor");
         }
 
         let source = StreamSource::new(arc_stream.clone(), TextureCreateFlags::LOAD_IMAGE_DATA);
         let written_texture = Texture::new(source);
-        written_texture.expect("reading the same KTX back from the cursor");
+        written_texture.expect("reading the same KTX back from the cursor")
     }
 
     #[test]
@@ -45,9 +153,748 @@ mod write {
         write_and_check(&texture);
     }
 
+    #[test]
+    fn write_ktx1_as_ktx2() {
+        let mut texture = Texture::new(Ktx1CreateInfo::default()).expect("a default KTX1 texture");
+        let cursor: Cursor<Vec<u8>> = Cursor::new(Vec::new());
+        let stream = RustKtxStream::new(Box::new(cursor)).expect("a ktxStream over a io::Cursor");
+        let arc_stream = Arc::new(Mutex::new(stream));
+        texture
+            .ktx1()
+            .expect("a KTX1 texture")
+            .write_ktx2_to_stream(&mut StreamSink::new(arc_stream.clone()))
+            .expect("writing a KTX1 as KTX2 to io::Cursor");
+
+        arc_stream
+            .lock()
+            .expect("Poisoned stream lock")
+            .rewind()
+            .expect("rewinding the io::Cursor");
+        let source = StreamSource::new(arc_stream.clone(), TextureCreateFlags::LOAD_IMAGE_DATA);
+        let mut written_texture = Texture::new(source).expect("reading the KTX2 back");
+        assert!(written_texture.ktx2().is_some());
+    }
+
+    #[test]
+    fn write_metadata_roundtrip() {
+        let mut texture = Texture::new(Ktx2CreateInfo::default()).expect("a default KTX2 texture");
+        texture
+            .set_metadata("MyProvenance", b"first")
+            .expect("setting a metadata key");
+        texture
+            .set_metadata("MyProvenance", b"libktx-rs tests")
+            .expect("overwriting a metadata key");
+        texture
+            .set_metadata("ToBeDeleted", b"")
+            .expect("setting another metadata key");
+        texture
+            .delete_metadata("ToBeDeleted")
+            .expect("deleting a metadata key");
+
+        let written_texture = write_and_check(&texture);
+        let metadata = written_texture.metadata();
+        assert_eq!(metadata.get("MyProvenance"), Some(&b"libktx-rs tests"[..]));
+        assert_eq!(metadata.get("ToBeDeleted"), None);
+        assert_eq!(
+            metadata
+                .iter()
+                .filter(|(key, _)| *key == b"MyProvenance")
+                .count(),
+            1
+        );
+        assert!(written_texture
+            .metadata_iter()
+            .any(|(key, value)| key == "MyProvenance" && value == b"libktx-rs tests"));
+    }
+
+    #[test]
+    fn serialized_len() {
+        let mut texture = Texture::new(Ktx2CreateInfo::default()).expect("a default KTX2 texture");
+        texture
+            .set_metadata("MyProvenance", b"libktx-rs tests")
+            .expect("setting a metadata key");
+        let bytes = texture.write_to_memory().expect("writing a KTX to memory");
+        assert_eq!(texture.serialized_len(), Ok(bytes.len()));
+        assert!(bytes.len() > texture.data_size());
+
+        let texture = Texture::new(Ktx1CreateInfo::default()).expect("a default KTX1 texture");
+        let bytes = texture.write_to_memory().expect("writing a KTX to memory");
+        assert_eq!(texture.serialized_len(), Ok(bytes.len()));
+    }
+
+    #[test]
+    fn write_writer_metadata() {
+        let mut texture = Texture::new(Ktx2CreateInfo::default()).expect("a default KTX2 texture");
+        assert_eq!(
+            texture.set_writer("bad\0writer"),
+            Err(KtxError::InvalidValue)
+        );
+        texture
+            .set_writer(DEFAULT_WRITER)
+            .expect("setting the writer metadata");
+
+        let written_texture = write_and_check(&texture);
+        let expected = format!("{}\0", DEFAULT_WRITER);
+        assert!(DEFAULT_WRITER.starts_with("libktx-rs "));
+        assert_eq!(
+            written_texture.metadata().get("KTXwriter"),
+            Some(expected.as_bytes())
+        );
+    }
+
+    #[test]
+    fn zstd_roundtrip() {
+        let mut texture = Texture::new(
+            Ktx2CreateInfo::builder()
+                .dimensions(16, 16, 1)
+                .build()
+                .expect("valid creation info"),
+        )
+        .expect("a KTX2 texture");
+        for (i, byte) in texture.data_mut().iter_mut().enumerate() {
+            *byte = (i % 7) as u8;
+        }
+        texture
+            .set_metadata("MyKey", b"MyValue")
+            .expect("setting a metadata key");
+        let original = texture.data().to_vec();
+
+        let mut ktx2 = texture.ktx2().expect("a KTX2 texture");
+        assert_eq!(
+            ktx2.inflate_zstd().map_err(|err| err.code()),
+            Err(KtxError::InvalidOperation)
+        );
+        ktx2.deflate_zstd(5).expect("deflating the texture");
+        assert_eq!(ktx2.supercompression_scheme(), SuperCompressionScheme::ZStd);
+        assert!(ktx2.is_zstd());
+        assert!(!ktx2.is_basis_compressed());
+        ktx2.inflate_zstd().expect("inflating the texture");
+        assert_eq!(ktx2.supercompression_scheme(), SuperCompressionScheme::None);
+        assert!(!ktx2.is_zstd());
+
+        assert_eq!(texture.data(), &original[..]);
+        assert_eq!(texture.metadata().get("MyKey"), Some(&b"MyValue"[..]));
+    }
+
     #[test]
     fn write_default_ktx2() {
         let texture = Texture::new(Ktx2CreateInfo::default()).expect("a default KTX2 texture");
         write_and_check(&texture);
     }
+
+    #[test]
+    fn read_back_from_memory() {
+        let texture = Texture::new(Ktx2CreateInfo::default()).expect("a default KTX2 texture");
+
+        let cursor: Cursor<Vec<u8>> = Cursor::new(Vec::new());
+        let stream = RustKtxStream::new(Box::new(cursor)).expect("a ktxStream over a io::Cursor");
+        let arc_stream = Arc::new(Mutex::new(stream));
+        texture
+            .write_to(&mut StreamSink::new(arc_stream.clone()))
+            .expect("writing a KTX to io::Cursor");
+        let bytes = arc_stream
+            .lock()
+            .expect("Poisoned stream lock")
+            .inner()
+            .get_ref()
+            .clone();
+
+        let source = MemorySource::new(&bytes, TextureCreateFlags::LOAD_IMAGE_DATA);
+        let read_texture = Texture::new(source).expect("reading the same KTX back from memory");
+        assert_eq!(read_texture.data(), texture.data());
+    }
+
+    #[test]
+    fn write_to_memory() {
+        let texture = Texture::new(Ktx1CreateInfo::default()).expect("a default KTX1 texture");
+        let bytes = texture.write_to_memory().expect("writing a KTX to memory");
+        assert!(!bytes.is_empty());
+
+        let source = MemorySource::new(&bytes, TextureCreateFlags::LOAD_IMAGE_DATA);
+        let read_texture = Texture::new(source).expect("reading the same KTX back from memory");
+        assert_eq!(read_texture.data(), texture.data());
+    }
+
+    #[test]
+    fn detect_format() {
+        let ktx1 = Texture::new(Ktx1CreateInfo::default()).expect("a default KTX1 texture");
+        let ktx1_bytes = ktx1.write_to_memory().expect("writing a KTX1 to memory");
+        assert_eq!(
+            libktx_rs::detect_format(&ktx1_bytes),
+            Some(TextureClass::Ktx1)
+        );
+
+        let ktx2 = Texture::new(Ktx2CreateInfo::default()).expect("a default KTX2 texture");
+        let ktx2_bytes = ktx2.write_to_memory().expect("writing a KTX2 to memory");
+        assert_eq!(
+            libktx_rs::detect_format(&ktx2_bytes),
+            Some(TextureClass::Ktx2)
+        );
+
+        assert_eq!(libktx_rs::detect_format(&ktx2_bytes[..11]), None);
+        assert_eq!(libktx_rs::detect_format(b"not a KTX file"), None);
+
+        let path = std::env::temp_dir().join("libktx-rs-detect-format.ktx2");
+        std::fs::write(&path, &ktx2_bytes).expect("writing a KTX2 file");
+        let detected = libktx_rs::detect_file_format(&path);
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(
+            detected.expect("reading the KTX2 file"),
+            Some(TextureClass::Ktx2)
+        );
+    }
+
+    #[test]
+    fn generate_mipmaps_on_load() {
+        let texture = Texture::new(
+            Ktx2CreateInfo::builder()
+                .vk_format(VkFormat::R8G8B8A8Unorm)
+                .dimensions(8, 8, 1)
+                .build()
+                .expect("valid creation info"),
+        )
+        .expect("a KTX2 texture");
+        let bytes = texture.write_to_memory().expect("writing a KTX to memory");
+
+        let source =
+            StreamSource::from_read_seekable(Cursor::new(bytes), TextureCreateFlags::empty())
+                .expect("a stream source over a io::Cursor")
+                .generate_mipmaps_on_load(true);
+        let read_texture = Texture::new(source).expect("reading the KTX back with mipmaps");
+        assert_eq!(read_texture.num_levels(), 4);
+    }
+
+    #[test]
+    fn write_to_writer() {
+        let texture = Texture::new(Ktx2CreateInfo::default()).expect("a default KTX2 texture");
+        // `Vec<u8>` is `Write`, but not `Seek`
+        let mut bytes = Vec::new();
+        texture
+            .write_to_writer(&mut bytes)
+            .expect("writing a KTX to a Vec");
+        assert_eq!(
+            bytes,
+            texture.write_to_memory().expect("writing a KTX to memory")
+        );
+
+        // A slice can't grow, so it fails once full
+        let mut too_small = [0u8; 4];
+        let err = texture
+            .write_to_writer(&mut &mut too_small[..])
+            .expect_err("writing a KTX to a 4-byte slice");
+        assert_eq!(err.code(), KtxError::FileWriteError);
+        let context = err.context().expect("a context for the error");
+        assert!(context.starts_with("Texture::write_to_writer: "));
+        assert_ne!(context, "Texture::write_to_writer: ");
+    }
+
+    #[test]
+    fn raw_data_source() {
+        let info = Ktx2CreateInfo::builder()
+            .vk_format(VkFormat::R8G8B8A8Unorm)
+            .dimensions(2, 2, 1)
+            .levels(2)
+            .build()
+            .expect("valid creation info");
+        let images = vec![(0, 0, 0, vec![1u8; 16]), (1, 0, 0, vec![2u8; 4])];
+
+        let texture = Texture::new(RawDataSource::new(info.clone(), images.clone()))
+            .expect("a texture from raw data");
+        assert_eq!(texture.get_image_data(0, 0, 0), Ok(&[1u8; 16][..]));
+        assert_eq!(texture.get_image_data(1, 0, 0), Ok(&[2u8; 4][..]));
+
+        let missing = vec![images[0].clone()];
+        let duplicate = vec![images[0].clone(), images[0].clone()];
+        let wrong_size = vec![images[0].clone(), (1, 0, 0, vec![2u8; 3])];
+        let out_of_range = vec![images[0].clone(), (1, 1, 0, vec![2u8; 4])];
+        for images in [missing, duplicate, wrong_size, out_of_range].iter() {
+            let err = Texture::new(RawDataSource::new(info.clone(), images.clone()))
+                .expect_err("invalid raw images");
+            assert_eq!(err.code(), KtxError::InvalidValue);
+        }
+    }
+
+    #[test]
+    fn sorted_metadata_is_deterministic() {
+        let keys = ["Zebra", "Apple", "Mango"];
+        let write_with_keys = |keys: &[&str]| {
+            let mut texture =
+                Texture::new(Ktx1CreateInfo::default()).expect("a default KTX1 texture");
+            for key in keys {
+                texture
+                    .set_metadata(key, b"value")
+                    .expect("setting a metadata key");
+            }
+            texture.sort_metadata().expect("sorting metadata");
+            let sorted_keys: Vec<_> = texture.metadata().iter().map(|(key, _)| key).collect();
+            assert_eq!(sorted_keys, [&b"Apple"[..], b"Mango", b"Zebra"]);
+            texture.write_to_memory().expect("writing a KTX to memory")
+        };
+
+        let reversed: Vec<_> = keys.iter().rev().copied().collect();
+        assert_eq!(write_with_keys(&keys), write_with_keys(&reversed));
+    }
+
+    #[test]
+    fn load_transcoded() {
+        let info = Ktx2CreateInfo::builder()
+            .dimensions(4, 4, 1)
+            .build()
+            .expect("a valid KTX2 create info");
+        let mut texture = Texture::new(info).expect("a 4x4 KTX2 texture");
+        texture
+            .ktx2()
+            .expect("a KTX2 texture")
+            .compress_basis_ex(BasisParams {
+                uastc: true,
+                thread_count: 1,
+                ..Default::default()
+            })
+            .expect("UASTC compression to succeed");
+        let bytes = texture.write_to_memory().expect("writing a KTX to memory");
+
+        // Image data is not loaded by the source itself
+        let mut loaded = Texture::load_transcoded(
+            MemorySource::new(&bytes, TextureCreateFlags::empty()),
+            TranscodeFormat::Rgba32,
+            TranscodeFlags::empty(),
+        )
+        .expect("loading and transcoding the KTX");
+        assert!(loaded.data_checked().is_some());
+        let ktx2 = loaded.ktx2().expect("a KTX2 texture");
+        assert!(!ktx2.needs_transcoding());
+        assert_eq!(ktx2.vk_format(), VkFormat::R8G8B8A8Unorm);
+
+        // KTX1s are just loaded
+        let texture = Texture::new(Ktx1CreateInfo::default()).expect("a default KTX1 texture");
+        let bytes = texture.write_to_memory().expect("writing a KTX to memory");
+        let loaded = Texture::load_transcoded(
+            MemorySource::new(&bytes, TextureCreateFlags::empty()),
+            TranscodeFormat::Rgba32,
+            TranscodeFlags::empty(),
+        )
+        .expect("loading the KTX");
+        assert_eq!(loaded.data(), texture.data());
+    }
+
+    #[test]
+    fn read_from_non_seekable() {
+        let texture = Texture::new(Ktx2CreateInfo::default()).expect("a default KTX2 texture");
+        let bytes = texture.write_to_memory().expect("writing a KTX to memory");
+
+        // `&[u8]` is `Read`, but not `Seek`
+        let source = BufferedSource::new(&bytes[..], TextureCreateFlags::LOAD_IMAGE_DATA);
+        let read_texture = Texture::new(source).expect("reading the same KTX back from a reader");
+        assert_eq!(read_texture.data(), texture.data());
+
+        let source = BufferedSource::new(DeniedReader, TextureCreateFlags::empty());
+        let err = Texture::new(source).expect_err("reading from a DeniedReader");
+        assert_eq!(err.code(), KtxError::FileReadError);
+        let io_err = std::io::Error::from(ErrorKind::PermissionDenied);
+        assert_eq!(
+            err.context(),
+            Some(format!("BufferedSource::create_texture: {}", io_err).as_str())
+        );
+
+        let source = BufferedSource::new(&b"not a KTX"[..], TextureCreateFlags::empty());
+        let err = Texture::new(source).expect_err("reading garbage as a KTX");
+        assert_eq!(
+            err.context(),
+            Some("BufferedSource::create_texture: OwnedMemorySource::create_texture")
+        );
+    }
+
+    #[test]
+    fn load_level() {
+        let info = Ktx2CreateInfo::builder()
+            .vk_format(VkFormat::R8G8B8A8Unorm)
+            .dimensions(4, 4, 1)
+            .levels(3)
+            .build()
+            .expect("a valid KTX2 create info");
+        let mut texture = Texture::new(info).expect("a mipmapped 4x4 KTX2 texture");
+        texture
+            .iterate_levels_mut(|mip, _face, _width, _height, _depth, pixel_data| {
+                pixel_data.fill(mip as u8 + 1);
+                Ok(())
+            })
+            .expect("mip/face read-write iteration to succeed");
+        let bytes = texture.write_to_memory().expect("writing a KTX to memory");
+
+        let mut loaded = Texture::new(MemorySource::new(&bytes, TextureCreateFlags::empty()))
+            .expect("reading a KTX from memory");
+        assert!(loaded.data_checked().is_none());
+        let mut ktx2 = loaded.ktx2().expect("a KTX2 texture");
+        assert_eq!(ktx2.load_level(2).expect("the 1x1 level"), vec![3; 4]);
+        assert_eq!(ktx2.load_level(0).expect("the 4x4 level"), vec![1; 64]);
+        assert_eq!(ktx2.load_level(1).expect("the 2x2 level"), vec![2; 16]);
+        assert_eq!(
+            ktx2.load_level(2).expect("the 1x1 level, again"),
+            vec![3; 4]
+        );
+        let err = ktx2.load_level(3).expect_err("an out-of-range level");
+        assert_eq!(err.code(), KtxError::InvalidValue);
+        // Levels are read on their own, leaving the rest of the texture loadable as usual
+        loaded.load_image_data().expect("loading the whole texture");
+        let offset = loaded
+            .get_image_offset(0, 0, 0)
+            .expect("the offset of the 4x4 level");
+        assert_eq!(loaded.data()[offset..offset + 64], [1; 64]);
+
+        let mut ktx2 = texture.ktx2().expect("a KTX2 texture");
+        ktx2.deflate_zstd(5).expect("deflating the texture");
+        let bytes = texture.write_to_memory().expect("writing a KTX to memory");
+
+        let mut loaded = Texture::new(MemorySource::new(&bytes, TextureCreateFlags::empty()))
+            .expect("reading a KTX from memory");
+        let err = loaded
+            .ktx2()
+            .expect("a KTX2 texture")
+            .load_level(0)
+            .expect_err("ZStandard data to need inflating");
+        assert_eq!(err.code(), KtxError::UnsupportedFeature);
+        let mut levels = Vec::new();
+        loaded
+            .iterate_load_level_faces(|mip, _face, _width, _height, _depth, pixel_data| {
+                levels.push((mip, pixel_data.to_vec()));
+                Ok(())
+            })
+            .expect("all levels to be read in one pass");
+        assert_eq!(
+            levels,
+            vec![(2, vec![3; 4]), (1, vec![2; 16]), (0, vec![1; 64])]
+        );
+        assert_eq!(loaded.load_image_data(), Err(KtxError::InvalidOperation));
+
+        let mut loaded = Texture::new(MemorySource::new(
+            &bytes,
+            TextureCreateFlags::LOAD_IMAGE_DATA,
+        ))
+        .expect("loading a KTX from memory");
+        let err = loaded
+            .ktx2()
+            .expect("a KTX2 texture")
+            .load_level(0)
+            .expect_err("image data to already be loaded");
+        assert_eq!(err.code(), KtxError::InvalidOperation);
+    }
+
+    #[test]
+    fn stream_last_io_error() {
+        let stream =
+            RustKtxStream::from_value(DeniedReader).expect("a ktxStream over a DeniedReader");
+        assert!(stream.last_io_error().is_none());
+        let rc_stream = Rc::new(RefCell::new(stream));
+        let source = StreamSource::new(rc_stream.clone(), TextureCreateFlags::LOAD_IMAGE_DATA);
+        let err = Texture::new(source).expect_err("reading from a DeniedReader");
+        assert_eq!(err.code(), KtxError::FileReadError);
+
+        let mut stream = rc_stream.borrow_mut();
+        assert_eq!(
+            stream.last_io_error().map(std::io::Error::kind),
+            Some(ErrorKind::PermissionDenied)
+        );
+        let io_err = stream.take_last_io_error().expect("the last I/O error");
+        assert_eq!(io_err.kind(), ErrorKind::PermissionDenied);
+        assert!(stream.last_io_error().is_none());
+    }
+
+    #[test]
+    fn stream_not_seekable() {
+        let mut stream = RustKtxStream::from_value(UnseekableReader)
+            .expect("a ktxStream over a UnseekableReader");
+        let ktx_stream = stream.ktx_stream();
+        let mut size = 0;
+        // SAFETY: Safe - `ktx_stream` is a valid Rust-based ktxStream, which outlives this call.
+        let err = unsafe {
+            let getsize_fn = (*ktx_stream).getsize.expect("a getsize function");
+            (getsize_fn)(ktx_stream, &mut size)
+        };
+        assert_eq!(KtxError::try_from(err), Ok(KtxError::FileIsPipe));
+        let io_err = stream.take_last_io_error().expect("the last I/O error");
+        assert_eq!(io_err.kind(), ErrorKind::Unsupported);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn stream_over_pipe() {
+        use std::{fs::File, os::unix::io::FromRawFd};
+
+        let mut fds = [0; 2];
+        // SAFETY: Safe - `fds` has room for both ends of the pipe.
+        assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+        // SAFETY: Safe - both file descriptors were just opened, and are owned by nothing else.
+        let (read_end, _write_end) =
+            unsafe { (File::from_raw_fd(fds[0]), File::from_raw_fd(fds[1])) };
+
+        let mut stream = RustKtxStream::from_value(read_end).expect("a ktxStream over a pipe");
+        let ktx_stream = stream.ktx_stream();
+        let mut size = 0;
+        // SAFETY: Safe - `ktx_stream` is a valid Rust-based ktxStream, which outlives this call.
+        let err = unsafe {
+            let getsize_fn = (*ktx_stream).getsize.expect("a getsize function");
+            (getsize_fn)(ktx_stream, &mut size)
+        };
+        assert_eq!(KtxError::try_from(err), Ok(KtxError::FileIsPipe));
+        let io_err = stream.take_last_io_error().expect("the last I/O error");
+        assert_eq!(io_err.raw_os_error(), Some(libc::ESPIPE));
+    }
+
+    #[test]
+    fn stream_drops_inner_once() {
+        let (counter, drops) = DropCounter::new(Vec::new());
+        let stream = RustKtxStream::from_value(counter).expect("a ktxStream over a DropCounter");
+        assert_eq!(drops.load(Ordering::SeqCst), 0);
+        drop(stream);
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn stream_into_inner_after_failed_read() {
+        let (counter, drops) = DropCounter::new(b"not a KTX".to_vec());
+        let stream = RustKtxStream::from_value(counter).expect("a ktxStream over a DropCounter");
+        let arc_stream = Arc::new(Mutex::new(stream));
+        let source = StreamSource::new(arc_stream.clone(), TextureCreateFlags::LOAD_IMAGE_DATA);
+        Texture::new(source).expect_err("reading garbage as a KTX");
+
+        let stream = Arc::try_unwrap(arc_stream)
+            .expect("the source to have released the stream")
+            .into_inner()
+            .expect("an unpoisoned stream lock");
+        let counter = stream.into_inner();
+        assert_eq!(drops.load(Ordering::SeqCst), 0);
+        assert_eq!(counter.cursor.get_ref(), b"not a KTX");
+        drop(counter);
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn stream_into_inner_after_successful_read() {
+        let texture = Texture::new(Ktx2CreateInfo::default()).expect("a default KTX2 texture");
+        let bytes = texture.write_to_memory().expect("writing a KTX to memory");
+
+        let (counter, drops) = DropCounter::new(bytes);
+        let stream = RustKtxStream::from_value(counter).expect("a ktxStream over a DropCounter");
+        let rc_stream = Rc::new(RefCell::new(stream));
+        // Without loading image data, the texture keeps reading from the stream
+        let source = StreamSource::new(rc_stream.clone(), TextureCreateFlags::empty());
+        let read_texture = Texture::new(source).expect("reading a KTX from the stream");
+        read_texture
+            .load_image_data()
+            .expect("loading image data from the stream");
+        assert_eq!(read_texture.data(), texture.data());
+        drop(read_texture);
+        assert_eq!(drops.load(Ordering::SeqCst), 0);
+
+        let counter = Rc::try_unwrap(rc_stream)
+            .expect("the texture to have released the stream")
+            .into_inner()
+            .into_inner();
+        assert_eq!(drops.load(Ordering::SeqCst), 0);
+        drop(counter);
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn poisoned_stream_lock() {
+        let texture = Texture::new(Ktx2CreateInfo::default()).expect("a default KTX2 texture");
+        let cursor: Cursor<Vec<u8>> = Cursor::new(Vec::new());
+        let stream = RustKtxStream::new(Box::new(cursor)).expect("a ktxStream over a io::Cursor");
+        let arc_stream = Arc::new(Mutex::new(stream));
+
+        // Panic while holding the lock, poisoning it
+        let poisoner = arc_stream.clone();
+        let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _guard = poisoner.lock().unwrap();
+            panic!("poisoning the stream lock");
+        }));
+        assert!(arc_stream.is_poisoned());
+
+        let err = texture
+            .write_to(&mut StreamSink::new(arc_stream.clone()))
+            .expect_err("writing to a poisoned stream");
+        assert_eq!(err.code(), KtxError::InvalidOperation);
+        let err = Texture::new(StreamSource::new(
+            arc_stream,
+            TextureCreateFlags::LOAD_IMAGE_DATA,
+        ))
+        .expect_err("reading from a poisoned stream");
+        assert_eq!(err.code(), KtxError::InvalidOperation);
+    }
+
+    #[test]
+    fn read_from_read_only_stream() {
+        let texture = Texture::new(Ktx2CreateInfo::default()).expect("a default KTX2 texture");
+        let bytes = texture.write_to_memory().expect("writing a KTX to memory");
+
+        // `Cursor<&[u8]>` is `Read + Seek`, but not `Write`
+        let cursor = Cursor::new(&bytes[..]);
+        let stream = RustKtxStream::new_read_only(Box::new(cursor))
+            .expect("a read-only ktxStream over a io::Cursor");
+        let source = StreamSource::new(
+            Arc::new(Mutex::new(stream)),
+            TextureCreateFlags::LOAD_IMAGE_DATA,
+        );
+        let read_texture = Texture::new(source).expect("reading a KTX from a read-only stream");
+        assert_eq!(read_texture.data(), texture.data());
+    }
+
+    #[test]
+    fn stream_from_rw_seekable() {
+        let texture = Texture::new(Ktx2CreateInfo::default()).expect("a default KTX2 texture");
+        let mut sink = StreamSink::from_rw_seekable(Cursor::new(Vec::new()))
+            .expect("a stream sink over a io::Cursor");
+        texture
+            .write_to(&mut sink)
+            .expect("writing a KTX to io::Cursor");
+        let bytes = sink
+            .try_unwrap()
+            .expect("the unshared io::Cursor")
+            .into_inner();
+
+        let source = StreamSource::from_read_seekable(
+            Cursor::new(&bytes[..]),
+            TextureCreateFlags::LOAD_IMAGE_DATA,
+        )
+        .expect("a stream source over a io::Cursor");
+        let read_texture = Texture::new(source).expect("reading the KTX back from the cursor");
+        assert_eq!(read_texture.data(), texture.data());
+    }
+
+    #[test]
+    fn unsynchronized_streams() {
+        let texture = Texture::new(Ktx2CreateInfo::default()).expect("a default KTX2 texture");
+        let stream = RustKtxStream::new(Box::new(Cursor::new(Vec::new())))
+            .expect("a ktxStream over a io::Cursor");
+        let mut sink = StreamSink::new(stream);
+        texture
+            .write_to(&mut sink)
+            .expect("writing a KTX to io::Cursor");
+        let bytes = sink.into_inner().into_inner().into_inner();
+
+        let stream = RustKtxStream::new_read_only(Box::new(Cursor::new(&bytes[..])))
+            .expect("a ktxStream over a io::Cursor");
+        let shared_stream = Rc::new(RefCell::new(stream));
+        let source = StreamSource::new(shared_stream.clone(), TextureCreateFlags::LOAD_IMAGE_DATA);
+        let read_texture = Texture::new(source).expect("reading the KTX back from the cursor");
+        assert_eq!(read_texture.data(), texture.data());
+        assert_eq!(shared_stream.borrow().inner().get_ref(), &&bytes[..]);
+    }
+
+    #[test]
+    fn stream_from_value() {
+        let texture = Texture::new(Ktx2CreateInfo::default()).expect("a default KTX2 texture");
+        let stream = RustKtxStream::from_value(Cursor::new(Vec::new()))
+            .expect("a ktxStream over a io::Cursor");
+        let mut sink = StreamSink::new(stream);
+        texture
+            .write_to(&mut sink)
+            .expect("writing a KTX to io::Cursor");
+        let bytes = sink.into_inner().into_inner().into_inner();
+        assert_eq!(
+            bytes,
+            texture.write_to_memory().expect("writing a KTX to memory")
+        );
+    }
+
+    #[test]
+    fn write_to_file() {
+        let texture = Texture::new(Ktx2CreateInfo::default()).expect("a default KTX2 texture");
+        let path = std::env::temp_dir().join("libktx-rs-write_to_file.ktx2");
+        texture
+            .write_to_file(&path)
+            .expect("writing a KTX to a file");
+
+        let read_texture = Texture::from_file(&path, TextureCreateFlags::LOAD_IMAGE_DATA)
+            .expect("reading the same KTX back from the file");
+        assert_eq!(read_texture.data(), texture.data());
+        std::fs::remove_file(&path).expect("removing the written file");
+    }
+
+    #[test]
+    fn read_header_only() {
+        let mut texture = Texture::new(
+            Ktx2CreateInfo::builder()
+                .vk_format(VkFormat::R8G8B8A8Srgb)
+                .dimensions(8, 4, 1)
+                .levels(2)
+                .build()
+                .expect("valid creation info"),
+        )
+        .expect("a KTX2 texture");
+        texture.data_mut().fill(42);
+        texture
+            .ktx2()
+            .expect("a KTX2 texture")
+            .deflate_zstd(3)
+            .expect("deflating the texture");
+        let path = std::env::temp_dir().join("libktx-rs-read_header_only.ktx2");
+        texture
+            .write_to_file(&path)
+            .expect("writing a KTX to a file");
+
+        let mut header = Texture::read_header(&path).expect("reading the KTX header");
+        assert_eq!((header.base_width(), header.base_height()), (8, 4));
+        assert_eq!(header.num_levels(), 2);
+        assert_eq!(header.data_checked(), None);
+        {
+            let ktx2 = header.ktx2().expect("a KTX2 texture");
+            assert_eq!(ktx2.vk_format(), VkFormat::R8G8B8A8Srgb);
+            assert_eq!(ktx2.supercompression_scheme(), SuperCompressionScheme::ZStd);
+        }
+
+        header.load_image_data().expect("loading the image data");
+        assert!(header.data().iter().all(|&byte| byte == 42));
+        std::fs::remove_file(&path).expect("removing the written file");
+    }
+
+    #[test]
+    fn write_to_missing_dir() {
+        let texture = Texture::new(Ktx2CreateInfo::default()).expect("a default KTX2 texture");
+        let path = std::env::temp_dir().join("libktx-rs-missing-dir/texture.ktx2");
+        assert_eq!(
+            texture.write_to_file(&path).map_err(|err| err.code()),
+            Err(KtxError::FileWriteError)
+        );
+    }
+
+    #[test]
+    fn send_owned_texture() {
+        let texture = Texture::new(Ktx2CreateInfo::default()).expect("a default KTX2 texture");
+        let written_texture = write_and_check(&texture);
+        let mut owned_texture = written_texture
+            .into_owned()
+            .expect("detaching the texture from its stream");
+
+        let data_size = std::thread::spawn(move || {
+            owned_texture.data_mut().fill(0x42);
+            assert!(owned_texture.ktx2().is_some());
+            assert!(owned_texture.data().iter().all(|&byte| byte == 0x42));
+            owned_texture.data_size()
+        })
+        .join()
+        .expect("the worker thread to succeed");
+        assert_eq!(data_size, texture.data_size());
+    }
+
+    #[test]
+    fn set_image() {
+        let mut texture = Texture::new(Ktx2CreateInfo::default()).expect("a default KTX2 texture");
+        texture
+            .set_image(0, 0, 0, &[10, 20, 30, 40])
+            .expect("setting the only image in the texture");
+        assert_eq!(texture.data(), &[10, 20, 30, 40]);
+
+        assert_eq!(
+            texture.set_image(0, 0, 0, &[1, 2, 3]),
+            Err(KtxError::InvalidValue)
+        );
+        assert_eq!(
+            texture.set_image(0, 0, 0, &[1, 2, 3, 4, 5]),
+            Err(KtxError::InvalidValue)
+        );
+
+        let written_texture = write_and_check(&texture);
+        assert_eq!(written_texture.data(), &[10, 20, 30, 40]);
+    }
 }